@@ -1,14 +1,22 @@
+//! Feldman's verifiable secret sharing over any [`Group`]. The group used
+//! to be fixed to the multiplicative group of [`Fq`], with verification
+//! points `[Fq; K]`; they are now `[G; K]` for a fourth type parameter `G`
+//! of [`FeldmanVSS`], which defaults to [`FqMultiplicativeGroup`] wrapping
+//! the former values.
+
 use std::{
     fmt::Debug,
-    ops::{Mul, Sub},
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Neg, Sub},
 };
 
 use ark_ff::fields::{Field, Fp64, MontBackend, MontConfig};
+use elliptic_curves::Group;
+use finite_fields::PrimeField;
 use num_traits::{One, Zero};
 use polynomial::Polynomial;
 use rand::{RngCore, SeedableRng};
 use shamir_secret_sharing::SecretShare;
-use simba::scalar::ComplexField;
 
 /// Feldman's Verifiable secret sharing is very close to
 /// Shamir's secret sharing: an ideal and perfect and ideal
@@ -16,11 +24,15 @@ use simba::scalar::ComplexField;
 /// with each secret share being verifiable.
 /// Here, `n` is the number of secret parts generated by the
 /// scheme, knowledge of any `k` of which can reconstruct original
-/// secret
-pub struct FeldmanVSS<T, const K: usize, const N: usize> {
+/// secret.
+/// Coefficients are committed to as multiples `[a_i]G` of the generator
+/// of a group `G` where discrete-log is hard, by default the
+/// multiplicative group of `Fq`.
+pub struct FeldmanVSS<T, const K: usize, const N: usize, G: Group = FqMultiplicativeGroup> {
     /// The underlying secret value to be broken up
     /// into secret "parts"
     secret: T,
+    _group: PhantomData<G>,
 }
 
 pub trait Roundable {
@@ -33,13 +45,65 @@ impl Roundable for f64 {
     }
 }
 
+/// Canonical representative in `[0, P)`. Commitments `[a]G` to elements
+/// of `F_P` are consistent as long as `G` has order `P`.
+impl<const P: u64> Roundable for PrimeField<P> {
+    fn round_to_nearest_integer(&self) -> u64 {
+        self.value()
+    }
+}
+
 #[derive(MontConfig)]
 #[modulus = "99679"]
 #[generator = "13"]
 pub struct FqConfig;
 pub type Fq = Fp64<MontBackend<FqConfig, 1>>;
 
-impl<T, const K: usize, const N: usize> FeldmanVSS<T, K, N>
+/// The multiplicative group of `Fq` written additively, so that
+/// `[k]G` is the exponentiation `13^k`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FqMultiplicativeGroup(pub Fq);
+
+impl Group for FqMultiplicativeGroup {
+    fn identity() -> Self {
+        Self(Fq::one())
+    }
+
+    fn generator() -> Self {
+        Self(Fq::from(13))
+    }
+
+    fn mul_bigint<S: AsRef<[u64]>>(&self, scalar: S) -> Self {
+        Self(self.0.pow(scalar))
+    }
+}
+
+impl Add for FqMultiplicativeGroup {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl Neg for FqMultiplicativeGroup {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(Field::inverse(&self.0).expect("group elements are non-zero"))
+    }
+}
+
+impl Sub for FqMultiplicativeGroup {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl<T, const K: usize, const N: usize, G: Group> FeldmanVSS<T, K, N, G>
 where
     T: From<u32>
         + Debug
@@ -48,38 +112,40 @@ where
         + One
         + Mul<Output = T>
         + Sub<Output = T>
-        + ComplexField
+        + Div<Output = T>
         + Roundable,
 {
     pub fn new_from_secret(secret: T) -> Self {
-        Self { secret }
+        Self {
+            secret,
+            _group: PhantomData,
+        }
     }
 
     pub fn new_from_verified_shares(
         shares: [SecretShare<T>; K],
-        verification_points: [Fq; K],
+        verification_points: [G; K],
     ) -> Self {
         let evaluations: Vec<(T, T)> = shares.iter().map(|s| s.into_tuple()).collect();
-        let reconstructed_poly = Polynomial::new_from_evals(&evaluations);
+        let reconstructed_poly = Polynomial::new_from_evals_lagrange(&evaluations);
 
         shares.iter().for_each(|share| {
-            let mut validating_point = Fq::one();
+            let mut validating_point = G::identity();
             for (exponent, point) in verification_points.iter().enumerate() {
                 validating_point = validating_point
-                    * point.pow(&[share
-                        .evaluation_point
-                        .clone()
-                        .powi(exponent as i32)
+                    + point.mul_bigint([(0..exponent)
+                        .fold(T::one(), |power, _| power * share.evaluation_point.clone())
                         .round_to_nearest_integer()])
             }
             assert_eq!(
-                Fq::from(13).pow(&[share.opening.round_to_nearest_integer()]),
+                G::generator().mul_bigint([share.opening.round_to_nearest_integer()]),
                 validating_point
             );
         });
 
         Self {
             secret: reconstructed_poly.eval(T::zero()),
+            _group: PhantomData,
         }
     }
 
@@ -91,7 +157,7 @@ where
     /// Generate `n` secret shares alongwith their polynomial
     /// coefficient as exponents over a finite cyclic group generator,
     /// called verification points.
-    pub fn generate_secret_shares(&self) -> ([SecretShare<T>; N], [Fq; K]) {
+    pub fn generate_secret_shares(&self) -> ([SecretShare<T>; N], [G; K]) {
         // first, generate a polynomial of form `f(x) = s + a*x + b*x^2 + ...`
         // where `s` is the encoded secret value, `a`, `b`... are random
         // coefficients and degree of `f(x)` is `K-1`.
@@ -102,9 +168,9 @@ where
             .chain((1..K).map(|_| T::from(rng.next_u32() % 100)))
             .collect();
 
-        let verification_points: Vec<Fq> = coefficients
+        let verification_points: Vec<G> = coefficients
             .iter()
-            .map(|x| Fq::from(13).pow(&[x.round_to_nearest_integer()]))
+            .map(|x| G::generator().mul_bigint([x.round_to_nearest_integer()]))
             .collect();
 
         let polynomial = Polynomial::new_from_coeffs(&coefficients);
@@ -133,6 +199,9 @@ where
 
 #[cfg(test)]
 mod tests {
+    use elliptic_curves::{short_weierstrass::Jacobian, toy::Toy10007};
+    use finite_fields::PrimeField;
+
     use crate::FeldmanVSS;
 
     #[test]
@@ -149,4 +218,50 @@ mod tests {
 
         assert_eq!(reconstructed_from_shares.get_secret() as i32, secret_value)
     }
+
+    #[test]
+    fn feldman_verifyable_secret_sharing_toy_curve() {
+        type Vss = FeldmanVSS<f64, 3, 5, Jacobian<Toy10007>>;
+        let secret_value = 7;
+
+        let (shares, verification_points) =
+            Vss::new_from_secret(secret_value.into()).generate_secret_shares();
+
+        let given_shares = [shares[1].clone(), shares[3].clone(), shares[4].clone()];
+        let reconstructed_from_shares =
+            Vss::new_from_verified_shares(given_shares, verification_points);
+
+        assert_eq!(
+            reconstructed_from_shares.get_secret().round() as i32,
+            secret_value
+        )
+    }
+
+    #[test]
+    #[should_panic]
+    fn feldman_rejects_tampered_share() {
+        type Vss = FeldmanVSS<f64, 2, 4, Jacobian<Toy10007>>;
+
+        let (mut shares, verification_points) = Vss::new_from_secret(5.0).generate_secret_shares();
+        shares[0].opening += 1.0;
+
+        Vss::new_from_verified_shares([shares[0].clone(), shares[1].clone()], verification_points);
+    }
+
+    #[test]
+    fn feldman_verifyable_secret_sharing_prime_field() {
+        // Shares over the scalar field of the toy curve, whose generator
+        // has order 10039
+        type Fr = PrimeField<10039>;
+        type Vss = FeldmanVSS<Fr, 3, 5, Jacobian<Toy10007>>;
+
+        let (shares, verification_points) =
+            Vss::new_from_secret(Fr::new(9876)).generate_secret_shares();
+
+        let given_shares = [shares[0].clone(), shares[2].clone(), shares[4].clone()];
+        let reconstructed_from_shares =
+            Vss::new_from_verified_shares(given_shares, verification_points);
+
+        assert_eq!(reconstructed_from_shares.get_secret(), Fr::new(9876))
+    }
 }
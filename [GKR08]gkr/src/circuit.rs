@@ -0,0 +1,170 @@
+//! Layered arithmetic circuits. Layer 0 holds the outputs, and every gate of
+//! layer `i` adds or multiplies two values of layer `i + 1`; the gates of
+//! the last layer read the inputs. Each layer is padded with zeros to a
+//! power of two, so that its values are a multilinear polynomial on a
+//! hypercube: a padding position has no gate and the wiring predicates
+//! vanish there.
+
+use finite_fields::Field;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GateKind {
+    Add,
+    Mul,
+}
+
+/// A gate and the positions of its operands in the next layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gate {
+    pub kind: GateKind,
+    pub left: usize,
+    pub right: usize,
+}
+
+impl Gate {
+    pub fn add(left: usize, right: usize) -> Self {
+        Self {
+            kind: GateKind::Add,
+            left,
+            right,
+        }
+    }
+
+    pub fn mul(left: usize, right: usize) -> Self {
+        Self {
+            kind: GateKind::Mul,
+            left,
+            right,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayeredCircuit {
+    layers: Vec<Vec<Gate>>,
+    num_inputs: usize,
+}
+
+/// Number of variables of a layer of `size` values
+fn num_vars(size: usize) -> usize {
+    size.next_power_of_two().trailing_zeros() as usize
+}
+
+impl LayeredCircuit {
+    /// The circuit with the given layers of gates, from the outputs down to
+    /// the layer reading the `num_inputs` inputs
+    pub fn new(layers: Vec<Vec<Gate>>, num_inputs: usize) -> Self {
+        assert!(
+            !layers.is_empty() && layers.iter().all(|layer| !layer.is_empty()) && num_inputs > 0,
+            "no empty layer"
+        );
+        let sizes = layers.iter().skip(1).map(Vec::len).chain([num_inputs]);
+        for (layer, size) in layers.iter().zip(sizes) {
+            assert!(
+                layer
+                    .iter()
+                    .all(|gate| gate.left < size && gate.right < size),
+                "gates read the next layer"
+            );
+        }
+        Self { layers, num_inputs }
+    }
+
+    /// Multiplies `2^depth` inputs pairwise, then the products pairwise
+    /// and so on, down to a single output. `depth` must be positive, as a
+    /// circuit has at least one layer of gates.
+    pub fn product_tree(depth: usize) -> Self {
+        assert!(depth > 0, "a product tree has at least one layer");
+        let layers = (0..depth)
+            .map(|i| (0..1 << i).map(|g| Gate::mul(2 * g, 2 * g + 1)).collect())
+            .collect();
+        Self::new(layers, 1 << depth)
+    }
+
+    /// The layers of gates, from the outputs down
+    pub fn layers(&self) -> &[Vec<Gate>] {
+        &self.layers
+    }
+
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    pub fn num_outputs(&self) -> usize {
+        self.layers[0].len()
+    }
+
+    /// Number of variables of layer `i`, where the inputs are layer
+    /// `layers().len()`
+    pub fn num_vars(&self, i: usize) -> usize {
+        match self.layers.get(i) {
+            Some(layer) => num_vars(layer.len()),
+            None => num_vars(self.num_inputs),
+        }
+    }
+
+    /// The padded values of every layer, from the outputs down to the
+    /// inputs
+    pub fn evaluate<F: Field>(&self, inputs: &[F]) -> Vec<Vec<F>> {
+        assert_eq!(inputs.len(), self.num_inputs, "one value per input");
+        let pad = |mut values: Vec<F>| {
+            values.resize(values.len().next_power_of_two(), F::zero());
+            values
+        };
+        let mut values = vec![pad(inputs.to_vec())];
+        for layer in self.layers.iter().rev() {
+            let next = values.last().expect("the inputs are there");
+            let current = layer
+                .iter()
+                .map(|gate| match gate.kind {
+                    GateKind::Add => next[gate.left] + next[gate.right],
+                    GateKind::Mul => next[gate.left] * next[gate.right],
+                })
+                .collect();
+            values.push(pad(current));
+        }
+        values.reverse();
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+
+    type Fq = PrimeField<2147483647>;
+
+    #[test]
+    fn layers_are_padded_with_zeros() {
+        // (a + b) c d and c d + 2 d from the inputs a, b, c, d
+        let circuit = LayeredCircuit::new(
+            vec![
+                vec![Gate::mul(0, 1), Gate::add(1, 2)],
+                vec![Gate::add(0, 1), Gate::mul(2, 3), Gate::add(3, 3)],
+            ],
+            4,
+        );
+        let values = circuit.evaluate(&[1, 2, 3, 4].map(Fq::from_u64));
+        let expected = [vec![36, 20], vec![3, 12, 8, 0], vec![1, 2, 3, 4]]
+            .map(|layer| layer.into_iter().map(Fq::from_u64).collect::<Vec<_>>());
+        assert_eq!(values, expected);
+        assert_eq!(
+            (0..3).map(|i| circuit.num_vars(i)).collect::<Vec<_>>(),
+            [1, 2, 2]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "gates read the next layer")]
+    fn gates_cannot_skip_layers() {
+        LayeredCircuit::new(vec![vec![Gate::add(0, 2)], vec![Gate::mul(0, 1)]], 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "a product tree has at least one layer")]
+    fn product_trees_have_a_layer() {
+        LayeredCircuit::product_tree(0);
+    }
+}
@@ -0,0 +1,310 @@
+//! The GKR protocol of Goldwasser, Kalai and Rothblum for layered
+//! arithmetic circuits. With `V_i` the multilinear extension of the values
+//! of layer `i`, every layer satisfies
+//!
+//! `V_i(z) = Σ_(b, c) add_i(z, b, c) (V_(i+1)(b) + V_(i+1)(c)) + mul_i(z, b, c) V_(i+1)(b) V_(i+1)(c)`
+//!
+//! over the hypercube of layer `i + 1`, where the wiring predicates
+//! `add_i(g, b, c)` and `mul_i(g, b, c)` are one exactly when gate `g` adds
+//! or multiplies `b` and `c`. The verifier starts from `V_0(r)` at a random
+//! `r`, computed from the outputs. A sumcheck over `(b, c)` reduces a claim
+//! on `V_i` to the two claims `V_(i+1)(r_b)` and `V_(i+1)(r_c)`, which the
+//! prover states. The verifier folds them into `α V_(i+1)(r_b) + β V_(i+1)(r_c)`
+//! for random `α`, `β`, which is the same sum with the wiring predicates
+//! `α add_(i+1)(r_b, ·) + β add_(i+1)(r_c, ·)` and likewise for `mul`, so one
+//! sumcheck per layer suffices. At the inputs the verifier checks
+//! both claims against the multilinear extension of the inputs itself.
+//!
+//! The verifier evaluates the wiring predicates gate by gate, in time
+//! linear in the size of the circuit. The prover writes them out densely
+//! on the `4^k` points `(b, c)` of the layer below, which keeps it to the
+//! sumcheck of `multivariate_sumcheck` and suits small layers.
+
+pub mod circuit;
+
+use fiat_shamir::Transcript;
+use finite_fields::Field;
+use multivariate_sumcheck::{
+    multilinear::MultilinearPolynomial,
+    sumcheck::{SumcheckProof, SumcheckProver, SumcheckVerifier},
+};
+
+use crate::circuit::{GateKind, LayeredCircuit};
+
+const LABEL: &[u8] = b"gkr";
+
+/// Degree of the summand in the variables `(b, c)`: a product of a wiring
+/// predicate with `V(b)` and `V(c)`, which share no variable
+const DEGREE: usize = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GkrError {
+    WrongInputLength { expected: usize, actual: usize },
+}
+
+/// The sumcheck of a layer and the claimed values `V_(i+1)(r_b)`,
+/// `V_(i+1)(r_c)` on the layer below
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerProof<F> {
+    pub sumcheck: SumcheckProof<F>,
+    pub left: F,
+    pub right: F,
+}
+
+/// One [`LayerProof`] per layer of gates, from the outputs down
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GkrProof<F> {
+    pub layers: Vec<LayerProof<F>>,
+}
+
+/// Points `r_j` and coefficients `α_j` of the claims on a layer, which
+/// stand for `Σ_j α_j V(r_j)`
+type Claims<F> = Vec<(Vec<F>, F)>;
+
+fn transcript<F: Field>(inputs: &[F], outputs: &[F]) -> Transcript {
+    let mut transcript = Transcript::new(LABEL);
+    for input in inputs {
+        transcript.append_field(b"input", input);
+    }
+    for output in outputs {
+        transcript.append_field(b"output", output);
+    }
+    transcript
+}
+
+/// `Σ_j α_j eq(r_j, g)` for every position `g` of a layer
+fn weights<F: Field>(claims: &Claims<F>, num_vars: usize) -> Vec<F> {
+    let mut weights = vec![F::zero(); 1 << num_vars];
+    for (point, coefficient) in claims {
+        let eq = MultilinearPolynomial::eq(point);
+        for (weight, value) in weights.iter_mut().zip(eq.evaluations()) {
+            *weight = *weight + *coefficient * *value;
+        }
+    }
+    weights
+}
+
+/// Folds the claims `V(r_b)`, `V(r_c)` on the next layer with random `α`,
+/// `β`, drawn after both values
+fn next_claims<F: Field>(
+    transcript: &mut Transcript,
+    point: &[F],
+    proof: &LayerProof<F>,
+) -> (Claims<F>, F) {
+    transcript.append_field(b"left", &proof.left);
+    transcript.append_field(b"right", &proof.right);
+    let alpha: F = transcript.challenge(b"alpha");
+    let beta: F = transcript.challenge(b"beta");
+    let (left, right) = point.split_at(point.len() / 2);
+    let claims = vec![(left.to_vec(), alpha), (right.to_vec(), beta)];
+    (claims, alpha * proof.left + beta * proof.right)
+}
+
+/// The summand at `[add, mul, V(b), V(c)]`
+fn summand<F: Field>(values: &[F]) -> F {
+    let [add, mul, left, right] = [0, 1, 2, 3].map(|i| values[i]);
+    add * (left + right) + mul * left * right
+}
+
+/// Evaluates the circuit on `inputs` and proves its outputs, which are
+/// returned without padding
+pub fn prove<F: Field>(
+    circuit: &LayeredCircuit,
+    inputs: &[F],
+) -> Result<(Vec<F>, GkrProof<F>), GkrError> {
+    if inputs.len() != circuit.num_inputs() {
+        return Err(GkrError::WrongInputLength {
+            expected: circuit.num_inputs(),
+            actual: inputs.len(),
+        });
+    }
+    let values = circuit.evaluate(inputs);
+    let outputs = values[0][..circuit.num_outputs()].to_vec();
+    let mut transcript = transcript(inputs, &outputs);
+    let point = (0..circuit.num_vars(0))
+        .map(|_| transcript.challenge(b"r"))
+        .collect();
+    let mut claims = vec![(point, F::one())];
+
+    let mut layers = Vec::with_capacity(circuit.layers().len());
+    for (i, gates) in circuit.layers().iter().enumerate() {
+        let weights = weights(&claims, circuit.num_vars(i));
+        let next = &values[i + 1];
+        let size = next.len();
+        let mut add = vec![F::zero(); size * size];
+        let mut mul = vec![F::zero(); size * size];
+        for (gate, weight) in gates.iter().zip(weights) {
+            let table = match gate.kind {
+                GateKind::Add => &mut add,
+                GateKind::Mul => &mut mul,
+            };
+            let entry = &mut table[gate.left * size + gate.right];
+            *entry = *entry + weight;
+        }
+        let left = next.iter().flat_map(|b| vec![*b; size]).collect();
+        let right = (0..size).flat_map(|_| next.iter().copied()).collect();
+        let prover = SumcheckProver::new(
+            [add, mul, left, right]
+                .map(MultilinearPolynomial::new)
+                .to_vec(),
+            DEGREE,
+            summand,
+        );
+        let (sumcheck, point) = prover.prove(&mut transcript);
+
+        let next = MultilinearPolynomial::new(next.clone());
+        let (b, c) = point.split_at(point.len() / 2);
+        let proof = LayerProof {
+            sumcheck,
+            left: next.evaluate(b),
+            right: next.evaluate(c),
+        };
+        (claims, _) = next_claims(&mut transcript, &point, &proof);
+        layers.push(proof);
+    }
+    Ok((outputs, GkrProof { layers }))
+}
+
+/// Checks one sumcheck per layer, evaluating the wiring predicates at the
+/// random points, and the last two claims against the inputs
+pub fn verify<F: Field>(
+    circuit: &LayeredCircuit,
+    inputs: &[F],
+    outputs: &[F],
+    proof: &GkrProof<F>,
+) -> bool {
+    if inputs.len() != circuit.num_inputs()
+        || outputs.len() != circuit.num_outputs()
+        || proof.layers.len() != circuit.layers().len()
+    {
+        return false;
+    }
+    let pad = |values: &[F]| {
+        let mut values = values.to_vec();
+        values.resize(values.len().next_power_of_two(), F::zero());
+        MultilinearPolynomial::new(values)
+    };
+    let mut transcript = transcript(inputs, outputs);
+    let point: Vec<F> = (0..circuit.num_vars(0))
+        .map(|_| transcript.challenge(b"r"))
+        .collect();
+    let mut claim = pad(outputs).evaluate(&point);
+    let mut claims = vec![(point, F::one())];
+
+    for (i, (gates, layer)) in circuit.layers().iter().zip(&proof.layers).enumerate() {
+        let num_vars = circuit.num_vars(i + 1);
+        let Some(subclaim) = SumcheckVerifier::new(2 * num_vars, DEGREE).verify(
+            &mut transcript,
+            claim,
+            &layer.sumcheck,
+        ) else {
+            return false;
+        };
+        let (b, c) = subclaim.point.split_at(num_vars);
+        let (eq_b, eq_c) = (MultilinearPolynomial::eq(b), MultilinearPolynomial::eq(c));
+        let (mut add, mut mul) = (F::zero(), F::zero());
+        for (gate, weight) in gates.iter().zip(weights(&claims, circuit.num_vars(i))) {
+            let wiring = weight * eq_b.evaluations()[gate.left] * eq_c.evaluations()[gate.right];
+            match gate.kind {
+                GateKind::Add => add = add + wiring,
+                GateKind::Mul => mul = mul + wiring,
+            }
+        }
+        if summand(&[add, mul, layer.left, layer.right]) != subclaim.value {
+            return false;
+        }
+        (claims, claim) = next_claims(&mut transcript, &subclaim.point, layer);
+    }
+
+    // The last layer reads the inputs, which the verifier has
+    let inputs = pad(inputs);
+    let last = &proof.layers[proof.layers.len() - 1];
+    inputs.evaluate(&claims[0].0) == last.left && inputs.evaluate(&claims[1].0) == last.right
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+    use crate::circuit::Gate;
+
+    /// The Goldilocks field of plonky2
+    type Fq = PrimeField<0xffff_ffff_0000_0001>;
+
+    /// `x · 2 · 3 ⋯ 100`, as in plonky2-factorial, by a product tree of
+    /// depth 7 over `x, 2, ..., 100` padded with ones
+    fn factorial_inputs(x: u64) -> Vec<Fq> {
+        let mut inputs: Vec<Fq> = [x].into_iter().chain(2..101).map(Fq::from_u64).collect();
+        inputs.resize(128, Fq::from_u64(1));
+        inputs
+    }
+
+    #[test]
+    fn factorial_is_proved() {
+        let circuit = LayeredCircuit::product_tree(7);
+        let inputs = factorial_inputs(3);
+        let (outputs, proof) = prove(&circuit, &inputs).unwrap();
+        let expected = (2..101).fold(Fq::from_u64(3), |acc, i| acc * Fq::from_u64(i));
+        assert_eq!(outputs, [expected]);
+        assert!(verify(&circuit, &inputs, &outputs, &proof));
+
+        // One sumcheck per layer, over both operands
+        let rounds: Vec<usize> = proof
+            .layers
+            .iter()
+            .map(|layer| layer.sumcheck.rounds.len())
+            .collect();
+        assert_eq!(rounds, [2, 4, 6, 8, 10, 12, 14]);
+
+        assert!(!verify(
+            &circuit,
+            &inputs,
+            &[expected + Fq::from_u64(1)],
+            &proof
+        ));
+        assert!(!verify(&circuit, &factorial_inputs(4), &outputs, &proof));
+    }
+
+    #[test]
+    fn tampered_proofs_are_rejected() {
+        // (a + b) c d and c d + 2 d
+        let circuit = LayeredCircuit::new(
+            vec![
+                vec![Gate::mul(0, 1), Gate::add(1, 2)],
+                vec![Gate::add(0, 1), Gate::mul(2, 3), Gate::add(3, 3)],
+            ],
+            4,
+        );
+        let inputs = [1, 2, 3, 4].map(Fq::from_u64);
+        let (outputs, proof) = prove(&circuit, &inputs).unwrap();
+        assert_eq!(outputs, [36, 20].map(Fq::from_u64));
+        assert!(verify(&circuit, &inputs, &outputs, &proof));
+
+        // A wrong value on the middle layer fails its sumcheck or the next
+        let mut tampered = proof.clone();
+        tampered.layers[0].left += Fq::from_u64(1);
+        assert!(!verify(&circuit, &inputs, &outputs, &tampered));
+        let mut tampered = proof.clone();
+        tampered.layers[1].sumcheck.rounds[0][0] += Fq::from_u64(1);
+        assert!(!verify(&circuit, &inputs, &outputs, &tampered));
+
+        // Another wiring with the same shape
+        let other = LayeredCircuit::new(
+            vec![
+                vec![Gate::mul(0, 1), Gate::add(1, 2)],
+                vec![Gate::add(0, 1), Gate::mul(2, 3), Gate::add(2, 3)],
+            ],
+            4,
+        );
+        assert!(!verify(&other, &inputs, &outputs, &proof));
+
+        assert_eq!(
+            prove(&circuit, &inputs[..3]).unwrap_err(),
+            GkrError::WrongInputLength {
+                expected: 4,
+                actual: 3
+            }
+        );
+    }
+}
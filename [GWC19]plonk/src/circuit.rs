@@ -0,0 +1,351 @@
+//! Circuits of PLONK's arithmetic gates. Every row `i` of the circuit is
+//! one gate on the three wires `a_i`, `b_i`, `c_i`,
+//!
+//! `q_L a_i + q_R b_i + q_O c_i + q_M a_i b_i + q_C + PI_i = 0`,
+//!
+//! and wires carrying the same variable are linked by copy constraints.
+//! Public inputs occupy the first rows, each as the gate `a_i - x_i = 0`
+//! with `PI_i = -x_i`.
+
+use finite_fields::Field;
+use univariate_polynomial_iop_zerotest::domain::Domain;
+
+/// A value of the witness, referred to by the wires that carry it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Variable(usize);
+
+/// The selectors of one row and the variables on its wires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gate<F> {
+    pub q_l: F,
+    pub q_r: F,
+    pub q_o: F,
+    pub q_m: F,
+    pub q_c: F,
+    pub wires: [Variable; 3],
+}
+
+/// Allocates variables and gates, public inputs first
+#[derive(Debug, Clone)]
+pub struct CircuitBuilder<F> {
+    variables: usize,
+    public: Vec<Variable>,
+    gates: Vec<Gate<F>>,
+}
+
+impl<F: Field> Default for CircuitBuilder<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Field> CircuitBuilder<F> {
+    pub fn new() -> Self {
+        Self {
+            variables: 0,
+            public: vec![],
+            gates: vec![],
+        }
+    }
+
+    pub fn variable(&mut self) -> Variable {
+        self.variables += 1;
+        Variable(self.variables - 1)
+    }
+
+    /// A variable whose value the verifier knows
+    pub fn public_input(&mut self) -> Variable {
+        let variable = self.variable();
+        self.public.push(variable);
+        variable
+    }
+
+    pub fn gate(&mut self, gate: Gate<F>) {
+        assert!(
+            gate.wires.iter().all(|wire| wire.0 < self.variables),
+            "wires must carry allocated variables"
+        );
+        self.gates.push(gate);
+    }
+
+    /// `c = a · b`
+    pub fn mul(&mut self, a: Variable, b: Variable) -> Variable {
+        let c = self.variable();
+        self.gate(Gate {
+            q_l: F::zero(),
+            q_r: F::zero(),
+            q_o: -F::one(),
+            q_m: F::one(),
+            q_c: F::zero(),
+            wires: [a, b, c],
+        });
+        c
+    }
+
+    /// `c = a + b`
+    pub fn add(&mut self, a: Variable, b: Variable) -> Variable {
+        let c = self.variable();
+        self.gate(Gate {
+            q_l: F::one(),
+            q_r: F::one(),
+            q_o: -F::one(),
+            q_m: F::zero(),
+            q_c: F::zero(),
+            wires: [a, b, c],
+        });
+        c
+    }
+
+    /// A variable fixed to `value` by the circuit itself
+    pub fn constant(&mut self, value: F) -> Variable {
+        let a = self.variable();
+        self.gate(Gate {
+            q_l: F::one(),
+            q_r: F::zero(),
+            q_o: F::zero(),
+            q_m: F::zero(),
+            q_c: -value,
+            wires: [a, a, a],
+        });
+        a
+    }
+
+    /// Lays the gates out on `domain`, padding with empty gates on a
+    /// variable of its own
+    pub fn build(self, domain: Domain<F>) -> Circuit<F> {
+        let rows = self.public.len() + self.gates.len();
+        let n = domain.size();
+        assert!(
+            rows <= n,
+            "{rows} gates do not fit into a domain of size {n}"
+        );
+        let padding = Variable(self.variables);
+        let zero = F::zero();
+        let public = self.public.iter().map(|variable| Gate {
+            q_l: F::one(),
+            q_r: zero,
+            q_o: zero,
+            q_m: zero,
+            q_c: zero,
+            wires: [*variable; 3],
+        });
+        let empty = Gate {
+            q_l: zero,
+            q_r: zero,
+            q_o: zero,
+            q_m: zero,
+            q_c: zero,
+            wires: [padding; 3],
+        };
+        let gates: Vec<Gate<F>> = public
+            .chain(self.gates)
+            .chain(std::iter::repeat(empty))
+            .take(n)
+            .collect();
+        Circuit {
+            domain,
+            variables: self.variables,
+            public: self.public,
+            gates,
+        }
+    }
+}
+
+/// `c = k a^2 b^2` with `c` public, as `(ab)^2` and then `k (ab)^2`, the
+/// statement of halo2's introductory example. The variables are `c`, `a`,
+/// `b`, `k`, `ab` and `(ab)^2`, in this order.
+pub fn scaled_square_product<F: Field>(k: F, domain: Domain<F>) -> Circuit<F> {
+    let mut builder = CircuitBuilder::new();
+    let c = builder.public_input();
+    let a = builder.variable();
+    let b = builder.variable();
+    let k = builder.constant(k);
+    let ab = builder.mul(a, b);
+    let absq = builder.mul(ab, ab);
+    builder.gate(Gate {
+        q_l: F::zero(),
+        q_r: F::zero(),
+        q_o: -F::one(),
+        q_m: F::one(),
+        q_c: F::zero(),
+        wires: [k, absq, c],
+    });
+    builder.build(domain)
+}
+
+/// The witness of [`scaled_square_product`] computed from `a` and `b`
+pub fn scaled_square_product_witness<F: Field>(k: F, a: F, b: F) -> Vec<F> {
+    let ab = a * b;
+    let absq = ab.square();
+    vec![k * absq, a, b, k, ab, absq]
+}
+
+/// A circuit laid out on the rows of a domain
+#[derive(Debug, Clone)]
+pub struct Circuit<F> {
+    domain: Domain<F>,
+    variables: usize,
+    public: Vec<Variable>,
+    gates: Vec<Gate<F>>,
+}
+
+/// `1, k_1, k_2` with `k_1 H` and `k_2 H` cosets of `H` distinct from each
+/// other and from `H`, so that the wires of the three columns get distinct
+/// labels `k_j ω^i`. Any generator of the multiplicative group works for
+/// fields of two-adic order, such as 7 for the scalars of BLS12-381.
+pub fn coset_shifts<F: Field>(domain: &Domain<F>) -> [F; 3] {
+    let k1 = F::from_u64(7);
+    let k2 = k1.square();
+    let n = [domain.size() as u64];
+    assert!(
+        k1.pow(n) != F::one() && k2.pow(n) != F::one() && k1.pow(n) != k2.pow(n),
+        "7 and 49 do not give distinct cosets"
+    );
+    [F::one(), k1, k2]
+}
+
+impl<F: Field> Circuit<F> {
+    pub fn domain(&self) -> &Domain<F> {
+        &self.domain
+    }
+
+    /// Number of variables a witness assigns
+    pub fn num_variables(&self) -> usize {
+        self.variables
+    }
+
+    pub fn num_public(&self) -> usize {
+        self.public.len()
+    }
+
+    /// The columns `q_L`, `q_R`, `q_O`, `q_M`, `q_C`
+    pub fn selectors(&self) -> [Vec<F>; 5] {
+        let column = |select: fn(&Gate<F>) -> F| self.gates.iter().map(select).collect();
+        [
+            column(|gate| gate.q_l),
+            column(|gate| gate.q_r),
+            column(|gate| gate.q_o),
+            column(|gate| gate.q_m),
+            column(|gate| gate.q_c),
+        ]
+    }
+
+    /// The columns `a`, `b`, `c` for a witness with one value per variable.
+    /// The padding variable is zero.
+    pub fn wire_values(&self, witness: &[F]) -> [Vec<F>; 3] {
+        assert_eq!(witness.len(), self.variables, "one value per variable");
+        let value = |variable: Variable| witness.get(variable.0).copied().unwrap_or(F::zero());
+        [0, 1, 2].map(|j| self.gates.iter().map(|gate| value(gate.wires[j])).collect())
+    }
+
+    /// The values of the public inputs in the witness
+    pub fn public_values(&self, witness: &[F]) -> Vec<F> {
+        self.public
+            .iter()
+            .map(|variable| witness[variable.0])
+            .collect()
+    }
+
+    /// The columns `S_σ1`, `S_σ2`, `S_σ3`: the wire at column `j` and row
+    /// `i` is labelled `k_j ω^i`, and `σ` sends it to the next wire
+    /// carrying the same variable, cycling through all of them
+    pub fn permutation(&self) -> [Vec<F>; 3] {
+        let n = self.domain.size();
+        let shifts = coset_shifts(&self.domain);
+        let elements = self.domain.elements();
+        let label = |position: usize| shifts[position / n] * elements[position % n];
+
+        let mut cycles: Vec<Vec<usize>> = vec![vec![]; self.variables + 1];
+        for j in 0..3 {
+            for (i, gate) in self.gates.iter().enumerate() {
+                cycles[gate.wires[j].0].push(j * n + i);
+            }
+        }
+        let mut sigma = vec![F::zero(); 3 * n];
+        for cycle in cycles.iter().filter(|cycle| !cycle.is_empty()) {
+            for (k, position) in cycle.iter().enumerate() {
+                sigma[*position] = label(cycle[(k + 1) % cycle.len()]);
+            }
+        }
+        [0, 1, 2].map(|j| sigma[j * n..(j + 1) * n].to_vec())
+    }
+
+    /// Checks every gate on the witness, returning the first failing row
+    pub fn is_satisfied(&self, witness: &[F]) -> Result<(), usize> {
+        let [a, b, c] = self.wire_values(witness);
+        let public = self.public_values(witness);
+        match self.gates.iter().enumerate().find(|(i, gate)| {
+            let pi = public.get(*i).map_or(F::zero(), |x| -*x);
+            gate.q_l * a[*i]
+                + gate.q_r * b[*i]
+                + gate.q_o * c[*i]
+                + gate.q_m * a[*i] * b[*i]
+                + gate.q_c
+                + pi
+                != F::zero()
+        }) {
+            Some((row, _)) => Err(row),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+
+    type Fq = PrimeField<41>;
+
+    /// `x + y = z` with `z` public and `y = 5`
+    fn circuit() -> (Circuit<Fq>, Vec<Fq>) {
+        let mut builder = CircuitBuilder::new();
+        let z = builder.public_input();
+        let x = builder.variable();
+        let y = builder.constant(Fq::from_u64(5));
+        let sum = builder.add(x, y);
+        builder.gate(Gate {
+            q_l: Fq::from_u64(1),
+            q_r: -Fq::from_u64(1),
+            q_o: Fq::from_u64(0),
+            q_m: Fq::from_u64(0),
+            q_c: Fq::from_u64(0),
+            wires: [sum, z, z],
+        });
+        let circuit = builder.build(Domain::new(Fq::from_u64(3), 8));
+        let witness = [12, 7, 5, 12].map(Fq::from_u64).to_vec();
+        (circuit, witness)
+    }
+
+    #[test]
+    fn satisfiability_reports_the_first_failing_row() {
+        let (circuit, mut witness) = circuit();
+        assert_eq!(circuit.is_satisfied(&witness), Ok(()));
+        assert_eq!(circuit.public_values(&witness), [Fq::from_u64(12)]);
+
+        witness[2] = Fq::from_u64(6);
+        assert_eq!(circuit.is_satisfied(&witness), Err(1));
+        witness[2] = Fq::from_u64(5);
+        witness[3] = Fq::from_u64(13);
+        assert_eq!(circuit.is_satisfied(&witness), Err(2));
+    }
+
+    #[test]
+    fn permutation_cycles_through_copies() {
+        let (circuit, _) = circuit();
+        let [k0, k1, k2] = coset_shifts(circuit.domain());
+        let omega = |i: u64| Fq::from_u64(3).pow([i]);
+        let [sigma_a, sigma_b, sigma_c] = circuit.permutation();
+
+        // `z` sits on a_0, b_0, c_0, b_3 and c_3, in this order
+        assert_eq!(sigma_a[0], k1 * omega(0));
+        assert_eq!(sigma_b[0], k1 * omega(3));
+        assert_eq!(sigma_b[3], k2 * omega(0));
+        assert_eq!(sigma_c[0], k2 * omega(3));
+        assert_eq!(sigma_c[3], k0 * omega(0));
+
+        // `x` is on a single wire, a fixed point of `σ`
+        assert_eq!(sigma_a[2], omega(2));
+    }
+}
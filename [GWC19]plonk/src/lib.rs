@@ -0,0 +1,261 @@
+//! PLONK [GWC19] with KZG commitments, following the rounds of the paper.
+//!
+//! After preprocessing commits to the selectors and the copy permutation of
+//! a [`circuit::Circuit`], the prover
+//!
+//! 1. commits to the blinded wire polynomials `a`, `b`, `c`,
+//! 2. receives `β`, `γ` and commits to the blinded accumulator `z` of the
+//!    permutation argument, `z(ω^(i+1)) = z(ω^i) Π_j (w_j + β k_j ω^i + γ) /
+//!    (w_j + β S_σj(ω^i) + γ)` starting from `z(1) = 1`,
+//! 3. receives `α` and commits to the quotient `t` of the gate and
+//!    permutation constraints by `Z_H`, split into `t_lo`, `t_mid`, `t_hi`,
+//! 4. receives `ζ` and sends `a`, `b`, `c`, `S_σ1`, `S_σ2` at `ζ` and `z`
+//!    at `ζ ω`,
+//! 5. receives `v` and opens the linearisation `r`, in which everything
+//!    but `z`, `S_σ3`, the selectors and `t` is replaced by its value at
+//!    `ζ`, batched with the other polynomials at `ζ`, and `z` at `ζ ω`.
+//!
+//! The verifier rebuilds the commitment to `r` from the preprocessed ones
+//! and checks both openings with a single pairing equation after drawing
+//! `u`.
+
+use elliptic_curves::{encoding::field_to_bytes, pairing::Pairing, Encoding, PrimeOrderGroup};
+use fiat_shamir::Transcript;
+use finite_fields::Field;
+use polynomial::Polynomial;
+use polynomial_commitments::{
+    kzg::{Kzg, KzgCommitterKey},
+    PolynomialCommitmentScheme,
+};
+use univariate_polynomial_iop_zerotest::domain::Domain;
+
+pub mod circuit;
+mod prover;
+mod verifier;
+
+pub use prover::prove;
+pub use verifier::verify;
+
+type Scalar<E> = <<E as Pairing>::G1 as PrimeOrderGroup>::ScalarField;
+
+const LABEL: &[u8] = b"plonk";
+
+/// Why the prover refused to prove
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlonkError {
+    /// The witness violates the gate at this row
+    UnsatisfiedGate(usize),
+}
+
+/// What the prover needs besides the witness: the circuit and its
+/// preprocessed polynomials
+#[derive(Debug, Clone)]
+pub struct ProvingKey<E: Pairing> {
+    pub circuit: circuit::Circuit<Scalar<E>>,
+    /// `q_L`, `q_R`, `q_O`, `q_M`, `q_C`
+    pub selectors: [Polynomial<Scalar<E>>; 5],
+    /// `S_σ1`, `S_σ2`, `S_σ3`
+    pub permutation: [Polynomial<Scalar<E>>; 3],
+    pub vk: VerifyingKey<E>,
+}
+
+/// Commitments to the preprocessed polynomials
+#[derive(Debug, Clone)]
+pub struct VerifyingKey<E: Pairing> {
+    pub domain: Domain<Scalar<E>>,
+    pub num_public: usize,
+    pub selectors: [E::G1; 5],
+    pub permutation: [E::G1; 3],
+}
+
+/// The prover's evaluations in round 4
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Evaluations<F> {
+    pub a: F,
+    pub b: F,
+    pub c: F,
+    pub sigma_1: F,
+    pub sigma_2: F,
+    /// `z(ζ ω)`
+    pub z_shifted: F,
+}
+
+/// The commitments of rounds 1 to 3, the evaluations and the two KZG
+/// openings of round 5
+#[derive(Debug, Clone)]
+pub struct Proof<E: Pairing> {
+    /// `[a]_1`, `[b]_1`, `[c]_1`
+    pub wires: [E::G1; 3],
+    pub z: E::G1,
+    /// `[t_lo]_1`, `[t_mid]_1`, `[t_hi]_1`
+    pub quotient: [E::G1; 3],
+    pub evaluations: Evaluations<Scalar<E>>,
+    /// `W_ζ`
+    pub opening: E::G1,
+    /// `W_ζω`
+    pub shifted_opening: E::G1,
+}
+
+impl<E: Pairing> Proof<E>
+where
+    E::G1: Encoding,
+{
+    /// Bytes of the encoded points and field elements
+    pub fn size(&self) -> usize {
+        let points = self
+            .wires
+            .iter()
+            .chain([&self.z])
+            .chain(&self.quotient)
+            .chain([&self.opening, &self.shifted_opening])
+            .map(|point| point.to_bytes().len())
+            .sum::<usize>();
+        let e = &self.evaluations;
+        let scalars = [e.a, e.b, e.c, e.sigma_1, e.sigma_2, e.z_shifted]
+            .iter()
+            .map(|value| field_to_bytes(value).len())
+            .sum::<usize>();
+        points + scalars
+    }
+}
+
+/// Interpolates the selectors and the permutation of `circuit` and commits
+/// to them
+pub fn setup<E>(ck: &KzgCommitterKey<E>, circuit: circuit::Circuit<Scalar<E>>) -> ProvingKey<E>
+where
+    E: Pairing,
+{
+    let domain = *circuit.domain();
+    let selectors = circuit
+        .selectors()
+        .map(|column| domain.interpolate(&column));
+    let permutation = circuit
+        .permutation()
+        .map(|column| domain.interpolate(&column));
+    let vk = VerifyingKey {
+        domain,
+        num_public: circuit.num_public(),
+        selectors: [0, 1, 2, 3, 4].map(|i| Kzg::commit(ck, &selectors[i])),
+        permutation: [0, 1, 2].map(|i| Kzg::commit(ck, &permutation[i])),
+    };
+    ProvingKey {
+        circuit,
+        selectors,
+        permutation,
+        vk,
+    }
+}
+
+/// The challenges of all five rounds
+#[derive(Debug, Clone, Copy)]
+struct Challenges<F> {
+    beta: F,
+    gamma: F,
+    alpha: F,
+    zeta: F,
+    v: F,
+    u: F,
+}
+
+/// Replays the transcript over the verifying key, the public inputs and
+/// the proof. The prover draws the same challenges round by round.
+fn transcript<E>(vk: &VerifyingKey<E>, public: &[Scalar<E>]) -> Transcript
+where
+    E: Pairing,
+    E::G1: Encoding,
+{
+    let mut transcript = Transcript::new(LABEL);
+    transcript.append_statement(b"domain size", &(vk.domain.size() as u64).to_le_bytes());
+    for commitment in vk.selectors.iter().chain(&vk.permutation) {
+        transcript.append_statement(b"preprocessed", &commitment.to_bytes());
+    }
+    for value in public {
+        transcript.append_statement(b"public input", &field_to_bytes(value));
+    }
+    transcript
+}
+
+fn append_evaluations<F: Field>(transcript: &mut Transcript, evaluations: &Evaluations<F>) {
+    let Evaluations {
+        a,
+        b,
+        c,
+        sigma_1,
+        sigma_2,
+        z_shifted,
+    } = evaluations;
+    for value in [a, b, c, sigma_1, sigma_2, z_shifted] {
+        transcript.append_field(b"evaluation", value);
+    }
+}
+
+/// `L_0(ζ) = (ζ^n - 1) / (n (ζ - 1))` and `PI(ζ) = -Σ_i x_i L_i(ζ)` with
+/// `L_i(ζ) = ω^i (ζ^n - 1) / (n (ζ - ω^i))`
+fn lagrange_terms<F: Field>(domain: &Domain<F>, public: &[F], zeta: F) -> (F, F) {
+    let vanishing = domain.evaluate_vanishing_polynomial(zeta);
+    let n = F::from_u64(domain.size() as u64);
+    let lagrange = |power: F| match (n * (zeta - power)).inverse() {
+        Some(inverse) => power * vanishing * inverse,
+        None => F::one(),
+    };
+    let public_input = domain
+        .elements()
+        .into_iter()
+        .zip(public)
+        .fold(F::zero(), |acc, (power, x)| acc - *x * lagrange(power));
+    (lagrange(F::one()), public_input)
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_ff::FftField;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+
+    /// `c = k a^2 b^2` with `c` public and `k = 7`, the statement of
+    /// `MyCircuit` in halo2-trials
+    fn circuit() -> circuit::Circuit<Fr> {
+        let domain = Domain::new(Fr::get_root_of_unity(8).unwrap(), 8);
+        circuit::scaled_square_product(Fr::from(7u64), domain)
+    }
+
+    fn witness(a: u64, b: u64) -> Vec<Fr> {
+        circuit::scaled_square_product_witness(Fr::from(7u64), Fr::from(a), Fr::from(b))
+    }
+
+    #[test]
+    fn honest_proof_is_accepted() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (ck, kzg) = Kzg::<Bls12_381>::setup(13, &mut rng);
+        let pk = setup(&ck, circuit());
+        let proof = prove(&ck, &pk, &witness(2, 3), &mut rng).unwrap();
+        assert!(verify(&kzg, &pk.vk, &[Fr::from(252u64)], &proof));
+    }
+
+    #[test]
+    fn forgeries_are_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let (ck, kzg) = Kzg::<Bls12_381>::setup(13, &mut rng);
+        let pk = setup(&ck, circuit());
+        let proof = prove(&ck, &pk, &witness(2, 3), &mut rng).unwrap();
+
+        assert!(!verify(&kzg, &pk.vk, &[Fr::from(253u64)], &proof));
+        assert!(!verify(&kzg, &pk.vk, &[], &proof));
+        let mut tampered = proof.clone();
+        tampered.evaluations.a += Fr::from(1u64);
+        assert!(!verify(&kzg, &pk.vk, &[Fr::from(252u64)], &tampered));
+        let mut tampered = proof;
+        tampered.quotient.swap(0, 1);
+        assert!(!verify(&kzg, &pk.vk, &[Fr::from(252u64)], &tampered));
+
+        let mut wrong = witness(2, 3);
+        wrong[0] = Fr::from(253u64);
+        assert_eq!(
+            prove(&ck, &pk, &wrong, &mut rng).unwrap_err(),
+            PlonkError::UnsatisfiedGate(4)
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use elliptic_curves::{pairing::Pairing, Encoding};
+use finite_fields::Field;
+use num_traits::{One, Zero};
+use polynomial::Polynomial;
+use polynomial_commitments::{
+    kzg::{Kzg, KzgCommitterKey},
+    random_field_element, PolynomialCommitmentScheme,
+};
+use rand::RngCore;
+use univariate_polynomial_iop_zerotest::domain::Domain;
+
+use crate::{
+    append_evaluations, circuit::coset_shifts, lagrange_terms, transcript, Evaluations, PlonkError,
+    Proof, ProvingKey, Scalar,
+};
+
+fn constant<F: Field>(value: F) -> Polynomial<F> {
+    Polynomial::new_from_coeffs(&[value])
+}
+
+/// `X - point`
+fn linear<F: Field>(point: F) -> Polynomial<F> {
+    Polynomial::new_from_coeffs(&[-point, F::one()])
+}
+
+/// The interpolation of `values` plus a random multiple of `Z_H` of degree
+/// `degree`, which hides the values at the few points the verifier learns
+fn blind<F: Field, R: RngCore>(
+    domain: &Domain<F>,
+    values: &[F],
+    degree: usize,
+    rng: &mut R,
+) -> Polynomial<F> {
+    let mask: Vec<F> = (0..=degree).map(|_| random_field_element(rng)).collect();
+    &domain.interpolate(values)
+        + &(&Polynomial::new_from_coeffs(&mask) * &domain.vanishing_polynomial())
+}
+
+/// Proves knowledge of a witness, with one value per variable, satisfying
+/// the circuit of `pk`. The public inputs are the values of the public
+/// variables.
+pub fn prove<E, R>(
+    ck: &KzgCommitterKey<E>,
+    pk: &ProvingKey<E>,
+    witness: &[Scalar<E>],
+    rng: &mut R,
+) -> Result<Proof<E>, PlonkError>
+where
+    E: Pairing,
+    E::G1: Encoding,
+    R: RngCore,
+{
+    pk.circuit
+        .is_satisfied(witness)
+        .map_err(PlonkError::UnsatisfiedGate)?;
+    let domain = pk.vk.domain;
+    let n = domain.size();
+    let elements = domain.elements();
+    let shifts = coset_shifts(&domain);
+    let public = pk.circuit.public_values(witness);
+    let mut transcript = transcript(&pk.vk, &public);
+    let one = Scalar::<E>::one();
+
+    // Round 1: the wires
+    let wire_values = pk.circuit.wire_values(witness);
+    let wires = wire_values
+        .clone()
+        .map(|column| blind(&domain, &column, 1, rng));
+    let wire_commitments = [0, 1, 2].map(|j| Kzg::commit(ck, &wires[j]));
+    for commitment in &wire_commitments {
+        transcript.append_point(b"wire", commitment);
+    }
+    let beta: Scalar<E> = transcript.challenge(b"beta");
+    let gamma: Scalar<E> = transcript.challenge(b"gamma");
+
+    // Round 2: the permutation accumulator
+    let sigma_values = pk.circuit.permutation();
+    let mut accumulator = vec![one];
+    for i in 0..n - 1 {
+        let (numerator, denominator) = (0..3).fold((one, one), |(num, den), j| {
+            let wire = wire_values[j][i] + gamma;
+            (
+                num * (wire + beta * shifts[j] * elements[i]),
+                den * (wire + beta * sigma_values[j][i]),
+            )
+        });
+        let ratio = numerator * denominator.inverse().expect("γ avoids every root");
+        accumulator.push(accumulator[i] * ratio);
+    }
+    let z = blind(&domain, &accumulator, 2, rng);
+    let z_commitment = Kzg::commit(ck, &z);
+    transcript.append_point(b"z", &z_commitment);
+    let alpha: Scalar<E> = transcript.challenge(b"alpha");
+
+    // Round 3: the quotient
+    let [q_l, q_r, q_o, q_m, q_c] = &pk.selectors;
+    let [sigma_1, sigma_2, sigma_3] = &pk.permutation;
+    let [a, b, c] = &wires;
+    let mut public_column = vec![Scalar::<E>::zero(); n];
+    for (cell, x) in public_column.iter_mut().zip(&public) {
+        *cell = -*x;
+    }
+    let public_input = domain.interpolate(&public_column);
+    let mut first = vec![Scalar::<E>::zero(); n];
+    first[0] = one;
+    let lagrange_first = domain.interpolate(&first);
+    let x = Polynomial::new_from_coeffs(&[Scalar::<E>::zero(), one]);
+
+    let gate =
+        &(&(&(&(a * b) * q_m) + &(a * q_l)) + &(&(b * q_r) + &(c * q_o))) + &(&public_input + q_c);
+    let identity = [a, b, c]
+        .into_iter()
+        .zip(shifts)
+        .fold(constant(one), |acc, (wire, shift)| {
+            &acc * &(&(wire + &(&x * &constant(beta * shift))) + &constant(gamma))
+        });
+    let copies = [(a, sigma_1), (b, sigma_2), (c, sigma_3)]
+        .into_iter()
+        .fold(constant(one), |acc, (wire, sigma)| {
+            &acc * &(&(wire + &(sigma * &constant(beta))) + &constant(gamma))
+        });
+    let permutation = &(&identity * &z) - &(&copies * &domain.shifted(&z));
+    let start = &(&z - &constant(one)) * &lagrange_first;
+    let numerator =
+        &(&gate + &(&permutation * &constant(alpha))) + &(&start * &constant(alpha.square()));
+    let (quotient, remainder) = numerator.div_rem(&domain.vanishing_polynomial());
+    debug_assert!(remainder.is_zero());
+    let mut coeffs = quotient.get_raw_coefficients();
+    assert!(
+        coeffs.len() <= 3 * (n + 2),
+        "quotient of degree at most 3n + 5"
+    );
+    coeffs.resize(3 * (n + 2), Scalar::<E>::zero());
+    let pieces: Vec<Polynomial<Scalar<E>>> = coeffs
+        .chunks(n + 2)
+        .map(Polynomial::new_from_coeffs)
+        .collect();
+    let quotient_commitments = [0, 1, 2].map(|k| Kzg::commit(ck, &pieces[k]));
+    for commitment in &quotient_commitments {
+        transcript.append_point(b"t", commitment);
+    }
+    let zeta: Scalar<E> = transcript.challenge(b"zeta");
+
+    // Round 4: the evaluations
+    let evaluations = Evaluations {
+        a: a.eval(zeta),
+        b: b.eval(zeta),
+        c: c.eval(zeta),
+        sigma_1: sigma_1.eval(zeta),
+        sigma_2: sigma_2.eval(zeta),
+        z_shifted: z.eval(zeta * domain.generator()),
+    };
+    append_evaluations(&mut transcript, &evaluations);
+    let v: Scalar<E> = transcript.challenge(b"v");
+
+    // Round 5: the linearisation and the openings
+    let Evaluations {
+        a: a_bar,
+        b: b_bar,
+        c: c_bar,
+        sigma_1: sigma_1_bar,
+        sigma_2: sigma_2_bar,
+        z_shifted,
+    } = evaluations;
+    let (lagrange_first_zeta, public_input_zeta) = lagrange_terms(&domain, &public, zeta);
+    let vanishing_zeta = domain.evaluate_vanishing_polynomial(zeta);
+    let zeta_n = zeta.pow([n as u64 + 2]);
+
+    let gate = &(&(&(q_m * &constant(a_bar * b_bar)) + &(q_l * &constant(a_bar)))
+        + &(&(q_r * &constant(b_bar)) + &(q_o * &constant(c_bar))))
+        + &(q_c + &constant(public_input_zeta));
+    let identity = [a_bar, b_bar, c_bar]
+        .into_iter()
+        .zip(shifts)
+        .fold(one, |acc, (wire, shift)| {
+            acc * (wire + beta * shift * zeta + gamma)
+        });
+    let copies = (a_bar + beta * sigma_1_bar + gamma) * (b_bar + beta * sigma_2_bar + gamma);
+    let permutation = &(&z * &constant(identity))
+        - &(&(&(sigma_3 * &constant(beta)) + &constant(c_bar + gamma))
+            * &constant(copies * z_shifted));
+    let start = &(&z - &constant(one)) * &constant(lagrange_first_zeta);
+    let quotient = &(&pieces[0] + &(&pieces[1] * &constant(zeta_n)))
+        + &(&pieces[2] * &constant(zeta_n.square()));
+    let linearisation = &(&(&gate + &(&permutation * &constant(alpha)))
+        + &(&start * &constant(alpha.square())))
+        - &(&quotient * &constant(vanishing_zeta));
+    debug_assert!(linearisation.eval(zeta).is_zero());
+
+    let mut batched = linearisation;
+    let mut power = one;
+    for (p, value) in [
+        (a, a_bar),
+        (b, b_bar),
+        (c, c_bar),
+        (sigma_1, sigma_1_bar),
+        (sigma_2, sigma_2_bar),
+    ] {
+        power = power * v;
+        batched = &batched + &(&(p - &constant(value)) * &constant(power));
+    }
+    let (opening, remainder) = batched.div_rem(&linear(zeta));
+    debug_assert!(remainder.is_zero());
+    let shifted_point = zeta * domain.generator();
+    let (shifted_opening, remainder) = (&z - &constant(z_shifted)).div_rem(&linear(shifted_point));
+    debug_assert!(remainder.is_zero());
+
+    Ok(Proof {
+        wires: wire_commitments,
+        z: z_commitment,
+        quotient: quotient_commitments,
+        evaluations,
+        opening: Kzg::commit(ck, &opening),
+        shifted_opening: Kzg::commit(ck, &shifted_opening),
+    })
+}
@@ -0,0 +1,131 @@
+use elliptic_curves::{pairing::Pairing, Encoding, PrimeOrderGroup};
+use finite_fields::Field;
+use num_traits::One;
+use polynomial_commitments::kzg::KzgVerifierKey;
+
+use crate::{
+    append_evaluations, circuit::coset_shifts, lagrange_terms, transcript, Challenges, Proof,
+    Scalar, VerifyingKey,
+};
+
+/// Checks `proof` for the circuit behind `vk` and the values of its public
+/// inputs
+pub fn verify<E>(
+    kzg: &KzgVerifierKey<E>,
+    vk: &VerifyingKey<E>,
+    public: &[Scalar<E>],
+    proof: &Proof<E>,
+) -> bool
+where
+    E: Pairing,
+    E::G1: Encoding,
+{
+    if public.len() != vk.num_public {
+        return false;
+    }
+    let mut transcript = transcript(vk, public);
+    for commitment in &proof.wires {
+        transcript.append_point(b"wire", commitment);
+    }
+    let beta = transcript.challenge(b"beta");
+    let gamma = transcript.challenge(b"gamma");
+    transcript.append_point(b"z", &proof.z);
+    let alpha = transcript.challenge(b"alpha");
+    for commitment in &proof.quotient {
+        transcript.append_point(b"t", commitment);
+    }
+    let zeta = transcript.challenge(b"zeta");
+    append_evaluations(&mut transcript, &proof.evaluations);
+    let v = transcript.challenge(b"v");
+    transcript.append_point(b"opening", &proof.opening);
+    transcript.append_point(b"shifted opening", &proof.shifted_opening);
+    let u = transcript.challenge(b"u");
+    let challenges = Challenges {
+        beta,
+        gamma,
+        alpha,
+        zeta,
+        v,
+        u,
+    };
+    check::<E>(kzg, vk, public, proof, &challenges)
+}
+
+fn check<E>(
+    kzg: &KzgVerifierKey<E>,
+    vk: &VerifyingKey<E>,
+    public: &[Scalar<E>],
+    proof: &Proof<E>,
+    challenges: &Challenges<Scalar<E>>,
+) -> bool
+where
+    E: Pairing,
+{
+    let Challenges {
+        beta,
+        gamma,
+        alpha,
+        zeta,
+        v,
+        u,
+    } = *challenges;
+    let evaluations = proof.evaluations;
+    let domain = vk.domain;
+    let n = domain.size();
+    let shifts = coset_shifts(&domain);
+    let (lagrange_first, public_input) = lagrange_terms(&domain, public, zeta);
+    let vanishing = domain.evaluate_vanishing_polynomial(zeta);
+    let alpha_squared = alpha.square();
+
+    // The constant term `r_0` of the linearisation and the commitment `[D]`
+    // to the rest of it, plus `u [z]` for the opening at `ζ ω`
+    let copies = (evaluations.a + beta * evaluations.sigma_1 + gamma)
+        * (evaluations.b + beta * evaluations.sigma_2 + gamma);
+    let r_0 = public_input
+        - lagrange_first * alpha_squared
+        - alpha * copies * (evaluations.c + gamma) * evaluations.z_shifted;
+    let identity = [evaluations.a, evaluations.b, evaluations.c]
+        .into_iter()
+        .zip(shifts)
+        .fold(Scalar::<E>::one(), |acc, (wire, shift)| {
+            acc * (wire + beta * shift * zeta + gamma)
+        });
+    let [q_l, q_r, q_o, q_m, q_c] = vk.selectors;
+    let zeta_n = zeta.pow([n as u64 + 2]);
+    let [t_lo, t_mid, t_hi] = proof.quotient;
+    let d = q_m.mul_scalar(&(evaluations.a * evaluations.b))
+        + q_l.mul_scalar(&evaluations.a)
+        + q_r.mul_scalar(&evaluations.b)
+        + q_o.mul_scalar(&evaluations.c)
+        + q_c
+        + proof
+            .z
+            .mul_scalar(&(identity * alpha + lagrange_first * alpha_squared + u))
+        - vk.permutation[2].mul_scalar(&(copies * alpha * beta * evaluations.z_shifted))
+        - (t_lo + t_mid.mul_scalar(&zeta_n) + t_hi.mul_scalar(&zeta_n.square()))
+            .mul_scalar(&vanishing);
+
+    // `[F]` batches `r` with the other openings at `ζ`, `[E]` their values
+    let mut f = d;
+    let mut e = evaluations.z_shifted * u - r_0;
+    let mut power = Scalar::<E>::one();
+    for (commitment, value) in [
+        (proof.wires[0], evaluations.a),
+        (proof.wires[1], evaluations.b),
+        (proof.wires[2], evaluations.c),
+        (vk.permutation[0], evaluations.sigma_1),
+        (vk.permutation[1], evaluations.sigma_2),
+    ] {
+        power = power * v;
+        f = f + commitment.mul_scalar(&power);
+        e = e + value * power;
+    }
+
+    // e(W_ζ + u W_ζω, [τ]_2) = e(ζ W_ζ + u ζ ω W_ζω + [F] - [E], [1]_2)
+    let shifted_zeta = zeta * domain.generator();
+    let left = proof.opening + proof.shifted_opening.mul_scalar(&u);
+    let right =
+        proof.opening.mul_scalar(&zeta) + proof.shifted_opening.mul_scalar(&(u * shifted_zeta)) + f
+            - kzg.g1.mul_scalar(&e);
+    E::pairing(&left, &kzg.powers_of_tau_g2[1]) == E::pairing(&right, &kzg.powers_of_tau_g2[0])
+}
@@ -0,0 +1,360 @@
+//! Groth16 [Gro16] for R1CS over any [`Pairing`], tested on BLS12-381.
+//!
+//! The circuit-specific trusted setup samples `α, β, γ, δ, τ` and publishes
+//! the QAP polynomials evaluated at `τ` in the exponent. For an assignment
+//! `z = (1, x, w)` with instance `x = (z_1, ..., z_l)` and random `r, s`,
+//! the proof is
+//!
+//! - `A = [α + Σ z_j u_j(τ) + r δ]_1`,
+//! - `B = [β + Σ z_j v_j(τ) + s δ]_2`,
+//! - `C = [(Σ_(j > l) z_j (β u_j(τ) + α v_j(τ) + w_j(τ)) + h(τ) Z_H(τ)) / δ
+//!   + s A + r B - r s δ]_1`,
+//!
+//! which the verifier checks with
+//!
+//! `e(A, B) = e([α]_1, [β]_2) · e(Σ_(j ≤ l) z_j [(β u_j(τ) + α v_j(τ) +
+//! w_j(τ)) / γ]_1, [γ]_2) · e(C, [δ]_2)`.
+//!
+//! Proofs are three group elements whatever the size of the circuit. They
+//! are malleable: [`rerandomize`] turns a proof into a fresh-looking one
+//! for the same instance without the witness. Several proofs under the same
+//! key can be checked together with [`batch_verify`].
+
+use elliptic_curves::{pairing::Pairing, scalar_mul::msm, Group, PrimeOrderGroup};
+use finite_fields::Field;
+use num_traits::{One, Zero};
+use polynomial_commitments::random_field_element;
+use r1cs::R1csError;
+use rand::RngCore;
+
+pub mod qap;
+
+use qap::Qap;
+
+type Scalar<E> = <<E as Pairing>::G1 as PrimeOrderGroup>::ScalarField;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Groth16Error {
+    /// The instance and witness do not satisfy the constraint system
+    R1cs(R1csError),
+}
+
+impl From<R1csError> for Groth16Error {
+    fn from(error: R1csError) -> Self {
+        Self::R1cs(error)
+    }
+}
+
+/// The verifier's part of the setup
+#[derive(Debug, Clone)]
+pub struct VerifyingKey<E: Pairing> {
+    /// `e([α]_1, [β]_2)`
+    pub alpha_beta: E::Target,
+    pub alpha_g1: E::G1,
+    pub beta_g2: E::G2,
+    pub gamma_g2: E::G2,
+    pub delta_g2: E::G2,
+    /// `[(β u_j(τ) + α v_j(τ) + w_j(τ)) / γ]_1` for the constant and every
+    /// instance variable
+    pub instance_query: Vec<E::G1>,
+}
+
+/// The QAP and the prover's part of the setup
+#[derive(Debug, Clone)]
+pub struct ProvingKey<E: Pairing> {
+    pub qap: Qap<Scalar<E>>,
+    pub beta_g1: E::G1,
+    pub delta_g1: E::G1,
+    /// `[u_j(τ)]_1` for every variable
+    pub a_query: Vec<E::G1>,
+    /// `[v_j(τ)]_1` for every variable
+    pub b_g1_query: Vec<E::G1>,
+    /// `[v_j(τ)]_2` for every variable
+    pub b_g2_query: Vec<E::G2>,
+    /// `[τ^i Z_H(τ) / δ]_1` for `i < n - 1`
+    pub h_query: Vec<E::G1>,
+    /// `[(β u_j(τ) + α v_j(τ) + w_j(τ)) / δ]_1` for every witness variable
+    pub witness_query: Vec<E::G1>,
+    pub vk: VerifyingKey<E>,
+}
+
+/// Three group elements, whatever the size of the circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Proof<E: Pairing> {
+    pub a: E::G1,
+    pub b: E::G2,
+    pub c: E::G1,
+}
+
+/// Random non-zero scalar
+fn random_nonzero<F: Field, R: RngCore>(rng: &mut R) -> F {
+    loop {
+        let value: F = random_field_element(rng);
+        if !value.is_zero() {
+            return value;
+        }
+    }
+}
+
+/// The trusted setup for `qap`. The trapdoor `α, β, γ, δ, τ` is dropped on
+/// return; whoever knows it can prove anything.
+pub fn setup<E, R>(qap: Qap<Scalar<E>>, rng: &mut R) -> ProvingKey<E>
+where
+    E: Pairing,
+    R: RngCore,
+{
+    let alpha: Scalar<E> = random_nonzero(rng);
+    let beta: Scalar<E> = random_nonzero(rng);
+    let gamma: Scalar<E> = random_nonzero(rng);
+    let delta: Scalar<E> = random_nonzero(rng);
+    let tau: Scalar<E> = loop {
+        let tau: Scalar<E> = random_field_element(rng);
+        if !qap.domain().evaluate_vanishing_polynomial(tau).is_zero() {
+            break tau;
+        }
+    };
+    let gamma_inverse = gamma.inverse().expect("γ is non-zero");
+    let delta_inverse = delta.inverse().expect("δ is non-zero");
+
+    let (g1, g2) = (E::G1::generator(), E::G2::generator());
+    let [u, v, w] = qap.evaluate_at(tau);
+    let num_public = 1 + qap.r1cs().num_instance;
+    let combined: Vec<Scalar<E>> = (0..qap.num_variables())
+        .map(|j| beta * u[j] + alpha * v[j] + w[j])
+        .collect();
+
+    let n = qap.domain().size();
+    let vanishing = qap.domain().evaluate_vanishing_polynomial(tau) * delta_inverse;
+    let h_query = std::iter::successors(Some(vanishing), |power| Some(*power * tau))
+        .take(n - 1)
+        .map(|power| g1.mul_scalar(&power))
+        .collect();
+
+    let alpha_g1 = g1.mul_scalar(&alpha);
+    let beta_g2 = g2.mul_scalar(&beta);
+    let vk = VerifyingKey {
+        alpha_beta: E::pairing(&alpha_g1, &beta_g2),
+        alpha_g1,
+        beta_g2,
+        gamma_g2: g2.mul_scalar(&gamma),
+        delta_g2: g2.mul_scalar(&delta),
+        instance_query: combined[..num_public]
+            .iter()
+            .map(|value| g1.mul_scalar(&(*value * gamma_inverse)))
+            .collect(),
+    };
+    ProvingKey {
+        beta_g1: g1.mul_scalar(&beta),
+        delta_g1: g1.mul_scalar(&delta),
+        a_query: u.iter().map(|value| g1.mul_scalar(value)).collect(),
+        b_g1_query: v.iter().map(|value| g1.mul_scalar(value)).collect(),
+        b_g2_query: v.iter().map(|value| g2.mul_scalar(value)).collect(),
+        h_query,
+        witness_query: combined[num_public..]
+            .iter()
+            .map(|value| g1.mul_scalar(&(*value * delta_inverse)))
+            .collect(),
+        qap,
+        vk,
+    }
+}
+
+/// Proves that the prover knows a witness for `instance`
+pub fn prove<E, R>(
+    pk: &ProvingKey<E>,
+    instance: &[Scalar<E>],
+    witness: &[Scalar<E>],
+    rng: &mut R,
+) -> Result<Proof<E>, Groth16Error>
+where
+    E: Pairing,
+    R: RngCore,
+{
+    let r1cs = pk.qap.r1cs();
+    r1cs.is_satisfied(instance, witness)?;
+    let z = r1cs.assignment(instance, witness)?;
+    let h = pk
+        .qap
+        .quotient(&z)
+        .expect("Z_H divides for satisfying assignments");
+
+    let r: Scalar<E> = random_field_element(rng);
+    let s: Scalar<E> = random_field_element(rng);
+    let delta_g2 = pk.vk.delta_g2;
+    let a = pk.vk.alpha_g1 + msm(&pk.a_query, &z) + pk.delta_g1.mul_scalar(&r);
+    let b = pk.vk.beta_g2 + msm(&pk.b_g2_query, &z) + delta_g2.mul_scalar(&s);
+    let b_g1 = pk.beta_g1 + msm(&pk.b_g1_query, &z) + pk.delta_g1.mul_scalar(&s);
+    let num_public = 1 + r1cs.num_instance;
+    let c = msm(&pk.witness_query, &z[num_public..])
+        + msm(&pk.h_query, &h.get_raw_coefficients())
+        + a.mul_scalar(&s)
+        + b_g1.mul_scalar(&r)
+        - pk.delta_g1.mul_scalar(&(r * s));
+    Ok(Proof { a, b, c })
+}
+
+/// `Σ_(j ≤ l) z_j [(β u_j(τ) + α v_j(τ) + w_j(τ)) / γ]_1`, or `None` if the
+/// instance has the wrong length
+fn instance_commitment<E: Pairing>(vk: &VerifyingKey<E>, instance: &[Scalar<E>]) -> Option<E::G1> {
+    if instance.len() + 1 != vk.instance_query.len() {
+        return None;
+    }
+    Some(vk.instance_query[0] + msm(&vk.instance_query[1..], instance))
+}
+
+/// Checks `proof` against the instance with a single pairing equation
+pub fn verify<E: Pairing>(vk: &VerifyingKey<E>, instance: &[Scalar<E>], proof: &Proof<E>) -> bool {
+    let Some(public) = instance_commitment(vk, instance) else {
+        return false;
+    };
+    E::pairing(&proof.a, &proof.b)
+        == vk.alpha_beta * E::pairing(&public, &vk.gamma_g2) * E::pairing(&proof.c, &vk.delta_g2)
+}
+
+/// A proof for the same instance that is independent of `proof`:
+/// `A' = A / ρ`, `B' = ρ B + ρ μ [δ]_2` and `C' = C + μ A` for random `ρ`,
+/// `μ`, so that `e(A', B') = e(A, B) e(A, [δ]_2)^μ` and `e(C', [δ]_2) =
+/// e(C, [δ]_2) e(A, [δ]_2)^μ`
+pub fn rerandomize<E, R>(vk: &VerifyingKey<E>, proof: &Proof<E>, rng: &mut R) -> Proof<E>
+where
+    E: Pairing,
+    R: RngCore,
+{
+    let rho: Scalar<E> = random_nonzero(rng);
+    let mu: Scalar<E> = random_field_element(rng);
+    Proof {
+        a: proof.a.mul_scalar(&rho.inverse().expect("ρ is non-zero")),
+        b: proof.b.mul_scalar(&rho) + vk.delta_g2.mul_scalar(&(rho * mu)),
+        c: proof.c + proof.a.mul_scalar(&mu),
+    }
+}
+
+/// Checks all `proofs` at once with a random linear combination `ρ_k` of
+/// their equations,
+///
+/// `Π_k e(ρ_k A_k, B_k) = e(Σ_k ρ_k [α]_1, [β]_2) · e(Σ_k ρ_k I_k, [γ]_2) ·
+/// e(Σ_k ρ_k C_k, [δ]_2)`,
+///
+/// which takes `k + 3` pairings instead of `3 k`. A batch with an invalid
+/// proof passes with probability about `1 / |F|`.
+pub fn batch_verify<E, R>(
+    vk: &VerifyingKey<E>,
+    proofs: &[(Vec<Scalar<E>>, Proof<E>)],
+    rng: &mut R,
+) -> bool
+where
+    E: Pairing,
+    R: RngCore,
+{
+    let mut left = E::Target::one();
+    let mut rho_sum = Scalar::<E>::zero();
+    let mut public = E::G1::identity();
+    let mut c = E::G1::identity();
+    for (instance, proof) in proofs {
+        let Some(commitment) = instance_commitment(vk, instance) else {
+            return false;
+        };
+        let rho: Scalar<E> = random_field_element(rng);
+        left = left * E::pairing(&proof.a.mul_scalar(&rho), &proof.b);
+        rho_sum = rho_sum + rho;
+        public = public + commitment.mul_scalar(&rho);
+        c = c + proof.c.mul_scalar(&rho);
+    }
+    left == E::pairing(&vk.alpha_g1.mul_scalar(&rho_sum), &vk.beta_g2)
+        * E::pairing(&public, &vk.gamma_g2)
+        * E::pairing(&c, &vk.delta_g2)
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_ff::FftField;
+    use r1cs::examples::{scaled_square_product, scaled_square_product_witness};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use univariate_polynomial_iop_zerotest::domain::Domain;
+
+    use super::*;
+
+    /// `c = k a^2 b^2` with `c` public and `k = 7`, as in halo2-trials
+    fn proving_key(rng: &mut ChaCha20Rng) -> ProvingKey<Bls12_381> {
+        let domain = Domain::new(Fr::get_root_of_unity(8).unwrap(), 8);
+        setup(
+            Qap::new(&scaled_square_product(Fr::from(7u64)), domain),
+            rng,
+        )
+    }
+
+    fn witness(a: u64, b: u64) -> Vec<Fr> {
+        scaled_square_product_witness(Fr::from(a), Fr::from(b))
+    }
+
+    #[test]
+    fn honest_proof_is_accepted() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let pk = proving_key(&mut rng);
+        let proof = prove(&pk, &[Fr::from(252u64)], &witness(2, 3), &mut rng).unwrap();
+        assert!(verify(&pk.vk, &[Fr::from(252u64)], &proof));
+        assert!(!verify(&pk.vk, &[Fr::from(253u64)], &proof));
+        assert!(!verify(&pk.vk, &[], &proof));
+
+        let tampered = Proof {
+            c: proof.c + proof.a,
+            ..proof
+        };
+        assert!(!verify(&pk.vk, &[Fr::from(252u64)], &tampered));
+    }
+
+    #[test]
+    fn unsatisfied_witness_is_refused() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let pk = proving_key(&mut rng);
+        let mut wrong = witness(2, 3);
+        wrong[3] = Fr::from(37u64);
+        assert_eq!(
+            prove(&pk, &[Fr::from(252u64)], &wrong, &mut rng),
+            Err(Groth16Error::R1cs(R1csError::Unsatisfied(1)))
+        );
+    }
+
+    #[test]
+    fn rerandomized_proof_is_accepted() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let pk = proving_key(&mut rng);
+        let instance = [Fr::from(252u64)];
+        let proof = prove(&pk, &instance, &witness(2, 3), &mut rng).unwrap();
+        let fresh = rerandomize(&pk.vk, &proof, &mut rng);
+        assert_ne!(fresh, proof);
+        assert_ne!(fresh.a, proof.a);
+        assert!(verify(&pk.vk, &instance, &fresh));
+        assert!(!verify(&pk.vk, &[Fr::from(253u64)], &fresh));
+    }
+
+    #[test]
+    fn batch_verification() {
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        let pk = proving_key(&mut rng);
+        let mut proofs: Vec<(Vec<Fr>, Proof<Bls12_381>)> = [(2, 3), (1, 5), (4, 4)]
+            .into_iter()
+            .map(|(a, b)| {
+                let instance = vec![Fr::from(7 * a * a * b * b)];
+                let proof = prove(&pk, &instance, &witness(a, b), &mut rng).unwrap();
+                (instance, proof)
+            })
+            .collect();
+        assert!(batch_verify(&pk.vk, &proofs, &mut rng));
+
+        // Swapping the instances of two valid proofs breaks both equations
+        let swap = |proofs: &mut Vec<(Vec<Fr>, Proof<Bls12_381>)>| {
+            let (first, rest) = proofs.split_at_mut(1);
+            std::mem::swap(&mut first[0].0, &mut rest[0].0);
+        };
+        swap(&mut proofs);
+        assert!(!batch_verify(&pk.vk, &proofs, &mut rng));
+        swap(&mut proofs);
+        assert!(batch_verify(&pk.vk, &proofs, &mut rng));
+
+        proofs[2].1.c = proofs[2].1.c + proofs[2].1.a;
+        assert!(!batch_verify(&pk.vk, &proofs, &mut rng));
+    }
+}
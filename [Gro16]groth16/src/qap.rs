@@ -0,0 +1,154 @@
+//! The reduction of an R1CS to a quadratic arithmetic program. Interpolating
+//! every column `j` of `A`, `B`, `C` over the rows, placed at the elements
+//! `ω^i` of a domain `H`, gives polynomials `u_j`, `v_j`, `w_j` such that `z`
+//! satisfies the R1CS exactly when
+//!
+//! `Σ_j z_j u_j(X) · Σ_j z_j v_j(X) - Σ_j z_j w_j(X) = h(X) Z_H(X)`
+//!
+//! for some `h`, since the left side vanishes at `ω^i` iff row `i` holds.
+
+use finite_fields::Field;
+use polynomial::Polynomial;
+use r1cs::{SparseMatrix, R1CS};
+use univariate_polynomial_iop_zerotest::domain::Domain;
+
+#[derive(Debug, Clone)]
+pub struct Qap<F> {
+    r1cs: R1CS<F>,
+    domain: Domain<F>,
+    a: SparseMatrix<F>,
+    b: SparseMatrix<F>,
+    c: SparseMatrix<F>,
+}
+
+impl<F: Field> Qap<F> {
+    /// Places the constraints of `r1cs` on `domain`, followed by a row
+    /// `z_j · 0 = 0` for the constant and every instance variable. These
+    /// make `u_0, ..., u_l` linearly independent, which Groth16 needs for
+    /// the proof to bind the instance.
+    pub fn new(r1cs: &R1CS<F>, domain: Domain<F>) -> Self {
+        let num_public = 1 + r1cs.num_instance;
+        let rows = r1cs.num_constraints() + num_public;
+        assert!(
+            rows <= domain.size(),
+            "{rows} rows do not fit into a domain of size {}",
+            domain.size()
+        );
+        let extend = |matrix: &SparseMatrix<F>, binding: bool| {
+            let rows = (0..matrix.num_rows())
+                .map(|i| matrix.row(i).to_vec())
+                .chain((0..num_public).map(|j| match binding {
+                    true => vec![(j, F::one())],
+                    false => vec![],
+                }))
+                .collect();
+            SparseMatrix::new(matrix.num_columns(), rows)
+        };
+        Self {
+            r1cs: r1cs.clone(),
+            domain,
+            a: extend(&r1cs.a, true),
+            b: extend(&r1cs.b, false),
+            c: extend(&r1cs.c, false),
+        }
+    }
+
+    pub fn domain(&self) -> &Domain<F> {
+        &self.domain
+    }
+
+    /// The constraint system before the extra rows
+    pub fn r1cs(&self) -> &R1CS<F> {
+        &self.r1cs
+    }
+
+    /// Length of `z`, the constant one included
+    pub fn num_variables(&self) -> usize {
+        self.a.num_columns()
+    }
+
+    /// `L_i(x)` for every `i`, with `L_i(x) = ω^i Z_H(x) / (n (x - ω^i))`
+    fn lagrange_basis(&self, x: F) -> Vec<F> {
+        let vanishing = self.domain.evaluate_vanishing_polynomial(x);
+        let n = F::from_u64(self.domain.size() as u64);
+        self.domain
+            .elements()
+            .into_iter()
+            .map(|power| match (n * (x - power)).inverse() {
+                Some(inverse) => power * vanishing * inverse,
+                None => F::one(),
+            })
+            .collect()
+    }
+
+    /// `u_j(x)`, `v_j(x)`, `w_j(x)` for every column `j`, summing the
+    /// Lagrange basis at `x` over the non-zero entries of each column
+    pub fn evaluate_at(&self, x: F) -> [Vec<F>; 3] {
+        let lagrange = self.lagrange_basis(x);
+        [&self.a, &self.b, &self.c].map(|matrix| {
+            let mut values = vec![F::zero(); matrix.num_columns()];
+            for (i, j, value) in matrix.entries() {
+                values[j] = values[j] + value * lagrange[i];
+            }
+            values
+        })
+    }
+
+    /// `Σ_j z_j u_j`, `Σ_j z_j v_j`, `Σ_j z_j w_j`, interpolating `A z`,
+    /// `B z`, `C z` rather than every column
+    pub fn combined_polynomials(&self, z: &[F]) -> [Polynomial<F>; 3] {
+        [&self.a, &self.b, &self.c].map(|matrix| {
+            let mut values = matrix.mul_vector(z);
+            values.resize(self.domain.size(), F::zero());
+            self.domain.interpolate(&values)
+        })
+    }
+
+    /// `h = (Σ z_j u_j · Σ z_j v_j - Σ z_j w_j) / Z_H`, or `None` if `Z_H`
+    /// does not divide, that is if `z` is not a satisfying assignment
+    pub fn quotient(&self, z: &[F]) -> Option<Polynomial<F>> {
+        let [a, b, c] = self.combined_polynomials(z);
+        let (quotient, remainder) = (&(&a * &b) - &c).div_rem(&self.domain.vanishing_polynomial());
+        remainder.is_zero().then_some(quotient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+    use r1cs::examples::cubic;
+
+    use super::*;
+
+    type Fq = PrimeField<41>;
+
+    fn assignment(values: [u64; 5]) -> Vec<Fq> {
+        values.map(Fq::from_u64).to_vec()
+    }
+
+    #[test]
+    fn divisibility_matches_satisfiability() {
+        let qap = Qap::new(&cubic::<Fq>(), Domain::new(Fq::from_u64(3), 8));
+        let h = qap.quotient(&assignment([1, 35, 3, 9, 27])).unwrap();
+        assert!(h.leading_degree().unwrap_or(0) <= 6);
+        assert!(qap.quotient(&assignment([1, 35, 3, 9, 28])).is_none());
+        assert!(qap.quotient(&assignment([1, 36, 3, 9, 27])).is_none());
+    }
+
+    #[test]
+    fn evaluations_match_the_combined_polynomials() {
+        let qap = Qap::new(&cubic::<Fq>(), Domain::new(Fq::from_u64(3), 8));
+        let z = assignment([1, 35, 3, 9, 27]);
+        let combined = qap.combined_polynomials(&z);
+        for x in [2, 3, 10].map(Fq::from_u64) {
+            let columns = qap.evaluate_at(x);
+            for (values, polynomial) in columns.iter().zip(&combined) {
+                let value = values
+                    .iter()
+                    .zip(&z)
+                    .fold(Fq::from_u64(0), |acc, (v, z)| acc + *v * *z);
+                assert_eq!(value, polynomial.eval(x));
+            }
+        }
+    }
+}
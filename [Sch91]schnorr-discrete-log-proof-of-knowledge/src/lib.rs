@@ -4,55 +4,166 @@
 use std::marker::PhantomData;
 
 use ark_crypto_primitives::Error;
-use ark_ec::{AffineRepr, CurveGroup, Group};
-use ark_ff::PrimeField;
+use elliptic_curves::{Encoding, PrimeOrderGroup};
+use fiat_shamir::{Binding, Transcript};
 
-pub struct Schnorr<C: CurveGroup> {
-    _group: PhantomData<C>,
+/// Schnorr's identification protocol over any group of prime order,
+/// be it an arkworks curve or one of the toy curves in `elliptic-curves`
+pub struct Schnorr<G: PrimeOrderGroup> {
+    _group: PhantomData<G>,
 }
 
-pub type PublicKey<C> = <C as CurveGroup>::Affine;
+/// The group element `h = g^x`. Before the crate was generic over
+/// [`PrimeOrderGroup`] this was the affine point of an arkworks curve; for
+/// those curves it is now the projective point `G` itself.
+pub type PublicKey<G> = G;
 
-#[derive(Clone, Default, Debug)]
-pub struct SecretKey<C: CurveGroup + Group> {
-    pub secret_key: C::ScalarField,
-    pub public_key: PublicKey<C>,
+#[derive(Clone, Debug)]
+pub struct SecretKey<G: PrimeOrderGroup> {
+    pub secret_key: G::ScalarField,
+    pub public_key: PublicKey<G>,
 }
 
-pub struct Parameters<C: CurveGroup> {
-    pub generator: C::Affine,
+/// The zero secret with the identity as its public key, as the derived
+/// `Default` of the arkworks version gave
+impl<G: PrimeOrderGroup> Default for SecretKey<G> {
+    fn default() -> Self {
+        Self {
+            secret_key: G::ScalarField::from(0u64),
+            public_key: G::identity(),
+        }
+    }
+}
+
+pub struct Parameters<G: PrimeOrderGroup> {
+    pub generator: G,
+    /// Domain separator for non-interactive proofs, so that proofs made
+    /// for one application are not valid in another
     pub salt: Option<[u8; 32]>,
 }
 
-impl<C: CurveGroup + Group> Schnorr<C>
-where
-    C::ScalarField: PrimeField,
-{
-    fn setup() -> Result<Parameters<C>, Error> {
-        Ok(Parameters::<C> {
-            generator: C::generator().into(),
+/// A non-interactive proof of knowledge of a discrete logarithm
+#[derive(Clone, Debug)]
+pub struct Proof<G: PrimeOrderGroup> {
+    pub commitment: G,
+    pub response: G::ScalarField,
+}
+
+impl<G: PrimeOrderGroup> Schnorr<G> {
+    pub fn setup() -> Result<Parameters<G>, Error> {
+        Ok(Parameters::<G> {
+            generator: G::generator(),
             salt: Default::default(),
         })
     }
 
-    fn from_secret(parameters: &Parameters<C>, secret: u64) -> Result<SecretKey<C>, Error> {
-        let secret_key = C::ScalarField::from_bigint(secret.into()).unwrap();
-        let public_key = parameters.generator.mul_bigint(&[secret]).into();
-        Ok(SecretKey::<C> {
+    pub fn from_secret(parameters: &Parameters<G>, secret: u64) -> Result<SecretKey<G>, Error> {
+        let secret_key = G::ScalarField::from(secret);
+        let public_key = parameters.generator.mul_bigint([secret]);
+        Ok(SecretKey::<G> {
             secret_key,
             public_key,
         })
     }
+
+    /// Prover's first message `u = g^r` for a random blinding factor `r`
+    pub fn commit(parameters: &Parameters<G>, blinding: &G::ScalarField) -> G {
+        parameters.generator.mul_scalar(blinding)
+    }
+
+    /// Prover's response `z = r + x c` to the verifier's challenge `c`
+    pub fn respond(
+        secret: &SecretKey<G>,
+        blinding: &G::ScalarField,
+        challenge: &G::ScalarField,
+    ) -> G::ScalarField {
+        *blinding + secret.secret_key * *challenge
+    }
+
+    /// Verifier accepts iff `g^z = u h^c`
+    pub fn verify(
+        parameters: &Parameters<G>,
+        public_key: &PublicKey<G>,
+        commitment: &G,
+        challenge: &G::ScalarField,
+        response: &G::ScalarField,
+    ) -> bool {
+        parameters.generator.mul_scalar(response) == *commitment + public_key.mul_scalar(challenge)
+    }
+}
+
+impl<G: PrimeOrderGroup + Encoding> Schnorr<G> {
+    /// The verifier's challenge as a hash of the salt, the statement and
+    /// the commitment. With [`Binding::Weak`] the statement, i.e. the
+    /// generator and the public key, is left out.
+    pub fn challenge(
+        parameters: &Parameters<G>,
+        public_key: &PublicKey<G>,
+        commitment: &G,
+        binding: Binding,
+    ) -> G::ScalarField {
+        let mut transcript = Transcript::with_binding(b"schnorr", binding);
+        if let Some(salt) = &parameters.salt {
+            transcript.append_message(b"salt", salt);
+        }
+        transcript.append_statement(b"generator", &parameters.generator.to_bytes());
+        transcript.append_statement(b"public_key", &public_key.to_bytes());
+        transcript.append_point(b"commitment", commitment);
+        transcript.challenge(b"challenge")
+    }
+
+    /// Non-interactive proof with the Fiat–Shamir challenge
+    pub fn prove(
+        parameters: &Parameters<G>,
+        secret: &SecretKey<G>,
+        blinding: &G::ScalarField,
+        binding: Binding,
+    ) -> Proof<G> {
+        let commitment = Self::commit(parameters, blinding);
+        let challenge = Self::challenge(parameters, &secret.public_key, &commitment, binding);
+        Proof {
+            commitment,
+            response: Self::respond(secret, blinding, &challenge),
+        }
+    }
+
+    pub fn verify_proof(
+        parameters: &Parameters<G>,
+        public_key: &PublicKey<G>,
+        proof: &Proof<G>,
+        binding: Binding,
+    ) -> bool {
+        let challenge = Self::challenge(parameters, public_key, &proof.commitment, binding);
+        Self::verify(
+            parameters,
+            public_key,
+            &proof.commitment,
+            &challenge,
+            &proof.response,
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use ark_bls12_381::G1Projective;
-    use ark_ec::{AffineRepr, Group};
+    use ark_ec::Group;
     use ark_ff::PrimeField;
+    use elliptic_curves::{short_weierstrass::Jacobian, toy::Toy10007, PrimeOrderGroup};
+    use finite_fields::{Halo2Field, PrimeField as ToyField};
+    use halo2_proofs::pasta::pallas;
 
     use super::Schnorr;
-    use crate::Parameters;
+    use crate::{Binding, Parameters, Proof, SecretKey};
+
+    #[test]
+    fn default_secret_key_is_zero() {
+        let params: Parameters<G1Projective> = Schnorr::setup().unwrap();
+        let zero = Schnorr::from_secret(&params, 0).unwrap();
+        let default = SecretKey::<G1Projective>::default();
+        assert_eq!(default.secret_key, zero.secret_key);
+        assert_eq!(default.public_key, zero.public_key);
+    }
 
     #[test]
     fn schnorr_dlog_pok() {
@@ -80,4 +191,123 @@ mod tests {
 
         assert_eq!(verifier_lhs, verifier_rhs);
     }
+
+    #[test]
+    fn schnorr_dlog_pok_toy_curve() {
+        type Toy = Schnorr<Jacobian<Toy10007>>;
+        // Scalars live modulo the group order 10039
+        type Fr = ToyField<10039>;
+
+        let params = Toy::setup().unwrap();
+        let secret = Toy::from_secret(&params, 541).unwrap();
+
+        let blinding = Fr::new(412);
+        let challenge = Fr::new(3181);
+        let commitment = Toy::commit(&params, &blinding);
+        let response = Toy::respond(&secret, &blinding, &challenge);
+        assert_eq!(response, Fr::new((412 + 541 * 3181) % 10039));
+        assert!(Toy::verify(
+            &params,
+            &secret.public_key,
+            &commitment,
+            &challenge,
+            &response
+        ));
+
+        // A response computed without the secret is rejected
+        assert!(!Toy::verify(
+            &params,
+            &secret.public_key,
+            &commitment,
+            &challenge,
+            &(response + Fr::new(1))
+        ));
+    }
+
+    #[test]
+    fn schnorr_dlog_pok_pallas() {
+        type Pallas = Schnorr<pallas::Point>;
+        let scalar = |value: u64| Halo2Field(pallas::Scalar::from(value));
+
+        let params = Pallas::setup().unwrap();
+        let secret = Pallas::from_secret(&params, 541).unwrap();
+        let (blinding, challenge) = (scalar(412), scalar(31981));
+        let commitment = Pallas::commit(&params, &blinding);
+        let response = Pallas::respond(&secret, &blinding, &challenge);
+        assert!(Pallas::verify(
+            &params,
+            &secret.public_key,
+            &commitment,
+            &challenge,
+            &response
+        ));
+    }
+
+    #[test]
+    fn non_interactive_proofs() {
+        type Pallas = Schnorr<pallas::Point>;
+        let scalar = |value: u64| Halo2Field(pallas::Scalar::from(value));
+
+        let mut params = Pallas::setup().unwrap();
+        params.salt = Some([7; 32]);
+        let secret = Pallas::from_secret(&params, 541).unwrap();
+        for binding in [Binding::Strong, Binding::Weak] {
+            let proof = Pallas::prove(&params, &secret, &scalar(412), binding);
+            assert!(Pallas::verify_proof(
+                &params,
+                &secret.public_key,
+                &proof,
+                binding
+            ));
+        }
+
+        // Proofs are bound to the salt
+        let proof = Pallas::prove(&params, &secret, &scalar(412), Binding::Strong);
+        let other = Parameters {
+            generator: params.generator,
+            salt: Some([8; 32]),
+        };
+        assert!(!Pallas::verify_proof(
+            &other,
+            &secret.public_key,
+            &proof,
+            Binding::Strong
+        ));
+    }
+
+    /// With weak Fiat–Shamir the challenge does not depend on the public
+    /// key. Anyone can then turn a proof for `h` into one for `h · g^δ`,
+    /// whose discrete logarithm nobody knows, by shifting the response by
+    /// `δ c`: `g^(z + δ c) = u h^c g^(δ c) = u (h g^δ)^c`.
+    #[test]
+    fn weak_fiat_shamir_forgery() {
+        type Toy = Schnorr<Jacobian<Toy10007>>;
+        type Fr = ToyField<10039>;
+
+        let params = Toy::setup().unwrap();
+        let secret = Toy::from_secret(&params, 541).unwrap();
+        let delta = Fr::new(1234);
+        let target = secret.public_key + params.generator.mul_scalar(&delta);
+
+        let forge = |proof: &Proof<_>, binding| {
+            let challenge = Toy::challenge(&params, &secret.public_key, &proof.commitment, binding);
+            Proof {
+                commitment: proof.commitment,
+                response: proof.response + delta * challenge,
+            }
+        };
+
+        let proof = Toy::prove(&params, &secret, &Fr::new(412), Binding::Weak);
+        let forged = forge(&proof, Binding::Weak);
+        assert!(Toy::verify_proof(&params, &target, &forged, Binding::Weak));
+
+        let proof = Toy::prove(&params, &secret, &Fr::new(412), Binding::Strong);
+        let forged = forge(&proof, Binding::Strong);
+        assert!(!Toy::verify_proof(
+            &params,
+            &target,
+            &forged,
+            Binding::Strong
+        ));
+    }
 }
@@ -0,0 +1,530 @@
+//! Spartan: a transparent SNARK for R1CS from two sumchecks and a
+//! multilinear polynomial commitment. With `s = log m` for `m` constraints,
+//! the matrices are multilinear `A(x, y)`, `B(x, y)`, `C(x, y)` over rows
+//! `x ∈ {0,1}^s` and columns `y`, and `z = (1, x, w)` is a multilinear
+//! `Z(y)`. The instance is satisfied iff
+//!
+//! `Σ_x eq(τ, x) (Σ_y A(x, y) Z(y) · Σ_y B(x, y) Z(y) - Σ_y C(x, y) Z(y)) = 0`
+//!
+//! for a random `τ`, up to a soundness error of `s / |F|`.
+//!
+//! 1. The prover commits to the witness `w` as a multilinear polynomial.
+//!    The transcript absorbs the R1CS, the commitment key, the instance
+//!    and this commitment before `τ`.
+//! 2. The outer sumcheck over `x`, of degree 3, leaves the claims
+//!    `Az(r_x)`, `Bz(r_x)`, `Cz(r_x)`, which the prover states.
+//! 3. The verifier folds them with random `r_A`, `r_B`, `r_C` into one
+//!    inner sumcheck over `y` of
+//!    `(r_A A(r_x, y) + r_B B(r_x, y) + r_C C(r_x, y)) Z(y)`, of degree 2.
+//! 4. This leaves `Z(r_y)`. The columns are laid out so that
+//!    `z = ((1, x, 0, ...), (w, 0, ...))` with halves of the same power of
+//!    two, hence `Z(r_y) = (1 - r_y,1) X(r_y,2..) + r_y,1 W(r_y,2..)`. The
+//!    verifier evaluates the public half itself and the prover opens the
+//!    commitment to `W`.
+//!
+//! The verifier evaluates the sparse matrices at `(r_x, r_y)` itself, in
+//! time linear in their number of entries, which is Spartan's NIZK
+//! variant; the SNARK variant commits to the matrices in preprocessing
+//! instead. Nothing here is zero-knowledge: the sumcheck messages and the
+//! claims leak about the witness.
+
+use elliptic_curves::{encoding::field_to_bytes, Encoding};
+use fiat_shamir::Transcript;
+use finite_fields::Field;
+use multivariate_sumcheck::{
+    multilinear::{eq, MultilinearPolynomial},
+    sumcheck::{SumcheckProof, SumcheckProver, SumcheckVerifier},
+};
+use polynomial_commitments::MultilinearCommitmentScheme;
+use r1cs::{R1csError, R1CS};
+use rand::RngCore;
+
+const LABEL: &[u8] = b"spartan";
+
+/// Degree of `eq(τ, x) (Az Bz - Cz)` in `x`
+const OUTER_DEGREE: usize = 3;
+
+/// Degree of the combined matrices times `Z` in `y`
+const INNER_DEGREE: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpartanError {
+    R1cs(R1csError),
+}
+
+impl From<R1csError> for SpartanError {
+    fn from(error: R1csError) -> Self {
+        Self::R1cs(error)
+    }
+}
+
+pub struct ProvingKey<F: Field, PC: MultilinearCommitmentScheme<F>> {
+    pub r1cs: R1CS<F>,
+    pub ck: PC::CommitterKey,
+    /// The verifier's key, which the prover absorbs as the verifier does
+    pub vk: PC::VerifierKey,
+}
+
+pub struct VerifyingKey<F: Field, PC: MultilinearCommitmentScheme<F>> {
+    pub r1cs: R1CS<F>,
+    pub vk: PC::VerifierKey,
+}
+
+#[derive(Debug, Clone)]
+pub struct Proof<F: Field, PC: MultilinearCommitmentScheme<F>> {
+    /// Commitment to the padded witness
+    pub witness: PC::Commitment,
+    /// Sumcheck over the rows
+    pub outer: SumcheckProof<F>,
+    /// `Az(r_x)`, `Bz(r_x)`, `Cz(r_x)`
+    pub evaluations: [F; 3],
+    /// Sumcheck over the columns
+    pub inner: SumcheckProof<F>,
+    /// `W(r_y,2..)` and its opening
+    pub witness_value: F,
+    pub opening: PC::Proof,
+}
+
+impl<F: Field, PC: MultilinearCommitmentScheme<F>> Proof<F, PC> {
+    /// Field elements besides the commitment and its opening
+    pub fn num_field_elements(&self) -> usize {
+        let sumchecks = [&self.outer, &self.inner]
+            .iter()
+            .flat_map(|sumcheck| &sumcheck.rounds)
+            .map(Vec::len)
+            .sum::<usize>();
+        sumchecks + self.evaluations.len() + 1
+    }
+}
+
+/// The padded dimensions of an R1CS: `2^row_vars` rows, and `z` split into
+/// two halves of `half` columns
+#[derive(Debug, Clone, Copy)]
+struct Shape {
+    num_instance: usize,
+    row_vars: usize,
+    half: usize,
+}
+
+impl Shape {
+    fn new<F: Field>(r1cs: &R1CS<F>) -> Self {
+        let half = (1 + r1cs.num_instance)
+            .next_power_of_two()
+            .max(r1cs.num_witness.next_power_of_two());
+        Self {
+            num_instance: r1cs.num_instance,
+            row_vars: r1cs.num_constraints().next_power_of_two().trailing_zeros() as usize,
+            half,
+        }
+    }
+
+    fn witness_vars(&self) -> usize {
+        self.half.trailing_zeros() as usize
+    }
+
+    /// Position of column `j` of `(1, x, w)` in the padded `z`
+    fn column(&self, j: usize) -> usize {
+        match j.checked_sub(1 + self.num_instance) {
+            Some(i) => self.half + i,
+            None => j,
+        }
+    }
+
+    /// `(1, x)` padded to the first half
+    fn public<F: Field>(&self, instance: &[F]) -> Vec<F> {
+        let mut public = vec![F::one()];
+        public.extend(instance);
+        public.resize(self.half, F::zero());
+        public
+    }
+
+    /// `w` padded to the second half
+    fn witness<F: Field>(&self, witness: &[F]) -> Vec<F> {
+        let mut witness = witness.to_vec();
+        witness.resize(self.half, F::zero());
+        witness
+    }
+
+    /// `Σ_M r_M M(r_x, y)` for every column `y` of the padded `z`, from the
+    /// table of `eq(r_x, ·)`
+    fn combined_rows<F: Field>(
+        &self,
+        r1cs: &R1CS<F>,
+        eq_rows: &[F],
+        coefficients: [F; 3],
+    ) -> Vec<F> {
+        let mut combined = vec![F::zero(); 2 * self.half];
+        for (matrix, coefficient) in [&r1cs.a, &r1cs.b, &r1cs.c].into_iter().zip(coefficients) {
+            for (row, column, value) in matrix.entries() {
+                let entry = &mut combined[self.column(column)];
+                *entry = *entry + coefficient * eq_rows[row] * value;
+            }
+        }
+        combined
+    }
+}
+
+/// Pads `values` with zeros to the hypercube of `num_vars` variables
+fn pad<F: Field>(mut values: Vec<F>, num_vars: usize) -> MultilinearPolynomial<F> {
+    values.resize(1 << num_vars, F::zero());
+    MultilinearPolynomial::new(values)
+}
+
+/// The dimensions of `r1cs` as little-endian `u64`s, then the non-zero
+/// entries of `A`, `B`, `C` as row, column and value
+fn r1cs_to_bytes<F: Field>(r1cs: &R1CS<F>) -> Vec<u8> {
+    let dimensions = [r1cs.num_instance, r1cs.num_witness, r1cs.num_constraints()];
+    let mut bytes: Vec<u8> = dimensions
+        .iter()
+        .flat_map(|n| (*n as u64).to_le_bytes())
+        .collect();
+    for matrix in [&r1cs.a, &r1cs.b, &r1cs.c] {
+        bytes.extend((matrix.entries().count() as u64).to_le_bytes());
+        for (row, column, value) in matrix.entries() {
+            bytes.extend((row as u64).to_le_bytes());
+            bytes.extend((column as u64).to_le_bytes());
+            bytes.extend(field_to_bytes(&value));
+        }
+    }
+    bytes
+}
+
+/// Starts the transcript with the statement: the constraint system, the
+/// commitment key, the instance and the commitment to the witness, so that
+/// `τ` depends on all of them
+fn transcript<F, K, C>(r1cs: &R1CS<F>, key: &K, instance: &[F], witness: &C) -> Transcript
+where
+    F: Field,
+    K: Encoding,
+    C: Encoding,
+{
+    let mut transcript = Transcript::new(LABEL);
+    transcript.append_message(b"r1cs", &r1cs_to_bytes(r1cs));
+    transcript.append_message(b"key", &key.to_bytes());
+    for value in instance {
+        transcript.append_field(b"instance", value);
+    }
+    transcript.append_message(b"witness", &witness.to_bytes());
+    transcript
+}
+
+/// Absorbs `Az(r_x)`, `Bz(r_x)`, `Cz(r_x)` and draws `r_A`, `r_B`, `r_C`
+fn fold_evaluations<F: Field>(transcript: &mut Transcript, evaluations: &[F; 3]) -> [F; 3] {
+    for value in evaluations {
+        transcript.append_field(b"evaluation", value);
+    }
+    [b"r_A", b"r_B", b"r_C"].map(|label| transcript.challenge(label))
+}
+
+/// Keys for `r1cs`, with a commitment scheme for witnesses padded to a
+/// hypercube
+pub fn setup<F, PC, R>(r1cs: &R1CS<F>, rng: &mut R) -> (ProvingKey<F, PC>, VerifyingKey<F, PC>)
+where
+    F: Field,
+    PC: MultilinearCommitmentScheme<F>,
+    PC::VerifierKey: Clone,
+    R: RngCore,
+{
+    let (ck, vk) = PC::setup(Shape::new(r1cs).witness_vars(), rng);
+    (
+        ProvingKey {
+            r1cs: r1cs.clone(),
+            ck,
+            vk: vk.clone(),
+        },
+        VerifyingKey {
+            r1cs: r1cs.clone(),
+            vk,
+        },
+    )
+}
+
+pub fn prove<F, PC>(
+    pk: &ProvingKey<F, PC>,
+    instance: &[F],
+    witness: &[F],
+) -> Result<Proof<F, PC>, SpartanError>
+where
+    F: Field,
+    PC: MultilinearCommitmentScheme<F>,
+    PC::VerifierKey: Encoding,
+    PC::Commitment: Encoding,
+{
+    let r1cs = &pk.r1cs;
+    r1cs.is_satisfied(instance, witness)?;
+    let shape = Shape::new(r1cs);
+    let padded_witness = shape.witness(witness);
+    let commitment = PC::commit(&pk.ck, &padded_witness);
+    let mut transcript = transcript(r1cs, &pk.vk, instance, &commitment);
+
+    let z = r1cs.assignment(instance, witness)?;
+    let tau: Vec<F> = (0..shape.row_vars)
+        .map(|_| transcript.challenge(b"tau"))
+        .collect();
+    let [az, bz, cz] = [&r1cs.a, &r1cs.b, &r1cs.c].map(|m| pad(m.mul_vector(&z), shape.row_vars));
+    let prover = SumcheckProver::new(
+        vec![
+            MultilinearPolynomial::eq(&tau),
+            az.clone(),
+            bz.clone(),
+            cz.clone(),
+        ],
+        OUTER_DEGREE,
+        |v: &[F]| v[0] * (v[1] * v[2] - v[3]),
+    );
+    let (outer, r_x) = prover.prove(&mut transcript);
+    let evaluations = [az, bz, cz].map(|p| p.evaluate(&r_x));
+
+    let coefficients = fold_evaluations(&mut transcript, &evaluations);
+    let eq_rows = MultilinearPolynomial::eq(&r_x);
+    let combined = shape.combined_rows(r1cs, eq_rows.evaluations(), coefficients);
+    let mut padded_z = shape.public(instance);
+    padded_z.extend(&padded_witness);
+    let prover = SumcheckProver::new(
+        vec![
+            MultilinearPolynomial::new(combined),
+            MultilinearPolynomial::new(padded_z),
+        ],
+        INNER_DEGREE,
+        |v: &[F]| v[0] * v[1],
+    );
+    let (inner, r_y) = prover.prove(&mut transcript);
+
+    let (witness_value, opening) = PC::open(&pk.ck, &padded_witness, &r_y[1..]);
+    Ok(Proof {
+        witness: commitment,
+        outer,
+        evaluations,
+        inner,
+        witness_value,
+        opening,
+    })
+}
+
+pub fn verify<F, PC>(vk: &VerifyingKey<F, PC>, instance: &[F], proof: &Proof<F, PC>) -> bool
+where
+    F: Field,
+    PC: MultilinearCommitmentScheme<F>,
+    PC::VerifierKey: Encoding,
+    PC::Commitment: Encoding,
+{
+    let r1cs = &vk.r1cs;
+    if instance.len() != r1cs.num_instance {
+        return false;
+    }
+    let shape = Shape::new(r1cs);
+    let mut transcript = transcript(r1cs, &vk.vk, instance, &proof.witness);
+
+    let tau: Vec<F> = (0..shape.row_vars)
+        .map(|_| transcript.challenge(b"tau"))
+        .collect();
+    let Some(outer) = SumcheckVerifier::new(shape.row_vars, OUTER_DEGREE).verify(
+        &mut transcript,
+        F::zero(),
+        &proof.outer,
+    ) else {
+        return false;
+    };
+    let [a, b, c] = proof.evaluations;
+    if eq(&tau, &outer.point) * (a * b - c) != outer.value {
+        return false;
+    }
+
+    let coefficients = fold_evaluations(&mut transcript, &proof.evaluations);
+    let claim = coefficients
+        .iter()
+        .zip(&proof.evaluations)
+        .fold(F::zero(), |acc, (r, value)| acc + *r * *value);
+    let Some(inner) = SumcheckVerifier::new(1 + shape.witness_vars(), INNER_DEGREE).verify(
+        &mut transcript,
+        claim,
+        &proof.inner,
+    ) else {
+        return false;
+    };
+    let r_y = &inner.point;
+    let eq_rows = MultilinearPolynomial::eq(&outer.point);
+    let combined =
+        MultilinearPolynomial::new(shape.combined_rows(r1cs, eq_rows.evaluations(), coefficients))
+            .evaluate(r_y);
+    let public = MultilinearPolynomial::new(shape.public(instance)).evaluate(&r_y[1..]);
+    let z = (F::one() - r_y[0]) * public + r_y[0] * proof.witness_value;
+
+    combined * z == inner.value
+        && PC::verify(
+            &vk.vk,
+            &proof.witness,
+            &r_y[1..],
+            proof.witness_value,
+            &proof.opening,
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr, G1Projective};
+    use ark_ff::FftField;
+    use frontend::R1csProgram;
+    use groth16::qap::Qap;
+    use plonk::circuit::{scaled_square_product, scaled_square_product_witness};
+    use polynomial_commitments::{ipa::MultilinearIpa, kzg::Kzg, PolynomialCommitmentScheme};
+    use r1cs::examples;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use univariate_polynomial_iop_zerotest::domain::Domain;
+
+    use super::*;
+
+    type Pcs = MultilinearIpa<G1Projective>;
+
+    /// `c = k a^2 b^2` with `c` public and `k = 7`, as in halo2-trials
+    fn r1cs() -> R1CS<Fr> {
+        examples::scaled_square_product(Fr::from(7u64))
+    }
+
+    fn witness(a: u64, b: u64) -> Vec<Fr> {
+        examples::scaled_square_product_witness(Fr::from(a), Fr::from(b))
+    }
+
+    #[test]
+    fn honest_proof_is_accepted() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (pk, vk) = setup::<_, Pcs, _>(&r1cs(), &mut rng);
+        let proof = prove(&pk, &[Fr::from(252u64)], &witness(2, 3)).unwrap();
+        assert!(verify(&vk, &[Fr::from(252u64)], &proof));
+        assert!(!verify(&vk, &[Fr::from(253u64)], &proof));
+        assert!(!verify(&vk, &[], &proof));
+    }
+
+    #[test]
+    fn forgeries_are_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let (pk, vk) = setup::<_, Pcs, _>(&r1cs(), &mut rng);
+        let instance = [Fr::from(252u64)];
+        let proof = prove(&pk, &instance, &witness(2, 3)).unwrap();
+
+        let mut tampered = proof.clone();
+        tampered.evaluations[2] += Fr::from(1u64);
+        assert!(!verify(&vk, &instance, &tampered));
+        let mut tampered = proof.clone();
+        tampered.witness_value += Fr::from(1u64);
+        assert!(!verify(&vk, &instance, &tampered));
+        let mut tampered = proof.clone();
+        tampered.inner.rounds[1][0] += Fr::from(1u64);
+        assert!(!verify(&vk, &instance, &tampered));
+        // A commitment to another witness changes every challenge
+        let mut tampered = proof.clone();
+        tampered.witness = Pcs::commit(&pk.ck, &witness(1, 6));
+        assert!(!verify(&vk, &instance, &tampered));
+        // Swapping `A` and `B` keeps the witness valid, but the transcript
+        // binds the constraint system
+        let mut swapped = vk.r1cs.clone();
+        std::mem::swap(&mut swapped.a, &mut swapped.b);
+        assert_eq!(swapped.is_satisfied(&instance, &witness(2, 3)), Ok(()));
+        let swapped = VerifyingKey {
+            r1cs: swapped,
+            vk: vk.vk.clone(),
+        };
+        assert!(!verify(&swapped, &instance, &proof));
+
+        let mut wrong = witness(2, 3);
+        wrong[3] = Fr::from(37u64);
+        assert_eq!(
+            prove(&pk, &instance, &wrong).unwrap_err(),
+            SpartanError::R1cs(R1csError::Unsatisfied(1))
+        );
+    }
+
+    /// Bytes of the compressed group elements and the field elements
+    fn spartan_size(proof: &Proof<Fr, Pcs>) -> usize {
+        let points = [proof.witness]
+            .iter()
+            .chain(&proof.opening.left)
+            .chain(&proof.opening.right)
+            .map(|point| point.to_bytes().len())
+            .sum::<usize>();
+        let scalars = proof.num_field_elements() + 1;
+        points + scalars * field_to_bytes(&proof.opening.a).len()
+    }
+
+    fn groth16_size(proof: &groth16::Proof<Bls12_381>) -> usize {
+        proof.a.to_bytes().len() + proof.b.to_bytes().len() + proof.c.to_bytes().len()
+    }
+
+    /// Proves and verifies with Spartan, returning the size of the proof
+    fn spartan(r1cs: &R1CS<Fr>, instance: &[Fr], witness: &[Fr]) -> usize {
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let (pk, vk) = setup::<_, Pcs, _>(r1cs, &mut rng);
+        let proof = prove(&pk, instance, witness).unwrap();
+        assert!(verify(&vk, instance, &proof));
+        spartan_size(&proof)
+    }
+
+    /// Proves and verifies with Groth16, returning the size of the proof
+    fn groth16(r1cs: &R1CS<Fr>, instance: &[Fr], witness: &[Fr]) -> usize {
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        // One more row for the constant and each instance variable
+        let n = (r1cs.num_constraints() + 1 + r1cs.num_instance).next_power_of_two();
+        let domain = Domain::new(Fr::get_root_of_unity(n as u64).unwrap(), n);
+        let pk = groth16::setup::<Bls12_381, _>(Qap::new(r1cs, domain), &mut rng);
+        let proof = groth16::prove(&pk, instance, witness, &mut rng).unwrap();
+        assert!(groth16::verify(&pk.vk, instance, &proof));
+        groth16_size(&proof)
+    }
+
+    #[test]
+    fn proof_sizes_on_identical_circuits() {
+        // c = 7 a^2 b^2 on all three backends
+        let instance = [Fr::from(252u64)];
+        let r1cs = r1cs();
+        let spartan = spartan(&r1cs, &instance, &witness(2, 3));
+
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        let domain = Domain::new(Fr::get_root_of_unity(8).unwrap(), 8);
+        let pk = groth16::setup::<Bls12_381, _>(Qap::new(&r1cs, domain), &mut rng);
+        let proof = groth16::prove(&pk, &instance, &witness(2, 3), &mut rng).unwrap();
+        assert!(groth16::verify(&pk.vk, &instance, &proof));
+        let groth16 = groth16_size(&proof);
+
+        let circuit = scaled_square_product(Fr::from(7u64), domain);
+        let (ck, kzg) = Kzg::<Bls12_381>::setup(13, &mut rng);
+        let pk = plonk::setup(&ck, circuit);
+        let witness = scaled_square_product_witness(Fr::from(7u64), Fr::from(2u64), Fr::from(3u64));
+        let proof = plonk::prove(&ck, &pk, &witness, &mut rng).unwrap();
+        assert!(plonk::verify(&kzg, &pk.vk, &instance, &proof));
+        let plonk = proof.size();
+        assert_eq!((spartan, groth16, plonk), (944, 192, 624));
+    }
+
+    #[test]
+    fn spartan_proofs_grow_logarithmically() {
+        // `x^(n + 1)` for a private `x` costs n + 1 constraints
+        let sizes = [15, 255].map(|n| {
+            let source = format!(
+                "public out; private x; let acc = x;
+                 for i in 0..{n} {{ acc = acc * x; }}
+                 assert acc == out;"
+            );
+            let ir = frontend::compile(&source).unwrap();
+            let program = R1csProgram::<Fr>::compile(&ir);
+            let x = Fr::from(3u64);
+            let out = (0..n).fold(x, |acc, _| acc * x);
+            let (instance, witness) = program.assignment(&ir.evaluate(&[out, x]).unwrap());
+            (
+                program.r1cs.num_constraints(),
+                spartan(&program.r1cs, &instance, &witness),
+                groth16(&program.r1cs, &instance, &witness),
+            )
+        });
+        // Four more variables for rows and for columns: 4 field elements
+        // per outer round, 3 per inner round and 2 points per IPA round,
+        // while Groth16 stays at three group elements
+        assert_eq!(
+            sizes,
+            [
+                (16, 1584, 192),
+                (256, 1584 + 4 * (4 * 32 + 3 * 32 + 2 * 48), 192)
+            ]
+        );
+    }
+}
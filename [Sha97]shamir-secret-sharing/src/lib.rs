@@ -3,8 +3,8 @@ use num_traits::Zero;
 use polynomial::Polynomial;
 use rand::RngCore;
 use rand::SeedableRng;
-use simba::scalar::ComplexField;
 use std::fmt::Debug;
+use std::ops::Div;
 use std::ops::Mul;
 use std::ops::Sub;
 
@@ -12,7 +12,9 @@ use std::ops::Sub;
 /// (k, n)-threshold scheme based on polynomial interpolation.
 /// Here, `n` is the number of secret parts generated by the
 /// scheme, knowledge of any `k` of which can reconstruct original
-/// secret
+/// secret.
+/// Shares can be taken over any field `T`, be it `f64` or one of the
+/// finite fields implementing `finite_fields::Field`.
 pub struct ShamirSecret<T, const K: usize, const N: usize> {
     /// The underlying secret value to be broken up
     /// into secret "parts"
@@ -38,7 +40,7 @@ where
 
 impl<T, const K: usize, const N: usize> ShamirSecret<T, K, N>
 where
-    T: From<u32> + Debug + Clone + Zero + One + Mul<Output = T> + Sub<Output = T> + ComplexField,
+    T: From<u32> + Debug + Clone + Zero + One + Mul<Output = T> + Sub<Output = T> + Div<Output = T>,
 {
     /// Generates a Shamir's secret generator for a given
     /// secret value `secret` such that any `k` of `n` shares
@@ -51,7 +53,7 @@ where
     /// Generate a Shamir's secret from `K` shares
     pub fn new_from_shares(shares: [SecretShare<T>; K]) -> Self {
         let evaluations: Vec<(T, T)> = shares.into_iter().map(|s| s.into_tuple()).collect();
-        let reconstructed_poly = Polynomial::new_from_evals(&evaluations);
+        let reconstructed_poly = Polynomial::new_from_evals_lagrange(&evaluations);
 
         Self {
             secret: reconstructed_poly.eval(T::zero()),
@@ -96,6 +98,9 @@ where
 
 #[cfg(test)]
 mod tests {
+    use finite_fields::{Field, Halo2Field, PrimeField};
+    use halo2_proofs::pasta::Fp as PallasBase;
+
     use crate::ShamirSecret;
 
     #[test]
@@ -110,4 +115,28 @@ mod tests {
 
         assert_eq!(reconstructed_from_shares.get_secret() as i32, secret_value)
     }
+
+    /// Splits `secret` into 5 shares and reconstructs it from each window
+    /// of 3 consecutive ones
+    fn shamir_roundtrip<F: Field + From<u32>>(secret: u32) {
+        let shares =
+            ShamirSecret::<F, 3, 5>::new_from_secret(secret.into()).generate_secret_shares();
+        for window in shares.windows(3) {
+            let given_shares = [window[0].clone(), window[1].clone(), window[2].clone()];
+            let reconstructed = ShamirSecret::<F, 3, 5>::new_from_shares(given_shares);
+            assert_eq!(reconstructed.get_secret(), F::from(secret));
+        }
+    }
+
+    #[test]
+    fn shamir_secret_sharing_finite_fields() {
+        fn check<F: Field + From<u32>>() {
+            shamir_roundtrip::<F>(2);
+            shamir_roundtrip::<F>(12345);
+        }
+        check::<PrimeField<99679>>();
+        check::<PrimeField<0xffff_ffff_0000_0001>>();
+        check::<ark_bls12_381::Fr>();
+        check::<Halo2Field<PallasBase>>();
+    }
 }
@@ -0,0 +1,73 @@
+//! Prover cost against table size: the precomputation grows with `N`, while
+//! proving a lookup of `m = 4` values should not (Caulk+) or only with
+//! `log N` (Caulk). Run with `cargo bench -p caulk`.
+
+use std::time::{Duration, Instant};
+
+use ::caulk::{caulk, caulk_plus, Table};
+use ark_bls12_381::{Bls12_381, Fr};
+use ark_ff::FftField;
+use polynomial_commitments::{kzg::Kzg, PolynomialCommitmentScheme};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use univariate_polynomial_iop_zerotest::domain::Domain;
+
+const SUBVECTOR: usize = 4;
+const RUNS: u32 = 5;
+
+fn time<T>(mut f: impl FnMut() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn main() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let domain = Domain::new(Fr::get_root_of_unity(SUBVECTOR as u64).unwrap(), SUBVECTOR);
+
+    println!(
+        "{:>6} {:>16} {:>16} {:>16}",
+        "N", "precompute", "caulk prove", "caulk+ prove"
+    );
+    for log_size in [6, 8, 10] {
+        let size = 1usize << log_size;
+        let (ck, vk) = Kzg::<Bls12_381>::setup(size.max(9 * SUBVECTOR), &mut rng);
+        let values: Vec<Fr> = (0..size as u64).map(|i| Fr::from(3 * i + 1)).collect();
+        let table_domain = Domain::new(Fr::get_root_of_unity(size as u64).unwrap(), size);
+        let (table, precompute) = time(|| Table::new(&ck, &vk, table_domain, values.clone()));
+
+        let positions: Vec<usize> = (0..SUBVECTOR).map(|j| (j * 7 + 1) % size).collect();
+        let (mut caulk, mut caulk_plus) = (Duration::ZERO, Duration::ZERO);
+        for _ in 0..RUNS {
+            let ((commitment, proof), elapsed) =
+                time(|| caulk::prove(&ck, &vk, &table, &domain, &positions, &mut rng));
+            assert!(caulk::verify(
+                &vk,
+                table.commitment(),
+                &domain,
+                &commitment,
+                &proof
+            ));
+            caulk += elapsed;
+
+            let ((commitment, proof), elapsed) =
+                time(|| caulk_plus::prove(&ck, &vk, &table, &domain, &positions, &mut rng));
+            assert!(caulk_plus::verify(
+                &vk,
+                table.commitment(),
+                &domain,
+                &commitment,
+                &proof
+            ));
+            caulk_plus += elapsed;
+        }
+
+        println!(
+            "{:>6} {:>16?} {:>16?} {:>16?}",
+            size,
+            precompute,
+            caulk / RUNS,
+            caulk_plus / RUNS
+        );
+    }
+}
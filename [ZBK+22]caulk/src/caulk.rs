@@ -0,0 +1,344 @@
+//! Caulk [ZBK+22] for a subvector of `m` values. The pairing check
+//!
+//! `e(C - [C_I]_1, [1]_2) = e([Z_I]_1, W)`
+//!
+//! for `W = [(C - C_I) / Z_I]_2` aggregated from the precomputed openings
+//! shows that `C` and `C_I` agree on the roots of `Z_I`. Unlike in Caulk+,
+//! these roots still have to be shown to be positions of the table, i.e.
+//! `N`-th roots of unity. As they are the values of `u` on `V_m`, this is
+//! the unity proof: with `N = 2^n`, the prover commits to `U_s`
+//! interpolating `u^(2^s)` on `V_m` for `s = 1, ..., n`, and a zero-test on
+//! `V_m` checks
+//!
+//! `U_(s+1) - U_s^2 = 0` for `U_0 = u`, and `U_n - 1 = 0`
+//!
+//! alongside the linking constraints. The prover is `O(m^2 + m log N)`.
+//! The paper packs the `U_s` into a single polynomial over a larger domain
+//! to keep the proof constant-sized; here they are committed to one by one,
+//! so the proof has `log N` more group elements.
+
+use elliptic_curves::{pairing::Pairing, Encoding};
+use finite_fields::Field;
+use num_traits::One;
+use polynomial::Polynomial;
+use polynomial_commitments::{
+    kzg::{Kzg, KzgCommitterKey, KzgVerifierKey},
+    random_field_element, PolynomialCommitmentScheme,
+};
+use rand::RngCore;
+use univariate_polynomial_iop_zerotest::domain::Domain;
+
+use crate::{
+    link::{constant, open, transcript, verify_openings, Openings, Witness},
+    table::{Table, TableCommitment},
+    Scalar,
+};
+
+const LABEL: &[u8] = b"caulk";
+
+#[derive(Debug, Clone)]
+pub struct Proof<E: Pairing> {
+    /// `[Z_I]_1`
+    pub vanishing: E::G1,
+    /// `[C_I]_1`
+    pub interpolation: E::G1,
+    /// `[u]_1`
+    pub positions: E::G1,
+    /// `[U_s]_1` for `s = 1, ..., log N`
+    pub powers: Vec<E::G1>,
+    /// `W = [(C - C_I) / Z_I]_2`
+    pub quotient_g2: E::G2,
+    /// `[q]_1` for the zero-test on `V_m`
+    pub quotient: E::G1,
+    /// `u`, `φ`, `q` and the `U_s` at `α`
+    pub at_alpha: Openings<E>,
+    /// `Z_I` and `C_I` at `u(α)`
+    pub at_positions: Openings<E>,
+}
+
+/// `log N`, the number of squarings from a position to one
+fn rounds(size: usize) -> usize {
+    assert!(size.is_power_of_two(), "table size must be a power of two");
+    size.trailing_zeros() as usize
+}
+
+/// `Σ_s χ^s (U_(s+1) - U_s^2) + χ^n (U_n - 1)` for any representation of
+/// the `U_s`, such as polynomials or their values at a point
+fn unity_constraint<T>(
+    powers: &[T],
+    challenge: T,
+    one: T,
+    mul: impl Fn(&T, &T) -> T,
+    sub: impl Fn(&T, &T) -> T,
+    add: impl Fn(&T, &T) -> T,
+) -> T
+where
+    T: Clone,
+{
+    let last = powers.last().expect("U_0 is always there");
+    let mut acc = sub(last, &one);
+    for window in powers.windows(2).rev() {
+        acc = add(
+            &mul(&acc, &challenge),
+            &sub(&window[1], &mul(&window[0], &window[0])),
+        );
+    }
+    acc
+}
+
+/// Proves that the subvector of the table at `positions` is a subvector,
+/// without revealing the positions. Returns the commitment `[φ(τ)]_1` to
+/// the blinded subvector on `domain` alongside the proof.
+pub fn prove<E, R>(
+    ck: &KzgCommitterKey<E>,
+    vk: &KzgVerifierKey<E>,
+    table: &Table<E>,
+    domain: &Domain<Scalar<E>>,
+    positions: &[usize],
+    rng: &mut R,
+) -> (E::G1, Proof<E>)
+where
+    E: Pairing,
+    E::G1: Encoding,
+    E::G2: Encoding,
+    R: RngCore,
+{
+    let witness = Witness::new(table, domain, positions, rng);
+    let commitment = Kzg::commit(ck, &witness.subvector);
+    let mut transcript = transcript::<E>(LABEL, table.commitment(), domain, &commitment);
+
+    // `U_s` blinded with a multiple of `Z_(V_m)`, as each is opened once
+    let omega = table.domain().generator();
+    let mut roots: Vec<Scalar<E>> = positions.iter().map(|i| omega.pow([*i as u64])).collect();
+    let mut unity = vec![witness.positions.clone()];
+    for _ in 0..rounds(table.domain().size()) {
+        roots.iter_mut().for_each(|root| *root = root.square());
+        let mask = constant(random_field_element::<Scalar<E>, _>(rng));
+        unity.push(&domain.interpolate(&roots) + &(&mask * &domain.vanishing_polynomial()));
+    }
+
+    let vanishing = Kzg::commit(ck, &witness.vanishing);
+    let interpolation = Kzg::commit(ck, &witness.interpolation);
+    let positions = Kzg::commit(ck, &witness.positions);
+    let powers: Vec<E::G1> = unity[1..].iter().map(|p| Kzg::commit(ck, p)).collect();
+    transcript.append_point(b"vanishing", &vanishing);
+    transcript.append_point(b"interpolation", &interpolation);
+    transcript.append_point(b"positions", &positions);
+    for power in &powers {
+        transcript.append_point(b"power", power);
+    }
+
+    let quotient_g2 = witness.quotient(vk);
+    transcript.append_point(b"quotient g2", &quotient_g2);
+    let linking: Scalar<E> = transcript.challenge(b"linking");
+
+    let unity_constraint = unity_constraint(
+        &unity,
+        constant(linking),
+        constant(Scalar::<E>::one()),
+        |a, b| a * b,
+        |a, b| a - b,
+        |a, b| a + b,
+    );
+    let constraint =
+        &witness.linking_constraint(linking) + &(&unity_constraint * &constant(linking.square()));
+    let (zero_test, remainder) = constraint.div_rem(&domain.vanishing_polynomial());
+    debug_assert!(remainder.is_zero());
+    let quotient = Kzg::commit(ck, &zero_test);
+    transcript.append_point(b"quotient", &quotient);
+    let alpha: Scalar<E> = transcript.challenge(b"alpha");
+
+    let mut at_alpha: Vec<Polynomial<Scalar<E>>> = vec![
+        witness.positions.clone(),
+        witness.subvector.clone(),
+        zero_test,
+    ];
+    at_alpha.extend(unity.into_iter().skip(1));
+    let at_alpha = open(ck, &mut transcript, &at_alpha, alpha);
+    let at_positions = open(
+        ck,
+        &mut transcript,
+        &[witness.vanishing.clone(), witness.interpolation.clone()],
+        at_alpha.values[0],
+    );
+
+    (
+        commitment,
+        Proof {
+            vanishing,
+            interpolation,
+            positions,
+            powers,
+            quotient_g2,
+            quotient,
+            at_alpha,
+            at_positions,
+        },
+    )
+}
+
+/// Checks that `commitment` is to a subvector of the table on `domain`
+pub fn verify<E>(
+    vk: &KzgVerifierKey<E>,
+    table: &TableCommitment<E>,
+    domain: &Domain<Scalar<E>>,
+    commitment: &E::G1,
+    proof: &Proof<E>,
+) -> bool
+where
+    E: Pairing,
+    E::G1: Encoding,
+    E::G2: Encoding,
+{
+    let rounds = rounds(table.size);
+    if proof.powers.len() != rounds
+        || proof.at_alpha.values.len() != 3 + rounds
+        || proof.at_positions.values.len() != 2
+    {
+        return false;
+    }
+
+    let mut transcript = transcript::<E>(LABEL, table, domain, commitment);
+    transcript.append_point(b"vanishing", &proof.vanishing);
+    transcript.append_point(b"interpolation", &proof.interpolation);
+    transcript.append_point(b"positions", &proof.positions);
+    for power in &proof.powers {
+        transcript.append_point(b"power", power);
+    }
+    transcript.append_point(b"quotient g2", &proof.quotient_g2);
+    let linking: Scalar<E> = transcript.challenge(b"linking");
+    transcript.append_point(b"quotient", &proof.quotient);
+    let alpha: Scalar<E> = transcript.challenge(b"alpha");
+
+    let agrees = E::pairing(
+        &(table.commitment - proof.interpolation),
+        &vk.powers_of_tau_g2[0],
+    ) == E::pairing(&proof.vanishing, &proof.quotient_g2);
+    if !agrees {
+        return false;
+    }
+
+    let [u, phi, q] = [0, 1, 2].map(|i| proof.at_alpha.values[i]);
+    let [vanishing, interpolation] = [0, 1].map(|i| proof.at_positions.values[i]);
+    let mut unity = vec![u];
+    unity.extend_from_slice(&proof.at_alpha.values[3..]);
+    let unity = unity_constraint(
+        &unity,
+        linking,
+        Scalar::<E>::one(),
+        |a, b| *a * *b,
+        |a, b| *a - *b,
+        |a, b| *a + *b,
+    );
+
+    let mut at_alpha = vec![proof.positions, *commitment, proof.quotient];
+    at_alpha.extend_from_slice(&proof.powers);
+    vanishing + linking * (interpolation - phi) + linking.square() * unity
+        == domain.evaluate_vanishing_polynomial(alpha) * q
+        && verify_openings(vk, &mut transcript, &at_alpha, alpha, &proof.at_alpha)
+        && verify_openings(
+            vk,
+            &mut transcript,
+            &[proof.vanishing, proof.interpolation],
+            u,
+            &proof.at_positions,
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_ff::FftField;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+
+    type Bls = Kzg<Bls12_381>;
+
+    fn table(ck: &KzgCommitterKey<Bls12_381>, vk: &KzgVerifierKey<Bls12_381>) -> Table<Bls12_381> {
+        let domain = Domain::new(Fr::get_root_of_unity(16).unwrap(), 16);
+        let values = (0..16u64).map(|i| Fr::from(i * i + 1)).collect();
+        Table::new(ck, vk, domain, values)
+    }
+
+    #[test]
+    fn unity_constraint_of_roots_of_unity() {
+        let omega = Fr::get_root_of_unity(8).unwrap();
+        let squarings = |x: Fr| {
+            std::iter::successors(Some(x), |x| Some(x.square()))
+                .take(4)
+                .collect::<Vec<_>>()
+        };
+        let evaluate = |powers: &[Fr]| {
+            unity_constraint(
+                powers,
+                Fr::from(7u64),
+                Fr::from(1u64),
+                |a, b| *a * *b,
+                |a, b| *a - *b,
+                |a, b| *a + *b,
+            )
+        };
+        assert_eq!(evaluate(&squarings(omega.pow([3]))), Fr::from(0u64));
+        // A 16-th root of unity needs one more squaring
+        let root = Fr::get_root_of_unity(16).unwrap();
+        assert_ne!(evaluate(&squarings(root)), Fr::from(0u64));
+        let mut wrong = squarings(omega);
+        wrong[2] = -wrong[2];
+        assert_ne!(evaluate(&wrong), Fr::from(0u64));
+    }
+
+    #[test]
+    fn subvector_is_accepted() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (ck, vk) = Bls::setup(36, &mut rng);
+        let table = table(&ck, &vk);
+        let domain = Domain::new(Fr::get_root_of_unity(4).unwrap(), 4);
+
+        let positions = table.positions(&[2u64, 50, 2, 226].map(Fr::from)).unwrap();
+        let (commitment, proof) = prove(&ck, &vk, &table, &domain, &positions, &mut rng);
+        assert_eq!(proof.powers.len(), 4);
+        assert!(verify(
+            &vk,
+            table.commitment(),
+            &domain,
+            &commitment,
+            &proof
+        ));
+    }
+
+    #[test]
+    fn other_commitments_are_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let (ck, vk) = Bls::setup(36, &mut rng);
+        let table = table(&ck, &vk);
+        let domain = Domain::new(Fr::get_root_of_unity(4).unwrap(), 4);
+        let (commitment, proof) = prove(&ck, &vk, &table, &domain, &[1, 3, 5, 7], &mut rng);
+
+        let outside = Bls::commit(&ck, &domain.interpolate(&[4u64, 10, 26, 50].map(Fr::from)));
+        assert!(!verify(&vk, table.commitment(), &domain, &outside, &proof));
+
+        // Dropping a round of the unity proof
+        let mut truncated = proof.clone();
+        truncated.powers.pop();
+        truncated.at_alpha.values.pop();
+        assert!(!verify(
+            &vk,
+            table.commitment(),
+            &domain,
+            &commitment,
+            &truncated
+        ));
+
+        let mut swapped = proof.clone();
+        swapped.powers.swap(0, 1);
+        assert!(!verify(
+            &vk,
+            table.commitment(),
+            &domain,
+            &commitment,
+            &swapped
+        ));
+    }
+}
@@ -0,0 +1,248 @@
+//! Caulk+ [PK22]: the roots of `Z_I` are positions of the table iff `Z_I`
+//! divides `Z_V = X^N - 1`. Together with `Z_I | C - C_I`, this is one
+//! pairing check on a random combination,
+//!
+//! `e(C - [C_I]_1 + χ [Z_V]_1, [1]_2) = e([Z_I]_1, W)`
+//!
+//! for `W = [(C - C_I) / Z_I + χ Z_V / Z_I]_2`, both of which the prover
+//! aggregates from the precomputed openings. What is left is the zero-test
+//! on `V_m` linking `Z_I` and `C_I` to the subvector. The prover is
+//! `O(m^2)`, whatever the size of the table.
+
+use elliptic_curves::{pairing::Pairing, Encoding, PrimeOrderGroup};
+use polynomial_commitments::{
+    kzg::{Kzg, KzgCommitterKey, KzgVerifierKey},
+    PolynomialCommitmentScheme,
+};
+use rand::RngCore;
+use univariate_polynomial_iop_zerotest::domain::Domain;
+
+use crate::{
+    link::{open, transcript, verify_openings, Openings, Witness},
+    table::{Table, TableCommitment},
+    Scalar,
+};
+
+const LABEL: &[u8] = b"caulk-plus";
+
+#[derive(Debug, Clone)]
+pub struct Proof<E: Pairing> {
+    /// `[Z_I]_1`
+    pub vanishing: E::G1,
+    /// `[C_I]_1`
+    pub interpolation: E::G1,
+    /// `[u]_1`
+    pub positions: E::G1,
+    /// `W = [(C - C_I) / Z_I + χ Z_V / Z_I]_2`
+    pub quotient_g2: E::G2,
+    /// `[q]_1` for the zero-test on `V_m`
+    pub quotient: E::G1,
+    /// `u`, `φ` and `q` at `α`
+    pub at_alpha: Openings<E>,
+    /// `Z_I` and `C_I` at `u(α)`
+    pub at_positions: Openings<E>,
+}
+
+/// Proves that the subvector of the table at `positions` is a subvector,
+/// without revealing the positions. Returns the commitment `[φ(τ)]_1` to
+/// the blinded subvector on `domain` alongside the proof.
+pub fn prove<E, R>(
+    ck: &KzgCommitterKey<E>,
+    vk: &KzgVerifierKey<E>,
+    table: &Table<E>,
+    domain: &Domain<Scalar<E>>,
+    positions: &[usize],
+    rng: &mut R,
+) -> (E::G1, Proof<E>)
+where
+    E: Pairing,
+    E::G1: Encoding,
+    E::G2: Encoding,
+    R: RngCore,
+{
+    let witness = Witness::new(table, domain, positions, rng);
+    let commitment = Kzg::commit(ck, &witness.subvector);
+    let mut transcript = transcript::<E>(LABEL, table.commitment(), domain, &commitment);
+
+    let vanishing = Kzg::commit(ck, &witness.vanishing);
+    let interpolation = Kzg::commit(ck, &witness.interpolation);
+    let positions = Kzg::commit(ck, &witness.positions);
+    transcript.append_point(b"vanishing", &vanishing);
+    transcript.append_point(b"interpolation", &interpolation);
+    transcript.append_point(b"positions", &positions);
+    let chi: Scalar<E> = transcript.challenge(b"chi");
+
+    let quotient_g2 = witness.quotient(vk) + witness.vanishing_quotient().mul_scalar(&chi);
+    transcript.append_point(b"quotient g2", &quotient_g2);
+    let linking: Scalar<E> = transcript.challenge(b"linking");
+
+    let (zero_test, remainder) = witness
+        .linking_constraint(linking)
+        .div_rem(&domain.vanishing_polynomial());
+    debug_assert!(remainder.is_zero());
+    let quotient = Kzg::commit(ck, &zero_test);
+    transcript.append_point(b"quotient", &quotient);
+    let alpha: Scalar<E> = transcript.challenge(b"alpha");
+
+    let at_alpha = open(
+        ck,
+        &mut transcript,
+        &[
+            witness.positions.clone(),
+            witness.subvector.clone(),
+            zero_test,
+        ],
+        alpha,
+    );
+    let at_positions = open(
+        ck,
+        &mut transcript,
+        &[witness.vanishing.clone(), witness.interpolation.clone()],
+        at_alpha.values[0],
+    );
+
+    (
+        commitment,
+        Proof {
+            vanishing,
+            interpolation,
+            positions,
+            quotient_g2,
+            quotient,
+            at_alpha,
+            at_positions,
+        },
+    )
+}
+
+/// Checks that `commitment` is to a subvector of the table on `domain`
+pub fn verify<E>(
+    vk: &KzgVerifierKey<E>,
+    table: &TableCommitment<E>,
+    domain: &Domain<Scalar<E>>,
+    commitment: &E::G1,
+    proof: &Proof<E>,
+) -> bool
+where
+    E: Pairing,
+    E::G1: Encoding,
+    E::G2: Encoding,
+{
+    let mut transcript = transcript::<E>(LABEL, table, domain, commitment);
+    transcript.append_point(b"vanishing", &proof.vanishing);
+    transcript.append_point(b"interpolation", &proof.interpolation);
+    transcript.append_point(b"positions", &proof.positions);
+    let chi: Scalar<E> = transcript.challenge(b"chi");
+    transcript.append_point(b"quotient g2", &proof.quotient_g2);
+    let linking: Scalar<E> = transcript.challenge(b"linking");
+    transcript.append_point(b"quotient", &proof.quotient);
+    let alpha: Scalar<E> = transcript.challenge(b"alpha");
+
+    let divides = E::pairing(
+        &(table.commitment - proof.interpolation + table.vanishing.mul_scalar(&chi)),
+        &vk.powers_of_tau_g2[0],
+    ) == E::pairing(&proof.vanishing, &proof.quotient_g2);
+    if !divides || proof.at_alpha.values.len() != 3 || proof.at_positions.values.len() != 2 {
+        return false;
+    }
+
+    let [u, phi, q] = [0, 1, 2].map(|i| proof.at_alpha.values[i]);
+    let [vanishing, interpolation] = [0, 1].map(|i| proof.at_positions.values[i]);
+    vanishing + linking * (interpolation - phi) == domain.evaluate_vanishing_polynomial(alpha) * q
+        && verify_openings(
+            vk,
+            &mut transcript,
+            &[proof.positions, *commitment, proof.quotient],
+            alpha,
+            &proof.at_alpha,
+        )
+        && verify_openings(
+            vk,
+            &mut transcript,
+            &[proof.vanishing, proof.interpolation],
+            u,
+            &proof.at_positions,
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_ff::FftField;
+    use elliptic_curves::Group;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+
+    type Bls = Kzg<Bls12_381>;
+
+    fn table(ck: &KzgCommitterKey<Bls12_381>, vk: &KzgVerifierKey<Bls12_381>) -> Table<Bls12_381> {
+        let domain = Domain::new(Fr::get_root_of_unity(16).unwrap(), 16);
+        let values = (0..16u64).map(|i| Fr::from(i * i + 1)).collect();
+        Table::new(ck, vk, domain, values)
+    }
+
+    #[test]
+    fn subvector_is_accepted() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (ck, vk) = Bls::setup(36, &mut rng);
+        let table = table(&ck, &vk);
+        let domain = Domain::new(Fr::get_root_of_unity(4).unwrap(), 4);
+
+        // Repeated positions are fine
+        let positions = table.positions(&[2u64, 50, 2, 226].map(Fr::from)).unwrap();
+        let (commitment, proof) = prove(&ck, &vk, &table, &domain, &positions, &mut rng);
+        assert!(verify(
+            &vk,
+            table.commitment(),
+            &domain,
+            &commitment,
+            &proof
+        ));
+
+        // The commitment to the subvector is blinded, and so are the others
+        let (other, second) = prove(&ck, &vk, &table, &domain, &positions, &mut rng);
+        assert_ne!(commitment, other);
+        assert_ne!(proof.vanishing, second.vanishing);
+        assert_ne!(proof.positions, second.positions);
+    }
+
+    #[test]
+    fn other_commitments_are_rejected() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let (ck, vk) = Bls::setup(36, &mut rng);
+        let table = table(&ck, &vk);
+        let domain = Domain::new(Fr::get_root_of_unity(4).unwrap(), 4);
+        let (commitment, proof) = prove(&ck, &vk, &table, &domain, &[1, 3, 5, 7], &mut rng);
+
+        // A subvector with 4 in place of 2 is not in the table
+        let outside = Bls::commit(&ck, &domain.interpolate(&[4u64, 10, 26, 50].map(Fr::from)));
+        assert!(!verify(&vk, table.commitment(), &domain, &outside, &proof));
+
+        let mut tampered = proof.clone();
+        tampered.quotient_g2 += <Bls12_381 as Pairing>::G2::generator();
+        assert!(!verify(
+            &vk,
+            table.commitment(),
+            &domain,
+            &commitment,
+            &tampered
+        ));
+
+        // Another table of the same size
+        let other = Table::new(
+            &ck,
+            &vk,
+            *table.domain(),
+            table.values().iter().map(|v| *v + Fr::from(1u64)).collect(),
+        );
+        assert!(!verify(
+            &vk,
+            other.commitment(),
+            &domain,
+            &commitment,
+            &proof
+        ));
+    }
+}
@@ -0,0 +1,104 @@
+//! Radix-2 FFTs over `<ω>` for group elements, with scalars from the
+//! field, and the cyclic correlations Caulk computes with them.
+
+use elliptic_curves::PrimeOrderGroup;
+use finite_fields::{
+    fft::{fft, field_fft, field_ifft, ifft},
+    Field,
+};
+
+pub fn group_fft<G: PrimeOrderGroup>(points: &[G], generator: G::ScalarField) -> Vec<G> {
+    fft(points, generator, &|point, scalar| {
+        point.mul_scalar(&scalar)
+    })
+}
+
+pub fn group_ifft<G: PrimeOrderGroup>(points: &[G], generator: G::ScalarField) -> Vec<G> {
+    ifft(points, generator, &|point, scalar| {
+        point.mul_scalar(&scalar)
+    })
+}
+
+/// `x_i = Σ_j a_j k_(j-i)` with indices mod `n`, a cyclic correlation of
+/// group elements with field elements computed with three FFTs
+pub fn group_correlation<G: PrimeOrderGroup>(
+    points: &[G],
+    kernel: &[G::ScalarField],
+    generator: G::ScalarField,
+) -> Vec<G> {
+    // Correlating with `k` is convolving with `k'_j = k_(-j)`
+    let n = kernel.len();
+    let reflected: Vec<G::ScalarField> = (0..n).map(|j| kernel[(n - j) % n]).collect();
+    let products: Vec<G> = group_fft(points, generator)
+        .iter()
+        .zip(field_fft(&reflected, generator))
+        .map(|(point, scalar)| point.mul_scalar(&scalar))
+        .collect();
+    group_ifft(&products, generator)
+}
+
+/// [`group_correlation`] for field elements
+pub fn field_correlation<F: Field>(values: &[F], kernel: &[F], generator: F) -> Vec<F> {
+    let n = kernel.len();
+    let reflected: Vec<F> = (0..n).map(|j| kernel[(n - j) % n]).collect();
+    let products: Vec<F> = field_fft(values, generator)
+        .iter()
+        .zip(field_fft(&reflected, generator))
+        .map(|(a, b)| *a * b)
+        .collect();
+    field_ifft(&products, generator)
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Fr, G1Projective};
+    use ark_ff::FftField;
+    use elliptic_curves::{scalar_mul::msm, Group};
+    use num_traits::Zero;
+
+    use super::*;
+
+    #[test]
+    fn group_transforms_match_field_transforms() {
+        let omega = Fr::get_root_of_unity(8).unwrap();
+        let values = [3u64, 1, 4, 1, 5, 9, 2, 6].map(Fr::from);
+        let in_exponent = |values: &[Fr]| -> Vec<G1Projective> {
+            values
+                .iter()
+                .map(|v| G1Projective::generator().mul_scalar(v))
+                .collect()
+        };
+        let points = in_exponent(&values);
+        assert_eq!(
+            group_fft(&points, omega),
+            in_exponent(&field_fft(&values, omega))
+        );
+        assert_eq!(group_ifft(&group_fft(&points, omega), omega), points);
+    }
+
+    #[test]
+    fn correlations_match_the_definition() {
+        let omega = Fr::get_root_of_unity(4).unwrap();
+        let values = [2u64, 7, 1, 8].map(Fr::from);
+        let kernel = [0u64, 3, 5, 11].map(Fr::from);
+        let expected: Vec<Fr> = (0..4)
+            .map(|i| {
+                (0..4).fold(Fr::zero(), |acc, j| {
+                    acc + values[j] * kernel[(j + 4 - i) % 4]
+                })
+            })
+            .collect();
+        assert_eq!(field_correlation(&values, &kernel, omega), expected);
+
+        let generator = G1Projective::generator();
+        let points: Vec<G1Projective> = values.iter().map(|v| generator.mul_scalar(v)).collect();
+        let correlated = group_correlation(&points, &kernel, omega);
+        assert_eq!(
+            correlated,
+            expected
+                .iter()
+                .map(|v| msm(&[generator], &[*v]))
+                .collect::<Vec<_>>()
+        );
+    }
+}
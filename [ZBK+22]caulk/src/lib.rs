@@ -0,0 +1,27 @@
+//! Caulk [ZBK+22] and Caulk+ [PK22]: lookup arguments whose prover is
+//! sublinear in the size of the table, on top of KZG.
+//!
+//! A table `c_0, ..., c_(N-1)` is committed to once as `C = [C(τ)]_1`. Given
+//! a commitment to `φ` interpolating `m` values on a subgroup `V_m`, the
+//! prover shows that every `φ_j` is some `c_(i_j)` without revealing the
+//! positions `i_j` ("position-hiding linkability"). After a one-off
+//! precomputation in `O(N log N)`, proving takes time polynomial in `m` and
+//! at most logarithmic in `N`, which `benches/prover.rs` measures.
+//!
+//! - [`table`] commits to the table, precomputes the opening proof of every
+//!   position and extracts the subtable at a set of positions.
+//! - [`caulk`] proves the positions are roots of unity with a unity proof.
+//! - [`caulk_plus`] replaces it with the divisibility `Z_I | X^N - 1`.
+
+use elliptic_curves::{pairing::Pairing, PrimeOrderGroup};
+
+pub mod caulk;
+pub mod caulk_plus;
+mod fft;
+mod link;
+pub mod table;
+
+pub use link::Openings;
+pub use table::{Subtable, Table, TableCommitment};
+
+type Scalar<E> = <<E as Pairing>::G1 as PrimeOrderGroup>::ScalarField;
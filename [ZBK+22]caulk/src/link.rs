@@ -0,0 +1,233 @@
+//! What Caulk and Caulk+ have in common: the blinded polynomials of the
+//! prover and the openings that link them to the subvector.
+//!
+//! The subvector `φ_j = c_(i_j)` lives on `V_m = <ν>` of order `m`. Besides
+//! `Z_I` and `C_I` of the subtable, the prover interpolates
+//! `u(ν^j) = ω^(i_j)`. Once the roots of `Z_I` are known to be positions of
+//! the table, `φ` is a subvector iff on `V_m`
+//!
+//! `Z_I(u(X)) = 0` and `C_I(u(X)) - φ(X) = 0`,
+//!
+//! which both protocols check with a zero-test on `V_m`. Every polynomial
+//! is blinded, so that its commitment and the one or two openings of it
+//! reveal nothing about the positions:
+//!
+//! - `Z_I = r_1 Π_(i ∈ I) (X - ω^i)`,
+//! - `C_I = C_I' + (r_2 + r_3 X + r_4 X^2) Z_I` for the plain interpolation `C_I'`,
+//! - `u` and `φ` by adding multiples of `Z_(V_m)`.
+
+use elliptic_curves::{pairing::Pairing, scalar_mul::msm, Encoding, PrimeOrderGroup};
+use fiat_shamir::Transcript;
+use finite_fields::Field;
+use num_traits::Zero;
+use polynomial::Polynomial;
+use polynomial_commitments::{
+    kzg::{Kzg, KzgCommitterKey, KzgVerifierKey},
+    random_field_element,
+};
+use rand::RngCore;
+use univariate_polynomial_iop_zerotest::domain::Domain;
+
+use crate::{
+    table::{Subtable, Table, TableCommitment},
+    Scalar,
+};
+
+pub(crate) fn constant<F: Field>(value: F) -> Polynomial<F> {
+    Polynomial::new_from_coeffs(&[value])
+}
+
+/// `p(u(X))` by Horner's rule
+pub(crate) fn compose<F: Field>(p: &Polynomial<F>, u: &Polynomial<F>) -> Polynomial<F> {
+    p.get_raw_coefficients()
+        .iter()
+        .rev()
+        .fold(Polynomial::zero(), |acc, coeff| {
+            &(&acc * u) + &constant(*coeff)
+        })
+}
+
+/// `p + (r_0 + r_1 X + ... ) Z_(V_m)` with `degree + 1` random `r_i`
+fn blind<F: Field, R: RngCore>(
+    domain: &Domain<F>,
+    p: Polynomial<F>,
+    degree: usize,
+    rng: &mut R,
+) -> Polynomial<F> {
+    let mask: Vec<F> = (0..=degree).map(|_| random_field_element(rng)).collect();
+    &p + &(&Polynomial::new_from_coeffs(&mask) * &domain.vanishing_polynomial())
+}
+
+/// The prover's polynomials for a lookup at some positions
+pub(crate) struct Witness<E: Pairing> {
+    subtable: Subtable<E>,
+    /// `r_1`
+    scale: Scalar<E>,
+    /// `r_2 + r_3 X + r_4 X^2`
+    mask: Polynomial<Scalar<E>>,
+    /// `Z_I`
+    pub vanishing: Polynomial<Scalar<E>>,
+    /// `C_I`
+    pub interpolation: Polynomial<Scalar<E>>,
+    /// `u`
+    pub positions: Polynomial<Scalar<E>>,
+    /// `φ`
+    pub subvector: Polynomial<Scalar<E>>,
+}
+
+impl<E: Pairing> Witness<E> {
+    pub fn new<R: RngCore>(
+        table: &Table<E>,
+        domain: &Domain<Scalar<E>>,
+        positions: &[usize],
+        rng: &mut R,
+    ) -> Self {
+        assert_eq!(
+            positions.len(),
+            domain.size(),
+            "one position per element of V_m"
+        );
+        let subtable = table.extract(positions);
+        let scale = loop {
+            let scale: Scalar<E> = random_field_element(rng);
+            if !scale.is_zero() {
+                break scale;
+            }
+        };
+        let mask = Polynomial::new_from_coeffs(
+            &(0..3)
+                .map(|_| random_field_element(rng))
+                .collect::<Vec<Scalar<E>>>(),
+        );
+        let vanishing = &subtable.vanishing * &constant(scale);
+        let interpolation = &subtable.interpolation + &(&mask * &vanishing);
+
+        let omega = table.domain().generator();
+        let roots: Vec<Scalar<E>> = positions.iter().map(|i| omega.pow([*i as u64])).collect();
+        let values: Vec<Scalar<E>> = positions.iter().map(|i| table.values()[*i]).collect();
+        Self {
+            positions: blind(domain, domain.interpolate(&roots), 2, rng),
+            subvector: blind(domain, domain.interpolate(&values), 0, rng),
+            subtable,
+            scale,
+            mask,
+            vanishing,
+            interpolation,
+        }
+    }
+
+    /// `[(C(τ) - C_I(τ)) / Z_I(τ)]_2`, which is the unblinded quotient over
+    /// `r_1` minus the mask of `C_I`
+    pub fn quotient(&self, vk: &KzgVerifierKey<E>) -> E::G2 {
+        let inverse = self.scale.inverse().expect("non-zero");
+        self.subtable.quotient.mul_scalar(&inverse)
+            - msm(&vk.powers_of_tau_g2, &self.mask.get_raw_coefficients())
+    }
+
+    /// `[Z_V(τ) / Z_I(τ)]_2`
+    pub fn vanishing_quotient(&self) -> E::G2 {
+        let inverse = self.scale.inverse().expect("non-zero");
+        self.subtable.vanishing_quotient.mul_scalar(&inverse)
+    }
+
+    /// `Z_I(u(X)) + challenge · (C_I(u(X)) - φ(X))`, where both protocols
+    /// pass their `linking` challenge
+    pub fn linking_constraint(&self, challenge: Scalar<E>) -> Polynomial<Scalar<E>> {
+        let lookup = &compose(&self.interpolation, &self.positions) - &self.subvector;
+        &compose(&self.vanishing, &self.positions) + &(&lookup * &constant(challenge))
+    }
+}
+
+/// Starts the transcript of either protocol with the statement: the
+/// table, the order of `V_m` and the commitment to the subvector
+pub(crate) fn transcript<E>(
+    label: &[u8],
+    table: &TableCommitment<E>,
+    domain: &Domain<Scalar<E>>,
+    commitment: &E::G1,
+) -> Transcript
+where
+    E: Pairing,
+    E::G1: Encoding,
+{
+    let mut transcript = Transcript::new(label);
+    transcript.append_statement(b"table", &table.commitment.to_bytes());
+    transcript.append_statement(b"table size", &(table.size as u64).to_le_bytes());
+    transcript.append_statement(b"subvector size", &(domain.size() as u64).to_le_bytes());
+    transcript.append_statement(b"subvector", &commitment.to_bytes());
+    transcript
+}
+
+/// Values of several committed polynomials at one point, with a single
+/// batched KZG proof
+#[derive(Debug, Clone)]
+pub struct Openings<E: Pairing> {
+    pub values: Vec<Scalar<E>>,
+    pub proof: E::G1,
+}
+
+/// Opens `polynomials` at `point` after absorbing their values, which fix
+/// the batching challenge
+pub(crate) fn open<E: Pairing>(
+    ck: &KzgCommitterKey<E>,
+    transcript: &mut Transcript,
+    polynomials: &[Polynomial<Scalar<E>>],
+    point: Scalar<E>,
+) -> Openings<E> {
+    let values: Vec<Scalar<E>> = polynomials.iter().map(|p| p.eval(point)).collect();
+    for value in &values {
+        transcript.append_field(b"value", value);
+    }
+    let challenge = transcript.challenge(b"batch");
+    let (values, proof) = Kzg::open_batch(ck, polynomials, point, challenge);
+    Openings { values, proof }
+}
+
+pub(crate) fn verify_openings<E: Pairing>(
+    vk: &KzgVerifierKey<E>,
+    transcript: &mut Transcript,
+    commitments: &[E::G1],
+    point: Scalar<E>,
+    openings: &Openings<E>,
+) -> bool {
+    for value in &openings.values {
+        transcript.append_field(b"value", value);
+    }
+    let challenge = transcript.challenge(b"batch");
+    Kzg::verify_batch(
+        vk,
+        commitments,
+        point,
+        &openings.values,
+        &openings.proof,
+        challenge,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::Fr;
+    use ark_ff::FftField;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+
+    #[test]
+    fn composition() {
+        // (X^2 + 1) ∘ (2X + 3) = 4X^2 + 12X + 10
+        let p = Polynomial::new_from_coeffs(&[1u64, 0, 1].map(Fr::from));
+        let u = Polynomial::new_from_coeffs(&[3u64, 2].map(Fr::from));
+        assert_eq!(
+            compose(&p, &u).get_raw_coefficients(),
+            [10u64, 12, 4].map(Fr::from)
+        );
+
+        let domain = Domain::new(Fr::get_root_of_unity(4).unwrap(), 4);
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let blinded = blind(&domain, p.clone(), 2, &mut rng);
+        for element in domain.elements() {
+            assert_eq!(blinded.eval(element), p.eval(element));
+        }
+    }
+}
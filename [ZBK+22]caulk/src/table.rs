@@ -0,0 +1,296 @@
+//! The table `c_0, ..., c_(N-1)` on `V = <ω>` of order `N`, committed to as
+//! `C = [C(τ)]_1` for the polynomial `C` interpolating it, and what either
+//! prover precomputes once per table.
+//!
+//! For a subset `I` of positions with `Z_I(X) = Π_(i ∈ I) (X - ω^i)`, both
+//! protocols need `[(C - C_I) / Z_I]_2` and Caulk+ also `[Z_V / Z_I]_2`,
+//! where `C_I` interpolates the table on `I`. Partial fractions turn them
+//! into sums over `I` of the single-position quotients,
+//!
+//! `(C - C_I) / Z_I = Σ_(i ∈ I) (C - c_i) / (X - ω^i) / Z_I'(ω^i)`,
+//!
+//! and likewise with `Z_V` in place of `C - C_I`. Precomputing the KZG
+//! opening proofs `[(C - c_i) / (X - ω^i)]_2` and `[Z_V / (X - ω^i)]_2` of
+//! every position thus leaves the prover `O(|I|^2)` work, independent of
+//! `N`. Computing each of them separately takes `O(N^2)` group operations;
+//! written in the Lagrange basis `L_i` of `V`,
+//!
+//! `(C - c_i) / (X - ω^i) = ω^-i (s_i L_i - Σ_(j ≠ i) c_j L_j / (1 - ω^(j-i)) + c_i D_i / N)`
+//!
+//! with `s_i = Σ_(j ≠ i) c_j ω^(j-i) / (1 - ω^(j-i))` and
+//! `D_i = Σ_k (N - 1 - k) ω^(-ik) X^k`, where the sums over `j` are cyclic
+//! correlations and the `D_i` one FFT, so all of them take `O(N log N)`.
+
+use elliptic_curves::{pairing::Pairing, scalar_mul::msm, PrimeOrderGroup};
+use finite_fields::Field;
+use num_traits::{One, Zero};
+use polynomial::Polynomial;
+use polynomial_commitments::kzg::{KzgCommitterKey, KzgVerifierKey};
+use univariate_polynomial_iop_zerotest::domain::Domain;
+
+use crate::{
+    fft::{field_correlation, group_correlation, group_fft, group_ifft},
+    Scalar,
+};
+
+/// What the verifier knows about the table
+#[derive(Debug, Clone)]
+pub struct TableCommitment<E: Pairing> {
+    /// `[C(τ)]_1`
+    pub commitment: E::G1,
+    /// `[Z_V(τ)]_1 = [τ^N - 1]_1`
+    pub vanishing: E::G1,
+    pub size: usize,
+}
+
+/// The table along with the opening proofs of every position
+#[derive(Debug, Clone)]
+pub struct Table<E: Pairing> {
+    domain: Domain<Scalar<E>>,
+    values: Vec<Scalar<E>>,
+    commitment: TableCommitment<E>,
+    /// `[(C(τ) - c_i) / (τ - ω^i)]_2`
+    openings: Vec<E::G2>,
+    /// `[Z_V(τ) / (τ - ω^i)]_2`
+    vanishing_openings: Vec<E::G2>,
+}
+
+impl<E: Pairing> Table<E> {
+    /// Commits to `values` on `domain` and precomputes the opening proofs
+    /// of every position in `O(N log N)`. The setup has to support degree
+    /// `N`.
+    pub fn new(
+        ck: &KzgCommitterKey<E>,
+        vk: &KzgVerifierKey<E>,
+        domain: Domain<Scalar<E>>,
+        values: Vec<Scalar<E>>,
+    ) -> Self {
+        let n = domain.size();
+        assert_eq!(values.len(), n, "one value per domain element");
+        assert!(
+            ck.powers_of_tau.len() > n && vk.powers_of_tau_g2.len() > n,
+            "setup does not support degree {n}"
+        );
+        let omega = domain.generator();
+        let omega_inverse = omega.inverse().expect("non-zero");
+        let n_field = Scalar::<E>::from_u64(n as u64);
+        let n_inverse = n_field.inverse().expect("domain size is invertible");
+
+        let lagrange_g1 = group_ifft(&ck.powers_of_tau[..n], omega);
+        let commitment = TableCommitment {
+            commitment: msm(&lagrange_g1, &values),
+            vanishing: ck.powers_of_tau[n] - ck.powers_of_tau[0],
+            size: n,
+        };
+
+        // `1 / (1 - ω^k)` and `ω^k / (1 - ω^k)`, with the terms `j = i`
+        // left out by zeros at `k = 0`
+        let powers = domain.elements();
+        let kernel: Vec<Scalar<E>> = powers
+            .iter()
+            .map(|power| {
+                (Scalar::<E>::one() - *power)
+                    .inverse()
+                    .unwrap_or_else(Scalar::<E>::zero)
+            })
+            .collect();
+        let shifted_kernel: Vec<Scalar<E>> = kernel
+            .iter()
+            .zip(&powers)
+            .map(|(k, power)| *k * *power)
+            .collect();
+
+        let lagrange_g2 = group_ifft(&vk.powers_of_tau_g2[..n], omega);
+        let sums = field_correlation(&values, &shifted_kernel, omega);
+        let scaled: Vec<E::G2> = lagrange_g2
+            .iter()
+            .zip(&values)
+            .map(|(lagrange, value)| lagrange.mul_scalar(value))
+            .collect();
+        let correlated = group_correlation(&scaled, &kernel, omega);
+        let weighted: Vec<E::G2> = vk.powers_of_tau_g2[..n]
+            .iter()
+            .enumerate()
+            .map(|(k, power)| power.mul_scalar(&Scalar::<E>::from_u64((n - 1 - k) as u64)))
+            .collect();
+        let diagonal = group_fft(&weighted, omega_inverse);
+
+        let mut openings = Vec::with_capacity(n);
+        let mut vanishing_openings = Vec::with_capacity(n);
+        let mut power_inverse = Scalar::<E>::one();
+        for i in 0..n {
+            let opening = lagrange_g2[i].mul_scalar(&sums[i]) - correlated[i]
+                + diagonal[i].mul_scalar(&(values[i] * n_inverse));
+            openings.push(opening.mul_scalar(&power_inverse));
+            vanishing_openings.push(lagrange_g2[i].mul_scalar(&(n_field * power_inverse)));
+            power_inverse = power_inverse * omega_inverse;
+        }
+
+        Self {
+            domain,
+            values,
+            commitment,
+            openings,
+            vanishing_openings,
+        }
+    }
+
+    pub fn domain(&self) -> &Domain<Scalar<E>> {
+        &self.domain
+    }
+
+    pub fn values(&self) -> &[Scalar<E>] {
+        &self.values
+    }
+
+    pub fn commitment(&self) -> &TableCommitment<E> {
+        &self.commitment
+    }
+
+    /// A position of every value of `subvector` in the table, `None` if
+    /// some value is missing
+    pub fn positions(&self, subvector: &[Scalar<E>]) -> Option<Vec<usize>> {
+        subvector
+            .iter()
+            .map(|value| self.values.iter().position(|entry| entry == value))
+            .collect()
+    }
+
+    /// Extracts the subtable at `positions` from the precomputed openings
+    /// with `O(|I|^2)` field and `O(|I|)` group operations
+    pub fn extract(&self, positions: &[usize]) -> Subtable<E> {
+        let mut distinct = positions.to_vec();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert!(
+            distinct
+                .last()
+                .is_some_and(|last| *last < self.values.len()),
+            "positions must be non-empty and inside the table"
+        );
+
+        let omega = self.domain.generator();
+        let roots: Vec<Scalar<E>> = distinct.iter().map(|i| omega.pow([*i as u64])).collect();
+        let vanishing = roots.iter().fold(
+            Polynomial::new_from_coeffs(&[Scalar::<E>::one()]),
+            |acc, root| &acc * &Polynomial::new_from_coeffs(&[-*root, Scalar::<E>::one()]),
+        );
+        let interpolation = Polynomial::new_from_evals_lagrange(
+            &roots
+                .iter()
+                .zip(&distinct)
+                .map(|(root, i)| (*root, self.values[*i]))
+                .collect::<Vec<_>>(),
+        );
+
+        // `1 / Z_I'(ω^i) = 1 / Π_(l ≠ i) (ω^i - ω^l)`
+        let weights: Vec<Scalar<E>> = roots
+            .iter()
+            .map(|root| {
+                roots
+                    .iter()
+                    .filter(|other| *other != root)
+                    .fold(Scalar::<E>::one(), |acc, other| acc * (*root - *other))
+                    .inverse()
+                    .expect("distinct roots")
+            })
+            .collect();
+        let pick =
+            |openings: &[E::G2]| -> Vec<E::G2> { distinct.iter().map(|i| openings[*i]).collect() };
+
+        Subtable {
+            quotient: msm(&pick(&self.openings), &weights),
+            vanishing_quotient: msm(&pick(&self.vanishing_openings), &weights),
+            positions: distinct,
+            vanishing,
+            interpolation,
+        }
+    }
+}
+
+/// The table restricted to a set `I` of positions
+#[derive(Debug, Clone)]
+pub struct Subtable<E: Pairing> {
+    /// The distinct positions in increasing order
+    pub positions: Vec<usize>,
+    /// `Z_I(X) = Π_(i ∈ I) (X - ω^i)`
+    pub vanishing: Polynomial<Scalar<E>>,
+    /// `C_I`, interpolating the table on `I`
+    pub interpolation: Polynomial<Scalar<E>>,
+    /// `[(C(τ) - C_I(τ)) / Z_I(τ)]_2`
+    pub quotient: E::G2,
+    /// `[Z_V(τ) / Z_I(τ)]_2`
+    pub vanishing_quotient: E::G2,
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_ff::FftField;
+    use polynomial_commitments::{kzg::Kzg, PolynomialCommitmentScheme};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+
+    type Bls = Kzg<Bls12_381>;
+
+    fn table(ck: &KzgCommitterKey<Bls12_381>, vk: &KzgVerifierKey<Bls12_381>) -> Table<Bls12_381> {
+        let domain = Domain::new(Fr::get_root_of_unity(8).unwrap(), 8);
+        let values = [7u64, 3, 3, 10, 0, 42, 5, 1].map(Fr::from).to_vec();
+        Table::new(ck, vk, domain, values)
+    }
+
+    /// `[p(τ)]_2` straight from the powers of `τ`
+    fn commit_g2(vk: &KzgVerifierKey<Bls12_381>, p: &Polynomial<Fr>) -> <Bls12_381 as Pairing>::G2 {
+        msm(&vk.powers_of_tau_g2, &p.get_raw_coefficients())
+    }
+
+    #[test]
+    fn precomputed_openings_match_direct_ones() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (ck, vk) = Bls::setup(8, &mut rng);
+        let table = table(&ck, &vk);
+        let c = table.domain().interpolate(table.values());
+        assert_eq!(table.commitment().commitment, Bls::commit(&ck, &c));
+
+        let vanishing = table.domain().vanishing_polynomial();
+        for (i, root) in table.domain().elements().into_iter().enumerate() {
+            let linear = Polynomial::new_from_coeffs(&[-root, Fr::from(1u64)]);
+            let shifted = &c - &Polynomial::new_from_coeffs(&[table.values()[i]]);
+            assert_eq!(
+                table.openings[i],
+                commit_g2(&vk, &shifted.div_rem(&linear).0)
+            );
+            assert_eq!(
+                table.vanishing_openings[i],
+                commit_g2(&vk, &vanishing.div_rem(&linear).0)
+            );
+        }
+    }
+
+    #[test]
+    fn extracted_quotients_match_direct_ones() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let (ck, vk) = Bls::setup(8, &mut rng);
+        let table = table(&ck, &vk);
+        let c = table.domain().interpolate(table.values());
+
+        let positions = table.positions(&[42, 3, 1, 42].map(Fr::from)).unwrap();
+        assert_eq!(positions, [5, 1, 7, 5]);
+        let subtable = table.extract(&positions);
+        assert_eq!(subtable.positions, [1, 5, 7]);
+
+        let (quotient, remainder) = (&c - &subtable.interpolation).div_rem(&subtable.vanishing);
+        assert!(remainder.is_zero());
+        assert_eq!(subtable.quotient, commit_g2(&vk, &quotient));
+        let (quotient, remainder) = table
+            .domain()
+            .vanishing_polynomial()
+            .div_rem(&subtable.vanishing);
+        assert!(remainder.is_zero());
+        assert_eq!(subtable.vanishing_quotient, commit_g2(&vk, &quotient));
+
+        assert_eq!(table.positions(&[4].map(Fr::from)), None);
+    }
+}
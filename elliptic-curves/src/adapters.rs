@@ -0,0 +1,211 @@
+//! `Group` and `Pairing` implementations for arkworks and pasta curves, so
+//! that protocols written against this crate run unchanged over production
+//! curves like BLS12-381 or Pallas.
+
+use ark_ec::{
+    bls12::{Bls12, Bls12Config},
+    pairing::Pairing as ArkPairing,
+    short_weierstrass::{Projective, SWCurveConfig},
+    CurveConfig, Group as ArkGroup,
+};
+use ark_ff::{PrimeField as ArkPrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use finite_fields::{Field, Halo2Field};
+use halo2_proofs::pasta::{
+    group::{ff::PrimeField as Halo2PrimeField, Group as Halo2Group, GroupEncoding},
+    pallas, vesta,
+};
+
+use crate::{pairing::Pairing, Encoding, Group, PrimeOrderGroup};
+
+impl<P: SWCurveConfig> Group for Projective<P> {
+    fn identity() -> Self {
+        <Self as Zero>::zero()
+    }
+
+    fn generator() -> Self {
+        <Self as ArkGroup>::generator()
+    }
+
+    fn double(&self) -> Self {
+        ArkGroup::double(self)
+    }
+
+    fn mul_bigint<S: AsRef<[u64]>>(&self, scalar: S) -> Self {
+        ArkGroup::mul_bigint(self, scalar)
+    }
+}
+
+impl<P: SWCurveConfig> PrimeOrderGroup for Projective<P>
+where
+    P::ScalarField: Field,
+{
+    type ScalarField = P::ScalarField;
+
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64> {
+        scalar.into_bigint().as_ref().to_vec()
+    }
+}
+
+/// The compressed encoding of `ark-serialize`. Decoding checks that the
+/// point lies in the prime order subgroup.
+impl<P: SWCurveConfig> Encoding for Projective<P> {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        self.serialize_compressed(&mut bytes)
+            .expect("serialising into a vector cannot fail");
+        bytes
+    }
+
+    fn from_bytes(mut bytes: &[u8]) -> Option<Self> {
+        let point = Self::deserialize_compressed(&mut bytes).ok()?;
+        bytes.is_empty().then_some(point)
+    }
+}
+
+/// The optimal Ate pairing of arkworks on BLS12 curves
+impl<P: Bls12Config> Pairing for Bls12<P>
+where
+    <P::G1Config as CurveConfig>::ScalarField: Field,
+{
+    type G1 = Projective<P::G1Config>;
+    type G2 = Projective<P::G2Config>;
+    type Target = <Self as ArkPairing>::TargetField;
+
+    fn pairing(p: &Self::G1, q: &Self::G2) -> Self::Target {
+        <Self as ArkPairing>::pairing(*p, *q).0
+    }
+}
+
+/// Implements `Group` and `PrimeOrderGroup` for a pasta curve with the
+/// given scalar field
+macro_rules! pasta_group {
+    ($curve:ty, $scalar:ty) => {
+        impl Group for $curve {
+            fn identity() -> Self {
+                <Self as Halo2Group>::identity()
+            }
+
+            fn generator() -> Self {
+                <Self as Halo2Group>::generator()
+            }
+
+            fn double(&self) -> Self {
+                Halo2Group::double(self)
+            }
+        }
+
+        impl PrimeOrderGroup for $curve {
+            type ScalarField = Halo2Field<$scalar>;
+
+            /// From the little-endian byte representation of `ff`
+            fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64> {
+                scalar
+                    .0
+                    .to_repr()
+                    .chunks(8)
+                    .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+                    .collect()
+            }
+
+            fn mul_scalar(&self, scalar: &Self::ScalarField) -> Self {
+                *self * scalar.0
+            }
+        }
+
+        /// The compressed encoding of `group`
+        impl Encoding for $curve {
+            fn to_bytes(&self) -> Vec<u8> {
+                GroupEncoding::to_bytes(self).as_ref().to_vec()
+            }
+
+            fn from_bytes(bytes: &[u8]) -> Option<Self> {
+                let mut repr = <Self as GroupEncoding>::Repr::default();
+                if bytes.len() != repr.as_ref().len() {
+                    return None;
+                }
+                repr.as_mut().copy_from_slice(bytes);
+                Option::from(<Self as GroupEncoding>::from_bytes(&repr))
+            }
+        }
+    };
+}
+
+pasta_group!(pallas::Point, pallas::Scalar);
+pasta_group!(vesta::Point, vesta::Scalar);
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr, G1Projective, G2Projective};
+    use ark_ff::Field as ArkField;
+    use ark_ff::UniformRand;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::scalar_mul;
+
+    #[test]
+    fn wnaf_matches_arkworks() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let point = <G1Projective as Group>::generator();
+        for _ in 0..4 {
+            let scalar = Fr::rand(&mut rng);
+            let limbs = G1Projective::scalar_to_limbs(&scalar);
+            assert_eq!(scalar_mul::wnaf_mul(&point, &limbs, 5), point * scalar);
+            assert_eq!(point.mul_scalar(&scalar), point * scalar);
+        }
+    }
+
+    #[test]
+    fn optimal_ate_through_pairing_trait() {
+        let (p, q) = (
+            <G1Projective as Group>::generator(),
+            <G2Projective as Group>::generator(),
+        );
+        let e = <Bls12_381 as Pairing>::pairing(&p, &q);
+        assert_ne!(e, ArkField::ONE);
+
+        let (a, b) = (Fr::from(6u64), Fr::from(7u64));
+        let lhs = <Bls12_381 as Pairing>::pairing(&p.mul_scalar(&a), &q.mul_scalar(&b));
+        assert_eq!(lhs, ArkField::pow(&e, [42]));
+        assert_eq!(
+            lhs,
+            <Bls12_381 as Pairing>::pairing(&p, &q.mul_scalar(&(a * b)))
+        );
+    }
+
+    #[test]
+    fn pasta_scalar_multiplication() {
+        let point = <pallas::Point as Group>::generator();
+        let scalar = Halo2Field(pallas::Scalar::from(0xdead_beef))
+            * Halo2Field(pallas::Scalar::from(0x1234_5678));
+        let limbs = pallas::Point::scalar_to_limbs(&scalar);
+        assert_eq!(
+            scalar_mul::wnaf_mul(&point, &limbs, 4),
+            point.mul_scalar(&scalar)
+        );
+
+        let point = <vesta::Point as Group>::generator();
+        assert_eq!(point.mul_bigint([3]), point * vesta::Scalar::from(3));
+        assert_eq!(
+            <vesta::Point as Encoding>::from_bytes(&Encoding::to_bytes(&point)),
+            Some(point)
+        );
+    }
+
+    #[test]
+    fn bls12_381_encoding() {
+        let point = Group::mul_bigint(&<G1Projective as Group>::generator(), [12345]);
+        let bytes = Encoding::to_bytes(&point);
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(<G1Projective as Encoding>::from_bytes(&bytes), Some(point));
+        assert_eq!(<G1Projective as Encoding>::from_bytes(&bytes[1..]), None);
+
+        let point = <G2Projective as Group>::generator();
+        assert_eq!(
+            <G2Projective as Encoding>::from_bytes(&Encoding::to_bytes(&point)),
+            Some(point)
+        );
+    }
+}
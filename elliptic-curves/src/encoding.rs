@@ -0,0 +1,232 @@
+//! Canonical byte encodings of group elements, used to hash them into
+//! Fiat–Shamir transcripts and to serialise proofs.
+
+use finite_fields::Field;
+
+use crate::{
+    short_weierstrass::{Affine, Jacobian, Projective, WeierstrassConfig},
+    twisted_edwards::{EdwardsAffine, EdwardsConfig, EdwardsProjective},
+    Group,
+};
+
+/// A canonical, injective byte encoding
+pub trait Encoding: Sized {
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Inverse of `to_bytes`, `None` unless `bytes` encodes a valid element
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+/// The limbs of `to_limbs` as little-endian bytes
+pub fn field_to_bytes<F: Field>(element: &F) -> Vec<u8> {
+    element
+        .to_limbs()
+        .iter()
+        .flat_map(|limb| limb.to_le_bytes())
+        .collect()
+}
+
+/// Inverse of `field_to_bytes`, `None` for non-canonical encodings
+pub fn field_from_bytes<F: Field>(bytes: &[u8]) -> Option<F> {
+    if bytes.len() % 8 != 0 {
+        return None;
+    }
+    let limbs: Vec<u64> = bytes
+        .chunks(8)
+        .map(|chunk| u64::from_le_bytes(chunk.try_into().expect("chunks of 8 bytes")))
+        .collect();
+    F::from_limbs(&limbs)
+}
+
+/// Appends `part` prefixed with its length as a little-endian `u32`, the
+/// framing of proofs made of several encoded elements
+pub fn write_prefixed(bytes: &mut Vec<u8>, part: &[u8]) {
+    let len = u32::try_from(part.len()).expect("proof components are short");
+    bytes.extend(len.to_le_bytes());
+    bytes.extend(part);
+}
+
+/// Reads back the parts written with [`write_prefixed`], in order
+#[derive(Debug, Clone)]
+pub struct PrefixedReader<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> PrefixedReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { rest: bytes }
+    }
+
+    /// A little-endian `u32` without a prefix, such as a count of parts
+    pub fn read_u32(&mut self) -> Option<u32> {
+        let (value, rest) = self.rest.split_first_chunk::<4>()?;
+        self.rest = rest;
+        Some(u32::from_le_bytes(*value))
+    }
+
+    /// The next part, `None` if the input ends before it does
+    pub fn next_part(&mut self) -> Option<&'a [u8]> {
+        let len = self.read_u32()? as usize;
+        let part = self.rest.get(..len)?;
+        self.rest = &self.rest[len..];
+        Some(part)
+    }
+
+    /// Whether every byte has been read
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+}
+
+/// Length in bytes of the encoding of any element of `F`
+fn field_width<F: Field>() -> usize {
+    8 * F::zero().to_limbs().len()
+}
+
+/// `x` and `y` encoded back to back
+fn coordinates_to_bytes<F: Field>(x: &F, y: &F) -> Vec<u8> {
+    let mut bytes = field_to_bytes(x);
+    bytes.extend(field_to_bytes(y));
+    bytes
+}
+
+fn coordinates_from_bytes<F: Field>(bytes: &[u8]) -> Option<(F, F)> {
+    let width = field_width::<F>();
+    if bytes.len() != 2 * width {
+        return None;
+    }
+    Some((
+        field_from_bytes(&bytes[..width])?,
+        field_from_bytes(&bytes[width..])?,
+    ))
+}
+
+/// A flag byte, `0` for the point at infinity and `1` otherwise, followed
+/// by the affine coordinates of finite points. Decoding checks the curve
+/// equation but not membership in the prime order subgroup.
+impl<C: WeierstrassConfig> Encoding for Affine<C> {
+    fn to_bytes(&self) -> Vec<u8> {
+        if self.infinity {
+            return vec![0];
+        }
+        let mut bytes = vec![1];
+        bytes.extend(coordinates_to_bytes(&self.x, &self.y));
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes.split_first()? {
+            (0, []) => Some(Self::identity()),
+            (1, coordinates) => {
+                let (x, y) = coordinates_from_bytes(coordinates)?;
+                Self::new(x, y)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Same as the affine point it stands for
+impl<C: WeierstrassConfig> Encoding for Projective<C> {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_affine().to_bytes()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Affine::from_bytes(bytes).map(|point| point.to_projective())
+    }
+}
+
+/// Same as the affine point it stands for
+impl<C: WeierstrassConfig> Encoding for Jacobian<C> {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_affine().to_bytes()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Affine::from_bytes(bytes).map(|point| point.to_jacobian())
+    }
+}
+
+/// The affine coordinates, as Edwards curves have no point at infinity
+impl<C: EdwardsConfig> Encoding for EdwardsAffine<C> {
+    fn to_bytes(&self) -> Vec<u8> {
+        coordinates_to_bytes(&self.x, &self.y)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let (x, y) = coordinates_from_bytes(bytes)?;
+        Self::new(x, y)
+    }
+}
+
+/// Same as the affine point it stands for
+impl<C: EdwardsConfig> Encoding for EdwardsProjective<C> {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_affine().to_bytes()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        EdwardsAffine::from_bytes(bytes).map(|point| point.to_projective())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+    use crate::toy::{Toy10007, Toy67Quadratic, ToyEdwards101};
+
+    fn roundtrip<G: Group + Encoding>(point: G) {
+        assert_eq!(G::from_bytes(&point.to_bytes()), Some(point));
+    }
+
+    #[test]
+    fn points_roundtrip() {
+        let generator = Projective::<Toy10007>::generator();
+        for k in [0u64, 1, 2, 5000, 10038] {
+            let point = generator.mul_bigint([k]);
+            roundtrip(point);
+            roundtrip(point.to_affine());
+            roundtrip(point.to_affine().to_jacobian());
+            roundtrip(EdwardsProjective::<ToyEdwards101>::generator().mul_bigint([k]));
+        }
+        roundtrip(Affine::<Toy67Quadratic>::generator());
+        assert_eq!(Affine::<Toy10007>::identity().to_bytes(), vec![0]);
+        assert_eq!(Affine::<Toy10007>::generator().to_bytes().len(), 17);
+    }
+
+    #[test]
+    fn invalid_encodings_are_rejected() {
+        let mut bytes = Affine::<Toy10007>::generator().to_bytes();
+        bytes[9] ^= 1;
+        assert_eq!(Affine::<Toy10007>::from_bytes(&bytes), None);
+        assert_eq!(Affine::<Toy10007>::from_bytes(&[0, 0]), None);
+        assert_eq!(Affine::<Toy10007>::from_bytes(&[2]), None);
+        assert_eq!(Affine::<Toy10007>::from_bytes(&[]), None);
+
+        // x = 10007 is not a canonical field element
+        let mut bytes = vec![1];
+        bytes.extend(10007u64.to_le_bytes());
+        bytes.extend(field_to_bytes(&PrimeField::<10007>::new(0)));
+        assert_eq!(Affine::<Toy10007>::from_bytes(&bytes), None);
+    }
+
+    #[test]
+    fn prefixed_parts_roundtrip() {
+        let mut bytes = 2u32.to_le_bytes().to_vec();
+        write_prefixed(&mut bytes, &[7, 8, 9]);
+        write_prefixed(&mut bytes, &[]);
+        let mut reader = PrefixedReader::new(&bytes);
+        assert_eq!(reader.read_u32(), Some(2));
+        assert_eq!(reader.next_part(), Some(&[7, 8, 9][..]));
+        assert_eq!(reader.next_part(), Some(&[][..]));
+        assert!(reader.is_empty());
+        assert_eq!(reader.next_part(), None);
+
+        // A prefix longer than what is left
+        let mut reader = PrefixedReader::new(&bytes[4..7]);
+        assert_eq!(reader.next_part(), None);
+    }
+}
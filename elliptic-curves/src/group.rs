@@ -0,0 +1,48 @@
+use std::{
+    fmt::Debug,
+    ops::{Add, Neg, Sub},
+};
+
+use finite_fields::Field;
+
+use crate::scalar_mul;
+
+/// A finite abelian group, written additively
+pub trait Group:
+    Copy + Debug + Eq + Add<Output = Self> + Sub<Output = Self> + Neg<Output = Self>
+{
+    /// The neutral element
+    fn identity() -> Self;
+
+    /// A fixed generator of the (sub)group used by protocols
+    fn generator() -> Self;
+
+    fn is_identity(&self) -> bool {
+        *self == Self::identity()
+    }
+
+    /// Returns `self + self`
+    fn double(&self) -> Self {
+        *self + *self
+    }
+
+    /// Computes `[k]P` for a scalar `k` given as little-endian `u64` limbs.
+    /// Defaults to wNAF scalar multiplication with a window of 4.
+    fn mul_bigint<S: AsRef<[u64]>>(&self, scalar: S) -> Self {
+        scalar_mul::wnaf_mul(self, scalar.as_ref(), 4)
+    }
+}
+
+/// A group whose `generator` has prime order `r`, so that scalars can be
+/// treated as elements of the field `F_r`
+pub trait PrimeOrderGroup: Group {
+    type ScalarField: Field + From<u64>;
+
+    /// Canonical representative of a scalar as little-endian `u64` limbs
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64>;
+
+    /// Computes `[k]P` for `k` in the scalar field
+    fn mul_scalar(&self, scalar: &Self::ScalarField) -> Self {
+        self.mul_bigint(Self::scalar_to_limbs(scalar))
+    }
+}
@@ -0,0 +1,12 @@
+mod adapters;
+pub mod encoding;
+mod group;
+pub mod pairing;
+pub mod scalar_mul;
+pub mod schoof;
+pub mod short_weierstrass;
+pub mod toy;
+pub mod twisted_edwards;
+
+pub use encoding::Encoding;
+pub use group::{Group, PrimeOrderGroup};
@@ -0,0 +1,181 @@
+//! Pairings computed with Miller's algorithm.
+//!
+//! For a point `P` of order `r`, the Miller function `f_{r,P}` has divisor
+//! `r(P) - r(O)`. It is built up from `f_{i+j,P} = f_{i,P} f_{j,P} l / v`
+//! where `l` is the line through `[i]P` and `[j]P` and `v` the vertical
+//! line through `[i+j]P`, using the binary expansion of `r`. Then
+//!
+//! - the reduced Tate pairing is `f_{r,P}(Q)^((q^k - 1) / r)`, with `q^k`
+//!   the size of the field holding the coordinates of `Q`, and
+//! - the Weil pairing is `(-1)^r f_{r,P}(Q) / f_{r,Q}(P)`.
+
+use std::{fmt::Debug, ops::Mul};
+
+use finite_fields::Field;
+use num_traits::One;
+
+use crate::{
+    short_weierstrass::{Affine, WeierstrassConfig},
+    Group, PrimeOrderGroup,
+};
+
+/// A bilinear, non-degenerate map `e: G1 x G2 -> GT` between groups of the
+/// same prime order, with `GT` written multiplicatively
+pub trait Pairing {
+    type G1: PrimeOrderGroup;
+    type G2: PrimeOrderGroup<ScalarField = <Self::G1 as PrimeOrderGroup>::ScalarField>;
+    type Target: Copy + Debug + Eq + One + Mul<Output = Self::Target>;
+
+    fn pairing(p: &Self::G1, q: &Self::G2) -> Self::Target;
+}
+
+/// Evaluates at `q` the line through `t` and `s` (the tangent if they are
+/// equal) divided by the vertical line through `t + s`, and returns that
+/// value alongside `t + s`
+fn line_over_vertical<C: WeierstrassConfig>(
+    t: &Affine<C>,
+    s: &Affine<C>,
+    q: &Affine<C>,
+) -> (C::BaseField, Affine<C>) {
+    if t.infinity || s.infinity {
+        return (C::BaseField::one(), *t + *s);
+    }
+    if t.x == s.x && t.y == -s.y {
+        // The line is vertical itself and `t + s` is the point at infinity
+        return (q.x - t.x, Affine::identity());
+    }
+    let sum = *t + *s;
+    let slope = if t == s {
+        (C::BaseField::from_u64(3) * t.x.square() + C::A) / (t.y + t.y)
+    } else {
+        (s.y - t.y) / (s.x - t.x)
+    };
+    let vertical = (q.x - sum.x)
+        .inverse()
+        .expect("evaluation point lies on a vertical line of the Miller loop");
+    ((q.y - t.y - slope * (q.x - t.x)) * vertical, sum)
+}
+
+/// Evaluates the Miller function `f_{r,P}` at `q` by double-and-add over
+/// the bits of `r`, given as little-endian `u64` limbs. `q` must not lie on
+/// any of the lines used, which holds whenever it is outside the group
+/// generated by `p`.
+pub fn miller_loop<C: WeierstrassConfig>(p: &Affine<C>, q: &Affine<C>, r: &[u64]) -> C::BaseField {
+    let bits: Vec<bool> = r
+        .iter()
+        .flat_map(|limb| (0..64).map(move |bit| (limb >> bit) & 1 == 1))
+        .collect();
+    let top = bits.iter().rposition(|bit| *bit).expect("r is non-zero");
+
+    let mut f = C::BaseField::one();
+    let mut t = *p;
+    for bit in bits[..top].iter().rev() {
+        let (line, doubled) = line_over_vertical(&t, &t, q);
+        f = f.square() * line;
+        t = doubled;
+        if *bit {
+            let (line, sum) = line_over_vertical(&t, p, q);
+            f = f * line;
+            t = sum;
+        }
+    }
+    f
+}
+
+/// Reduced Tate pairing `f_{r,P}(Q)^final_exponent` where the final
+/// exponent is `(q^k - 1) / r` for coordinates in `F_{q^k}`
+pub fn tate_pairing<C: WeierstrassConfig>(
+    p: &Affine<C>,
+    q: &Affine<C>,
+    r: &[u64],
+    final_exponent: &[u64],
+) -> C::BaseField {
+    if p.infinity || q.infinity {
+        return C::BaseField::one();
+    }
+    miller_loop(p, q, r).pow(final_exponent)
+}
+
+/// Weil pairing `(-1)^r f_{r,P}(Q) / f_{r,Q}(P)` of two points of order
+/// `r` generating distinct subgroups
+pub fn weil_pairing<C: WeierstrassConfig>(p: &Affine<C>, q: &Affine<C>, r: &[u64]) -> C::BaseField {
+    if p.infinity || q.infinity {
+        return C::BaseField::one();
+    }
+    let ratio = miller_loop(p, q, r)
+        * miller_loop(q, p, r)
+            .inverse()
+            .expect("points generate distinct subgroups");
+    if r[0] & 1 == 1 {
+        -ratio
+    } else {
+        ratio
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::{ExtensionField, PrimeField};
+
+    use super::*;
+    use crate::toy::{distortion_map, Toy67, Toy67Quadratic, ToyTatePairing, ToyWeilPairing};
+
+    type F67Quadratic = ExtensionField<PrimeField<67>, 2>;
+
+    fn check_pairing<E: Pairing>(p: E::G1, q: E::G2) {
+        let one = E::Target::one();
+        let e = E::pairing(&p, &q);
+        assert_ne!(e, one, "pairing is degenerate");
+
+        for (a, b) in [(2u64, 3u64), (5, 11), (16, 16)] {
+            let lhs = E::pairing(&p.mul_bigint([a]), &q.mul_bigint([b]));
+            assert_eq!(lhs, E::pairing(&p.mul_bigint([a * b]), &q));
+            assert_eq!(lhs, E::pairing(&p, &q.mul_bigint([a * b])));
+        }
+        assert_eq!(
+            E::pairing(&(p + p.double()), &q),
+            E::pairing(&p, &q) * E::pairing(&p.double(), &q)
+        );
+        assert_eq!(E::pairing(&E::G1::identity(), &q), one);
+    }
+
+    #[test]
+    fn toy_pairings_are_bilinear() {
+        let generator = Affine::<Toy67>::generator();
+        check_pairing::<ToyTatePairing>(generator, generator);
+        check_pairing::<ToyWeilPairing>(generator, generator.mul_bigint([5]));
+    }
+
+    #[test]
+    fn toy_pairings_are_roots_of_unity() {
+        let p = Affine::<Toy67>::generator();
+        for pairing in [
+            ToyTatePairing::pairing(&p, &p),
+            ToyWeilPairing::pairing(&p, &p),
+        ] {
+            assert_eq!(pairing.pow([17]), F67Quadratic::one());
+        }
+    }
+
+    #[test]
+    fn weil_pairing_is_antisymmetric() {
+        let p = Affine::<Toy67Quadratic>::generator();
+        let q = distortion_map(&Affine::<Toy67>::generator().mul_bigint([3]));
+        let e = weil_pairing(&p, &q, &[17]);
+        assert_eq!(e * weil_pairing(&q, &p, &[17]), F67Quadratic::one());
+        assert_eq!(weil_pairing(&p, &(q + p), &[17]), e);
+    }
+
+    #[test]
+    fn tate_pairing_is_linear_in_second_argument() {
+        // Without the final exponentiation this only holds up to r-th
+        // powers
+        let p = Affine::<Toy67Quadratic>::generator();
+        let q = distortion_map(&Affine::<Toy67>::generator());
+        let r = [17];
+        let final_exponent = [(67 * 67 - 1) / 17];
+        let lhs = tate_pairing(&p, &q.double(), &r, &final_exponent);
+        let rhs = tate_pairing(&p, &q, &r, &final_exponent).square();
+        assert_eq!(lhs, rhs);
+    }
+}
@@ -0,0 +1,187 @@
+//! Scalar multiplication `[k]P` over any [`Group`].
+
+use crate::{Group, PrimeOrderGroup};
+
+/// Left-to-right double-and-add over the bits of `scalar`
+pub fn double_and_add<G: Group>(base: &G, scalar: &[u64]) -> G {
+    let mut result = G::identity();
+    for limb in scalar.iter().rev() {
+        for bit in (0..64).rev() {
+            result = result.double();
+            if (limb >> bit) & 1 == 1 {
+                result = result + *base;
+            }
+        }
+    }
+    result
+}
+
+/// Width-`w` non-adjacent form of `scalar`, least significant digit
+/// first. Every non-zero digit is odd and lies in `(-2^(w-1), 2^(w-1))`,
+/// and any `w` consecutive digits contain at most one non-zero digit.
+pub fn wnaf_digits(scalar: &[u64], window: usize) -> Vec<i64> {
+    assert!((2..=16).contains(&window), "unsupported wNAF window");
+
+    // One spare limb absorbs the carry from negative digits
+    let mut k: Vec<u64> = scalar.iter().copied().chain([0]).collect();
+    let modulus = 1i64 << window;
+    let mut digits = vec![];
+
+    while k.iter().any(|limb| *limb != 0) {
+        let digit = if k[0] & 1 == 1 {
+            let mut digit = (k[0] & (modulus as u64 - 1)) as i64;
+            if digit >= modulus / 2 {
+                digit -= modulus;
+            }
+            // Clear the low bits: `k - digit` is divisible by `2^window`
+            if digit > 0 {
+                sub_small(&mut k, digit as u64);
+            } else {
+                add_small(&mut k, digit.unsigned_abs());
+            }
+            digit
+        } else {
+            0
+        };
+        digits.push(digit);
+        shift_right_one(&mut k);
+    }
+    digits
+}
+
+/// wNAF scalar multiplication: precomputes the odd multiples `P, 3P, ...,
+/// (2^(w-1) - 1)P` and then adds or subtracts one of them for each
+/// non-zero digit while doubling through the rest
+pub fn wnaf_mul<G: Group>(base: &G, scalar: &[u64], window: usize) -> G {
+    let double = base.double();
+    let mut odd_multiples = vec![*base];
+    for idx in 1..(1 << (window - 2)) {
+        odd_multiples.push(odd_multiples[idx - 1] + double);
+    }
+
+    wnaf_digits(scalar, window)
+        .iter()
+        .rev()
+        .fold(G::identity(), |acc, digit| {
+            let acc = acc.double();
+            match digit.signum() {
+                1 => acc + odd_multiples[(*digit as usize - 1) / 2],
+                -1 => acc - odd_multiples[(digit.unsigned_abs() as usize - 1) / 2],
+                _ => acc,
+            }
+        })
+}
+
+/// Multi-scalar multiplication `Σ [k_i] P_i`, one scalar multiplication
+/// per term. Extra bases or scalars are ignored.
+pub fn msm<G: PrimeOrderGroup>(bases: &[G], scalars: &[G::ScalarField]) -> G {
+    bases
+        .iter()
+        .zip(scalars)
+        .fold(G::identity(), |acc, (base, scalar)| {
+            acc + base.mul_scalar(scalar)
+        })
+}
+
+fn add_small(limbs: &mut [u64], value: u64) {
+    let mut carry = value;
+    for limb in limbs.iter_mut() {
+        let (sum, overflow) = limb.overflowing_add(carry);
+        *limb = sum;
+        carry = overflow as u64;
+        if carry == 0 {
+            break;
+        }
+    }
+}
+
+fn sub_small(limbs: &mut [u64], value: u64) {
+    let mut borrow = value;
+    for limb in limbs.iter_mut() {
+        let (diff, underflow) = limb.overflowing_sub(borrow);
+        *limb = diff;
+        borrow = underflow as u64;
+        if borrow == 0 {
+            break;
+        }
+    }
+}
+
+fn shift_right_one(limbs: &mut [u64]) {
+    for idx in 0..limbs.len() {
+        let high = limbs.get(idx + 1).map_or(0, |next| next << 63);
+        limbs[idx] = (limbs[idx] >> 1) | high;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngCore, SeedableRng};
+
+    use finite_fields::PrimeField;
+
+    use super::*;
+    use crate::{short_weierstrass::Jacobian, toy::Toy10007};
+
+    fn digits_value(digits: &[i64]) -> i128 {
+        digits
+            .iter()
+            .rev()
+            .fold(0i128, |acc, digit| 2 * acc + *digit as i128)
+    }
+
+    #[test]
+    fn wnaf_digits_are_valid() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for window in 2..=6 {
+            for _ in 0..100 {
+                let scalar = rng.next_u64() >> 2;
+                let digits = wnaf_digits(&[scalar], window);
+                assert_eq!(digits_value(&digits), scalar as i128);
+                for (idx, digit) in digits.iter().enumerate() {
+                    assert!(*digit == 0 || digit % 2 != 0);
+                    assert!(digit.abs() < 1 << (window - 1));
+                    if *digit != 0 {
+                        assert!(digits[idx + 1..].iter().take(window - 1).all(|d| *d == 0));
+                    }
+                }
+            }
+        }
+        // 7 = 8 - 1 in NAF
+        assert_eq!(wnaf_digits(&[7], 2), vec![-1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn multi_limb_scalars() {
+        // `2^64 + 1` spans two limbs
+        let generator = Jacobian::<Toy10007>::generator();
+        let expected = generator.mul_bigint([u64::MAX]) + generator.double();
+        assert_eq!(double_and_add(&generator, &[1, 1]), expected);
+        for window in 2..=5 {
+            assert_eq!(wnaf_mul(&generator, &[1, 1], window), expected);
+        }
+    }
+
+    #[test]
+    fn msm_is_sum_of_products() {
+        let generator = Jacobian::<Toy10007>::generator();
+        let bases = [generator, generator.double(), generator.mul_bigint([5])];
+        let scalars = [3u64, 4, 10].map(PrimeField::<10039>::from);
+        // 3 + 4 * 2 + 10 * 5 = 61
+        assert_eq!(msm(&bases, &scalars), generator.mul_bigint([61]));
+        assert!(msm::<Jacobian<Toy10007>>(&[], &[]).is_identity());
+    }
+
+    #[test]
+    fn wnaf_agrees_with_double_and_add() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        let generator = Jacobian::<Toy10007>::generator();
+        for _ in 0..20 {
+            let scalar = [rng.next_u64()];
+            let expected = double_and_add(&generator, &scalar);
+            for window in 2..=6 {
+                assert_eq!(wnaf_mul(&generator, &scalar, window), expected);
+            }
+        }
+    }
+}
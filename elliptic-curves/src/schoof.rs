@@ -0,0 +1,382 @@
+//! Counting points on `y^2 = x^3 + Ax + B` over a prime field `F_p`.
+//!
+//! The number of points is `#E = p + 1 - t` where the trace of Frobenius
+//! satisfies Hasse's bound `|t| <= 2 sqrt(p)`. Schoof's algorithm finds
+//! `t mod l` for small primes `l` by working with the `l`-torsion points
+//! symbolically, in the ring `F_p[x, y] / (psi_l(x), y^2 - x^3 - Ax - B)`,
+//! where the Frobenius `pi: (x, y) -> (x^p, y^p)` satisfies
+//! `pi^2 - t pi + p = 0`. Once the product of the primes exceeds `4 sqrt(p)`
+//! the residues pin `t` down by the Chinese remainder theorem.
+
+use finite_fields::{Field, LegendreSymbol, PrimeField};
+use num_traits::{One, Zero};
+use polynomial::Polynomial;
+
+type Poly<const P: u64> = Polynomial<PrimeField<P>>;
+
+/// Counts the points of `y^2 = x^3 + ax + b` over `F_P`, including the
+/// point at infinity, by evaluating the curve equation at every `x`. Takes
+/// `O(P)` time, so it is only meant for small fields.
+pub fn naive_point_count<const P: u64>(a: PrimeField<P>, b: PrimeField<P>) -> u64 {
+    (0..P).fold(1, |count, x| {
+        let x = PrimeField::<P>::new(x);
+        count
+            + match (x * x * x + a * x + b).legendre() {
+                LegendreSymbol::Zero => 1,
+                LegendreSymbol::QuadraticResidue => 2,
+                LegendreSymbol::QuadraticNonResidue => 0,
+            }
+    })
+}
+
+/// Counts the points of `y^2 = x^3 + ax + b` over `F_P`, including the
+/// point at infinity, using Schoof's algorithm. `P` needs to be larger
+/// than 3 and the curve must be non-singular.
+pub fn schoof_point_count<const P: u64>(a: PrimeField<P>, b: PrimeField<P>) -> u64 {
+    assert!(
+        P > 3,
+        "Schoof's algorithm needs characteristic larger than 3"
+    );
+    assert!(
+        !(PrimeField::<P>::new(4) * a * a * a + PrimeField::<P>::new(27) * b * b).is_zero(),
+        "curve is singular"
+    );
+
+    let f = poly(&[b, a, PrimeField::zero(), PrimeField::one()]);
+
+    // t mod 2: t is even exactly when the curve has a point of order two,
+    // i.e. when f has a root in F_p
+    let x = poly(&[PrimeField::zero(), PrimeField::one()]);
+    let x_p = pow_mod(&x, P as u128, &f);
+    let t_mod_2 = if gcd(&(&x_p - &x), &f).leading_degree() == Some(0) {
+        1
+    } else {
+        0
+    };
+
+    let mut residues = vec![(t_mod_2, 2u64)];
+    let mut modulus = 2u128;
+    let mut division_polynomials = DivisionPolynomials::new(a, b);
+    let mut l = 3u64;
+    while modulus * modulus <= 16 * P as u128 {
+        if l != P && is_small_prime(l) {
+            let psi = division_polynomials.get(l as usize);
+            residues.push((trace_mod_l(&f, &psi, l), l));
+            modulus *= l as u128;
+        }
+        l += 2;
+    }
+
+    // Chinese remainder theorem, then lift t into (-modulus / 2, modulus / 2]
+    let t = residues.iter().fold(0u128, |acc, &(residue, l)| {
+        let l = l as u128;
+        let rest = modulus / l;
+        let rest_inv = inverse_mod(rest % l, l);
+        (acc + residue as u128 * rest % modulus * rest_inv) % modulus
+    });
+    let t = if t > modulus / 2 {
+        t as i128 - modulus as i128
+    } else {
+        t as i128
+    };
+
+    (P as i128 + 1 - t) as u64
+}
+
+/// Finds `t mod l` for an odd prime `l` by searching for `tau` such that
+/// `pi^2(P) + [p]P = [tau] pi(P)` for a generic `l`-torsion point `P`.
+fn trace_mod_l<const P: u64>(f: &Poly<P>, psi: &Poly<P>, l: u64) -> u64 {
+    let mut ring = TorsionRing {
+        modulus: monic(psi),
+        f: f.clone(),
+    };
+    // Whenever an inversion fails, the non-invertible element shares a
+    // factor with the modulus. The relation holds for every `l`-torsion
+    // point, so we continue with the points whose x-coordinate is a root
+    // of the factor.
+    loop {
+        match ring.trace(l) {
+            Ok(tau) => return tau,
+            Err(factor) => ring.modulus = factor,
+        }
+    }
+}
+
+/// Reduced division polynomials `g_n`, with `psi_n = g_n` for odd `n` and
+/// `psi_n = 2y g_n` for even `n`, so that none of them involve `y`
+struct DivisionPolynomials<const P: u64> {
+    f_squared: Poly<P>,
+    polynomials: Vec<Poly<P>>,
+}
+
+impl<const P: u64> DivisionPolynomials<P> {
+    fn new(a: PrimeField<P>, b: PrimeField<P>) -> Self {
+        let c = |value: u64| PrimeField::<P>::new(value);
+        let f = poly(&[b, a, c(0), c(1)]);
+        let g3 = poly(&[-(a * a), c(12) * b, c(6) * a, c(0), c(3)]);
+        let g4 = poly(&[
+            c(2) * (-c(8) * b * b - a * a * a),
+            c(2) * (-c(4) * a * b),
+            c(2) * (-c(5) * a * a),
+            c(2) * c(20) * b,
+            c(2) * c(5) * a,
+            c(0),
+            c(2),
+        ]);
+        Self {
+            f_squared: &f * &f,
+            polynomials: vec![Poly::zero(), poly(&[c(1)]), poly(&[c(1)]), g3, g4],
+        }
+    }
+
+    fn get(&mut self, n: usize) -> Poly<P> {
+        let sixteen_f_squared = &poly(&[PrimeField::new(16)]) * &self.f_squared;
+        let g = &mut self.polynomials;
+        while g.len() <= n {
+            let k = g.len();
+            let m = k / 2;
+            let cube = |p: &Poly<P>| &(p * p) * p;
+            let next = if k % 2 == 1 {
+                let (lhs, rhs) = (&g[m + 2] * &cube(&g[m]), &g[m - 1] * &cube(&g[m + 1]));
+                if m % 2 == 0 {
+                    &(&sixteen_f_squared * &lhs) - &rhs
+                } else {
+                    &lhs - &(&sixteen_f_squared * &rhs)
+                }
+            } else {
+                let lhs = &g[m + 2] * &(&g[m - 1] * &g[m - 1]);
+                let rhs = &g[m - 2] * &(&g[m + 1] * &g[m + 1]);
+                &g[m] * &(&lhs - &rhs)
+            };
+            g.push(next);
+        }
+        g[n].clone()
+    }
+}
+
+/// A point `(a(x), b(x) y)` of the curve over `F_p[x, y] / (h(x), y^2 - f(x))`
+#[derive(Clone)]
+enum RingPoint<const P: u64> {
+    Infinity,
+    Affine(Poly<P>, Poly<P>),
+}
+
+/// Arithmetic in `F_p[x] / h(x)`. Operations return `Err` with a proper
+/// factor of `h` when they run into a non-invertible element.
+struct TorsionRing<const P: u64> {
+    modulus: Poly<P>,
+    f: Poly<P>,
+}
+
+impl<const P: u64> TorsionRing<P> {
+    fn reduce(&self, value: &Poly<P>) -> Poly<P> {
+        value.div_rem(&self.modulus).1
+    }
+
+    fn mul(&self, lhs: &Poly<P>, rhs: &Poly<P>) -> Poly<P> {
+        self.reduce(&(lhs * rhs))
+    }
+
+    fn inverse(&self, value: &Poly<P>) -> Result<Poly<P>, Poly<P>> {
+        let (gcd, inverse) = extended_gcd(value, &self.modulus);
+        if gcd.leading_degree() == Some(0) {
+            Ok(self.reduce(&inverse))
+        } else {
+            Err(gcd)
+        }
+    }
+
+    fn eq(&self, lhs: &RingPoint<P>, rhs: &RingPoint<P>) -> bool {
+        match (lhs, rhs) {
+            (RingPoint::Infinity, RingPoint::Infinity) => true,
+            (RingPoint::Affine(a1, b1), RingPoint::Affine(a2, b2)) => {
+                (a1 - a2).is_zero() && (b1 - b2).is_zero()
+            }
+            _ => false,
+        }
+    }
+
+    fn double(&self, point: &RingPoint<P>) -> Result<RingPoint<P>, Poly<P>> {
+        let RingPoint::Affine(a, b) = point else {
+            return Ok(RingPoint::Infinity);
+        };
+        if b.is_zero() {
+            return Ok(RingPoint::Infinity);
+        }
+        // lambda = (3a^2 + A) / (2 b y) = (3a^2 + A) / (2 b f) * y
+        let curve_a = self.f.get_raw_coefficients()[1];
+        let numerator = &(&poly(&[PrimeField::new(3)]) * &self.mul(a, a)) + &poly(&[curve_a]);
+        let denominator = self.mul(&(&poly(&[PrimeField::new(2)]) * b), &self.f);
+        let lambda = self.mul(&numerator, &self.inverse(&denominator)?);
+        Ok(self.chord(&lambda, a, b, a))
+    }
+
+    fn add(&self, lhs: &RingPoint<P>, rhs: &RingPoint<P>) -> Result<RingPoint<P>, Poly<P>> {
+        let (RingPoint::Affine(a1, b1), RingPoint::Affine(a2, b2)) = (lhs, rhs) else {
+            return Ok(match lhs {
+                RingPoint::Infinity => rhs.clone(),
+                _ => lhs.clone(),
+            });
+        };
+        if (a1 - a2).is_zero() {
+            return if (b1 - b2).is_zero() {
+                self.double(lhs)
+            } else if (b1 + b2).is_zero() {
+                Ok(RingPoint::Infinity)
+            } else {
+                // b1 = b2 at some roots of h and b1 = -b2 at others
+                Err(gcd(&(b1 - b2), &self.modulus))
+            };
+        }
+        // lambda = (b2 - b1) y / (a2 - a1)
+        let lambda = self.mul(&(b2 - b1), &self.inverse(&(a2 - a1))?);
+        Ok(self.chord(&lambda, a1, b1, a2))
+    }
+
+    /// Third intersection of the line through `(a1, b1 y)` of slope
+    /// `lambda y` with the curve, negated. `a2` is the x-coordinate of the
+    /// second point on the line.
+    fn chord(&self, lambda: &Poly<P>, a1: &Poly<P>, b1: &Poly<P>, a2: &Poly<P>) -> RingPoint<P> {
+        let a3 = self.reduce(&(&(&self.mul(&self.mul(lambda, lambda), &self.f) - a1) - a2));
+        let b3 = self.reduce(&(&self.mul(lambda, &(a1 - &a3)) - b1));
+        RingPoint::Affine(a3, b3)
+    }
+
+    fn scalar_mul(&self, point: &RingPoint<P>, scalar: u64) -> Result<RingPoint<P>, Poly<P>> {
+        let mut result = RingPoint::Infinity;
+        for bit in (0..64).rev() {
+            result = self.double(&result)?;
+            if (scalar >> bit) & 1 == 1 {
+                result = self.add(&result, point)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Frobenius applied to the generic point: `(x^q, y^q)` where
+    /// `y^q = f^((q - 1) / 2) y`
+    fn frobenius(&self, q: u128) -> RingPoint<P> {
+        let x = poly(&[PrimeField::zero(), PrimeField::one()]);
+        RingPoint::Affine(
+            pow_mod(&x, q, &self.modulus),
+            pow_mod(&self.f, (q - 1) / 2, &self.modulus),
+        )
+    }
+
+    fn trace(&self, l: u64) -> Result<u64, Poly<P>> {
+        let generic = RingPoint::Affine(
+            self.reduce(&poly(&[PrimeField::zero(), PrimeField::one()])),
+            poly(&[PrimeField::one()]),
+        );
+        let pi = self.frobenius(P as u128);
+        let pi_squared = self.frobenius(P as u128 * P as u128);
+        let target = self.add(&pi_squared, &self.scalar_mul(&generic, P % l)?)?;
+        if matches!(target, RingPoint::Infinity) {
+            return Ok(0);
+        }
+
+        let mut multiple = pi.clone();
+        for tau in 1..l {
+            if self.eq(&multiple, &target) {
+                return Ok(tau);
+            }
+            multiple = self.add(&multiple, &pi)?;
+        }
+        unreachable!("the characteristic equation of Frobenius always has a solution")
+    }
+}
+
+/// Inverse of `value` modulo a prime by Fermat's little theorem
+fn inverse_mod(value: u128, modulus: u128) -> u128 {
+    let (mut base, mut exp, mut result) = (value, modulus - 2, 1u128);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result
+}
+
+fn is_small_prime(n: u64) -> bool {
+    n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n % d != 0)
+}
+
+fn poly<const P: u64>(coeffs: &[PrimeField<P>]) -> Poly<P> {
+    Poly::new_from_coeffs(coeffs)
+}
+
+fn monic<const P: u64>(value: &Poly<P>) -> Poly<P> {
+    let scale = value
+        .leading_coefficient()
+        .expect("zero polynomial")
+        .inverse()
+        .unwrap();
+    value * &poly(&[scale])
+}
+
+fn pow_mod<const P: u64>(base: &Poly<P>, exp: u128, modulus: &Poly<P>) -> Poly<P> {
+    let mut result = poly(&[PrimeField::one()]);
+    let mut base = base.div_rem(modulus).1;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = (&result * &base).div_rem(modulus).1;
+        }
+        base = (&base * &base).div_rem(modulus).1;
+        exp >>= 1;
+    }
+    result.div_rem(modulus).1
+}
+
+/// Monic greatest common divisor
+fn gcd<const P: u64>(lhs: &Poly<P>, rhs: &Poly<P>) -> Poly<P> {
+    let (mut a, mut b) = (lhs.clone(), rhs.clone());
+    while !b.is_zero() {
+        let remainder = a.div_rem(&b).1;
+        a = b;
+        b = remainder;
+    }
+    monic(&a)
+}
+
+/// Returns `(g, s)` with `s * value = g (mod modulus)` and `g` the monic
+/// greatest common divisor of `value` and `modulus`
+fn extended_gcd<const P: u64>(value: &Poly<P>, modulus: &Poly<P>) -> (Poly<P>, Poly<P>) {
+    let (mut r0, mut r1) = (modulus.clone(), value.div_rem(modulus).1);
+    let (mut s0, mut s1) = (Poly::zero(), poly(&[PrimeField::one()]));
+    while !r1.is_zero() {
+        let (quotient, remainder) = r0.div_rem(&r1);
+        let s2 = &s0 - &(&quotient * &s1);
+        (r0, r1) = (r1, remainder);
+        (s0, s1) = (s1, s2);
+    }
+    let scale = r0.leading_coefficient().unwrap().inverse().unwrap();
+    (&r0 * &poly(&[scale]), &s0 * &poly(&[scale]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_toy_curves() {
+        type F101 = PrimeField<101>;
+        type F10007 = PrimeField<10007>;
+
+        assert_eq!(naive_point_count(F101::new(3), F101::new(17)), 107);
+        assert_eq!(schoof_point_count(F101::new(3), F101::new(17)), 107);
+        assert_eq!(naive_point_count(F10007::new(3), F10007::new(6)), 10039);
+        assert_eq!(schoof_point_count(F10007::new(3), F10007::new(6)), 10039);
+    }
+
+    #[test]
+    fn schoof_agrees_with_naive_count() {
+        type F = PrimeField<1009>;
+        for (a, b) in [(1, 1), (0, 7), (5, 0), (2, 3), (100, 200), (1008, 17)] {
+            let (a, b) = (F::new(a), F::new(b));
+            assert_eq!(schoof_point_count(a, b), naive_point_count(a, b));
+        }
+    }
+}
@@ -0,0 +1,517 @@
+//! Curves in short Weierstrass form `y^2 = x^3 + a x + b`, with points in
+//! affine, homogeneous projective and Jacobian coordinates.
+
+use std::{
+    fmt::Debug,
+    ops::{Add, Neg, Sub},
+};
+
+use finite_fields::Field;
+use num_traits::{One, Zero};
+
+use crate::{Group, PrimeOrderGroup};
+
+/// Parameters of a short Weierstrass curve `y^2 = x^3 + a x + b`
+pub trait WeierstrassConfig: 'static + Copy + Debug + Eq {
+    type BaseField: Field;
+    type ScalarField: Field + From<u64>;
+
+    const A: Self::BaseField;
+    const B: Self::BaseField;
+
+    /// Affine coordinates of a generator of the prime order subgroup
+    const GENERATOR: (Self::BaseField, Self::BaseField);
+
+    /// Number of curve points divided by the order of the subgroup
+    const COFACTOR: u64;
+
+    /// Canonical representative of a scalar as little-endian `u64` limbs
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64>;
+}
+
+/// A point `(x, y)` satisfying the curve equation, or the point at
+/// infinity. Addition needs a field inversion per operation.
+#[derive(Debug, Clone, Copy)]
+pub struct Affine<C: WeierstrassConfig> {
+    pub x: C::BaseField,
+    pub y: C::BaseField,
+    pub infinity: bool,
+}
+
+/// A point `(X : Y : Z)` in homogeneous projective coordinates, standing
+/// for the affine point `(X / Z, Y / Z)`. The point at infinity is
+/// `(0 : 1 : 0)`.
+#[derive(Debug, Clone, Copy)]
+pub struct Projective<C: WeierstrassConfig> {
+    pub x: C::BaseField,
+    pub y: C::BaseField,
+    pub z: C::BaseField,
+}
+
+/// A point `(X : Y : Z)` in Jacobian coordinates, standing for the affine
+/// point `(X / Z^2, Y / Z^3)`. The point at infinity is `(1 : 1 : 0)`.
+#[derive(Debug, Clone, Copy)]
+pub struct Jacobian<C: WeierstrassConfig> {
+    pub x: C::BaseField,
+    pub y: C::BaseField,
+    pub z: C::BaseField,
+}
+
+fn constant<F: Field>(value: u64) -> F {
+    F::from_u64(value)
+}
+
+impl<C: WeierstrassConfig> Affine<C> {
+    /// Generate a point from its coordinates, `None` if it is not on the
+    /// curve
+    pub fn new(x: C::BaseField, y: C::BaseField) -> Option<Self> {
+        let point = Self::new_unchecked(x, y);
+        point.is_on_curve().then_some(point)
+    }
+
+    /// Generate a point without checking the curve equation
+    pub fn new_unchecked(x: C::BaseField, y: C::BaseField) -> Self {
+        Self {
+            x,
+            y,
+            infinity: false,
+        }
+    }
+
+    /// Checks whether `y^2 = x^3 + a x + b`
+    pub fn is_on_curve(&self) -> bool {
+        self.infinity || self.y.square() == self.x.square() * self.x + C::A * self.x + C::B
+    }
+
+    /// Checks that `[r]P` is the identity, where `r` is the order of the
+    /// subgroup generated by `C::GENERATOR`
+    pub fn is_in_prime_order_subgroup(&self) -> bool {
+        let order = C::scalar_to_limbs(&-C::ScalarField::one());
+        (self.mul_bigint(order) + *self).is_identity()
+    }
+
+    pub fn to_projective(&self) -> Projective<C> {
+        if self.infinity {
+            return Projective::identity();
+        }
+        Projective {
+            x: self.x,
+            y: self.y,
+            z: C::BaseField::one(),
+        }
+    }
+
+    pub fn to_jacobian(&self) -> Jacobian<C> {
+        if self.infinity {
+            return Jacobian::identity();
+        }
+        Jacobian {
+            x: self.x,
+            y: self.y,
+            z: C::BaseField::one(),
+        }
+    }
+}
+
+impl<C: WeierstrassConfig> PartialEq for Affine<C> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.infinity, other.infinity) {
+            (true, true) => true,
+            (false, false) => self.x == other.x && self.y == other.y,
+            _ => false,
+        }
+    }
+}
+
+impl<C: WeierstrassConfig> Eq for Affine<C> {}
+
+impl<C: WeierstrassConfig> Group for Affine<C> {
+    fn identity() -> Self {
+        Self {
+            x: C::BaseField::zero(),
+            y: C::BaseField::one(),
+            infinity: true,
+        }
+    }
+
+    fn generator() -> Self {
+        Self::new_unchecked(C::GENERATOR.0, C::GENERATOR.1)
+    }
+
+    /// Tangent rule, `λ = (3x^2 + a) / 2y`
+    fn double(&self) -> Self {
+        if self.infinity || self.y.is_zero() {
+            return Self::identity();
+        }
+        let lambda = (constant::<C::BaseField>(3) * self.x.square() + C::A)
+            / (constant::<C::BaseField>(2) * self.y);
+        let x = lambda.square() - self.x - self.x;
+        let y = lambda * (self.x - x) - self.y;
+        Self::new_unchecked(x, y)
+    }
+}
+
+impl<C: WeierstrassConfig> Add for Affine<C> {
+    type Output = Self;
+
+    /// Chord rule, `λ = (y2 - y1) / (x2 - x1)`, and the third intersection
+    /// of the line with the curve reflected over the x-axis
+    fn add(self, rhs: Self) -> Self {
+        if self.infinity {
+            return rhs;
+        }
+        if rhs.infinity {
+            return self;
+        }
+        if self.x == rhs.x {
+            return if self.y == rhs.y {
+                self.double()
+            } else {
+                Self::identity()
+            };
+        }
+        let lambda = (rhs.y - self.y) / (rhs.x - self.x);
+        let x = lambda.square() - self.x - rhs.x;
+        let y = lambda * (self.x - x) - self.y;
+        Self::new_unchecked(x, y)
+    }
+}
+
+impl<C: WeierstrassConfig> Neg for Affine<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self { y: -self.y, ..self }
+    }
+}
+
+impl<C: WeierstrassConfig> Sub for Affine<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl<C: WeierstrassConfig> Projective<C> {
+    pub fn to_affine(&self) -> Affine<C> {
+        match self.z.inverse() {
+            None => Affine::identity(),
+            Some(z_inv) => Affine::new_unchecked(self.x * z_inv, self.y * z_inv),
+        }
+    }
+}
+
+impl<C: WeierstrassConfig> PartialEq for Projective<C> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.z.is_zero(), other.z.is_zero()) {
+            (true, true) => true,
+            (false, false) => {
+                self.x * other.z == other.x * self.z && self.y * other.z == other.y * self.z
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<C: WeierstrassConfig> Eq for Projective<C> {}
+
+impl<C: WeierstrassConfig> Group for Projective<C> {
+    fn identity() -> Self {
+        Self {
+            x: C::BaseField::zero(),
+            y: C::BaseField::one(),
+            z: C::BaseField::zero(),
+        }
+    }
+
+    fn generator() -> Self {
+        Affine::<C>::generator().to_projective()
+    }
+
+    /// `dbl-2007-bl` from the Explicit-Formulas Database
+    fn double(&self) -> Self {
+        if self.z.is_zero() || self.y.is_zero() {
+            return Self::identity();
+        }
+        let two = constant::<C::BaseField>(2);
+        let xx = self.x.square();
+        let zz = self.z.square();
+        let w = C::A * zz + constant::<C::BaseField>(3) * xx;
+        let s = two * self.y * self.z;
+        let ss = s.square();
+        let sss = s * ss;
+        let r = self.y * s;
+        let rr = r.square();
+        let b = (self.x + r).square() - xx - rr;
+        let h = w.square() - two * b;
+        Self {
+            x: h * s,
+            y: w * (b - h) - two * rr,
+            z: sss,
+        }
+    }
+}
+
+impl<C: WeierstrassConfig> Add for Projective<C> {
+    type Output = Self;
+
+    /// `add-1998-cmo-2` from the Explicit-Formulas Database
+    fn add(self, rhs: Self) -> Self {
+        if self.z.is_zero() {
+            return rhs;
+        }
+        if rhs.z.is_zero() {
+            return self;
+        }
+        let y1z2 = self.y * rhs.z;
+        let x1z2 = self.x * rhs.z;
+        let z1z2 = self.z * rhs.z;
+        let u = rhs.y * self.z - y1z2;
+        let v = rhs.x * self.z - x1z2;
+        if v.is_zero() {
+            return if u.is_zero() {
+                self.double()
+            } else {
+                Self::identity()
+            };
+        }
+        let uu = u.square();
+        let vv = v.square();
+        let vvv = v * vv;
+        let r = vv * x1z2;
+        let a = uu * z1z2 - vvv - constant::<C::BaseField>(2) * r;
+        Self {
+            x: v * a,
+            y: u * (r - a) - vvv * y1z2,
+            z: vvv * z1z2,
+        }
+    }
+}
+
+impl<C: WeierstrassConfig> Neg for Projective<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self { y: -self.y, ..self }
+    }
+}
+
+impl<C: WeierstrassConfig> Sub for Projective<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl<C: WeierstrassConfig> Jacobian<C> {
+    pub fn to_affine(&self) -> Affine<C> {
+        match self.z.inverse() {
+            None => Affine::identity(),
+            Some(z_inv) => {
+                let z_inv2 = z_inv.square();
+                Affine::new_unchecked(self.x * z_inv2, self.y * z_inv2 * z_inv)
+            }
+        }
+    }
+}
+
+impl<C: WeierstrassConfig> PartialEq for Jacobian<C> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.z.is_zero(), other.z.is_zero()) {
+            (true, true) => true,
+            (false, false) => {
+                let (z1z1, z2z2) = (self.z.square(), other.z.square());
+                self.x * z2z2 == other.x * z1z1
+                    && self.y * z2z2 * other.z == other.y * z1z1 * self.z
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<C: WeierstrassConfig> Eq for Jacobian<C> {}
+
+impl<C: WeierstrassConfig> Group for Jacobian<C> {
+    fn identity() -> Self {
+        Self {
+            x: C::BaseField::one(),
+            y: C::BaseField::one(),
+            z: C::BaseField::zero(),
+        }
+    }
+
+    fn generator() -> Self {
+        Affine::<C>::generator().to_jacobian()
+    }
+
+    /// `dbl-2007-bl` from the Explicit-Formulas Database
+    fn double(&self) -> Self {
+        if self.z.is_zero() || self.y.is_zero() {
+            return Self::identity();
+        }
+        let two = constant::<C::BaseField>(2);
+        let xx = self.x.square();
+        let yy = self.y.square();
+        let yyyy = yy.square();
+        let zz = self.z.square();
+        let s = two * ((self.x + yy).square() - xx - yyyy);
+        let m = constant::<C::BaseField>(3) * xx + C::A * zz.square();
+        let t = m.square() - two * s;
+        Self {
+            x: t,
+            y: m * (s - t) - constant::<C::BaseField>(8) * yyyy,
+            z: (self.y + self.z).square() - yy - zz,
+        }
+    }
+}
+
+impl<C: WeierstrassConfig> Add for Jacobian<C> {
+    type Output = Self;
+
+    /// `add-2007-bl` from the Explicit-Formulas Database
+    fn add(self, rhs: Self) -> Self {
+        if self.z.is_zero() {
+            return rhs;
+        }
+        if rhs.z.is_zero() {
+            return self;
+        }
+        let two = constant::<C::BaseField>(2);
+        let z1z1 = self.z.square();
+        let z2z2 = rhs.z.square();
+        let u1 = self.x * z2z2;
+        let u2 = rhs.x * z1z1;
+        let s1 = self.y * rhs.z * z2z2;
+        let s2 = rhs.y * self.z * z1z1;
+        let h = u2 - u1;
+        if h.is_zero() {
+            return if s1 == s2 {
+                self.double()
+            } else {
+                Self::identity()
+            };
+        }
+        let i = (two * h).square();
+        let j = h * i;
+        let r = two * (s2 - s1);
+        let v = u1 * i;
+        let x = r.square() - j - two * v;
+        Self {
+            x,
+            y: r * (v - x) - two * s1 * j,
+            z: ((self.z + rhs.z).square() - z1z1 - z2z2) * h,
+        }
+    }
+}
+
+impl<C: WeierstrassConfig> Neg for Jacobian<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self { y: -self.y, ..self }
+    }
+}
+
+impl<C: WeierstrassConfig> Sub for Jacobian<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl<C: WeierstrassConfig> PrimeOrderGroup for Affine<C> {
+    type ScalarField = C::ScalarField;
+
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64> {
+        C::scalar_to_limbs(scalar)
+    }
+}
+
+impl<C: WeierstrassConfig> PrimeOrderGroup for Projective<C> {
+    type ScalarField = C::ScalarField;
+
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64> {
+        C::scalar_to_limbs(scalar)
+    }
+}
+
+impl<C: WeierstrassConfig> PrimeOrderGroup for Jacobian<C> {
+    type ScalarField = C::ScalarField;
+
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64> {
+        C::scalar_to_limbs(scalar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+    use crate::toy::{Toy10007, Toy101};
+
+    type F101 = PrimeField<101>;
+
+    #[test]
+    fn hand_checkable_points() {
+        // 44^2 = 1936 = 17 mod 101, and 0^3 + 3 * 0 + 17 = 17
+        let generator = Affine::<Toy101>::generator();
+        assert!(generator.is_on_curve());
+        assert!(Affine::<Toy101>::new(F101::new(0), F101::new(45)).is_none());
+
+        // Doubling (0, 44): λ = 3 / 88 = 93, x = 93^2 = 64 and
+        // y = 93 * (0 - 64) - 44 = 64
+        let doubled = generator.double();
+        assert_eq!((doubled.x.value(), doubled.y.value()), (64, 64));
+        assert!(doubled.is_on_curve());
+    }
+
+    #[test]
+    fn generator_has_prime_order() {
+        let generator = Affine::<Toy101>::generator();
+        assert!(generator.mul_bigint([107]).is_identity());
+        assert!(!generator.mul_bigint([53]).is_identity());
+        assert!(generator.is_in_prime_order_subgroup());
+
+        let generator = Jacobian::<Toy10007>::generator();
+        assert!(generator.mul_bigint([10039]).is_identity());
+    }
+
+    #[test]
+    fn coordinate_systems_agree() {
+        let affine = Affine::<Toy101>::generator();
+        let projective = Projective::<Toy101>::generator();
+        let jacobian = Jacobian::<Toy101>::generator();
+
+        let mut expected = Affine::identity();
+        for k in 0..120u64 {
+            assert_eq!(affine.mul_bigint([k]), expected);
+            assert_eq!(projective.mul_bigint([k]).to_affine(), expected);
+            assert_eq!(jacobian.mul_bigint([k]).to_affine(), expected);
+            assert!(expected.is_on_curve());
+            expected = expected + affine;
+        }
+    }
+
+    #[test]
+    fn group_laws() {
+        let p = Jacobian::<Toy10007>::generator().mul_bigint([1234]);
+        let q = Jacobian::<Toy10007>::generator().mul_bigint([5678]);
+        let r = Jacobian::<Toy10007>::generator().mul_bigint([91011]);
+
+        assert_eq!(p + q, q + p);
+        assert_eq!((p + q) + r, p + (q + r));
+        assert_eq!(p - p, Jacobian::identity());
+        assert_eq!(p + Jacobian::identity(), p);
+        assert_eq!(p.double(), p + p);
+        assert_eq!(
+            (p + q).to_affine(),
+            Jacobian::<Toy10007>::generator()
+                .mul_bigint([1234 + 5678])
+                .to_affine()
+        );
+    }
+}
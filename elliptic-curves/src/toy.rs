@@ -0,0 +1,175 @@
+//! Curves over tiny prime fields, small enough to check by hand.
+
+use finite_fields::{Extendable, ExtensionField, Field, PrimeField};
+
+use crate::{
+    pairing::{tate_pairing, weil_pairing, Pairing},
+    short_weierstrass::{Affine, WeierstrassConfig},
+    twisted_edwards::EdwardsConfig,
+    Group,
+};
+
+/// `y^2 = x^3 + 3x + 17` over `F_101`. The curve has 107 points, a prime,
+/// so every point other than the identity generates it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Toy101;
+
+impl WeierstrassConfig for Toy101 {
+    type BaseField = PrimeField<101>;
+    type ScalarField = PrimeField<107>;
+
+    const A: Self::BaseField = PrimeField::new(3);
+    const B: Self::BaseField = PrimeField::new(17);
+    const GENERATOR: (Self::BaseField, Self::BaseField) = (PrimeField::new(0), PrimeField::new(44));
+    const COFACTOR: u64 = 1;
+
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64> {
+        vec![scalar.value()]
+    }
+}
+
+/// `y^2 = x^3 + 3x + 6` over `F_10007`, a group of prime order 10039
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Toy10007;
+
+impl WeierstrassConfig for Toy10007 {
+    type BaseField = PrimeField<10007>;
+    type ScalarField = PrimeField<10039>;
+
+    const A: Self::BaseField = PrimeField::new(3);
+    const B: Self::BaseField = PrimeField::new(6);
+    const GENERATOR: (Self::BaseField, Self::BaseField) =
+        (PrimeField::new(0), PrimeField::new(1973));
+    const COFACTOR: u64 = 1;
+
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64> {
+        vec![scalar.value()]
+    }
+}
+
+/// `x^2 + y^2 = 1 + 27 x^2 y^2` over `F_101`. Since 27 is not a square
+/// mod 101 the addition law is complete. The curve has `92 = 4 * 23`
+/// points, and `(44, 76)` generates the subgroup of order 23.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToyEdwards101;
+
+impl EdwardsConfig for ToyEdwards101 {
+    type BaseField = PrimeField<101>;
+    type ScalarField = PrimeField<23>;
+
+    const A: Self::BaseField = PrimeField::new(1);
+    const D: Self::BaseField = PrimeField::new(27);
+    const GENERATOR: (Self::BaseField, Self::BaseField) =
+        (PrimeField::new(44), PrimeField::new(76));
+    const COFACTOR: u64 = 4;
+
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64> {
+        vec![scalar.value()]
+    }
+}
+
+/// `y^2 = x^3 + x` over `F_67`. As `67 = 3 (mod 4)` the curve is
+/// supersingular with `68 = 4 * 17` points and embedding degree 2: the
+/// 17-torsion is fully defined over `F_{67^2}`. `(62, 65)` generates the
+/// subgroup of order 17.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Toy67;
+
+impl WeierstrassConfig for Toy67 {
+    type BaseField = PrimeField<67>;
+    type ScalarField = PrimeField<17>;
+
+    const A: Self::BaseField = PrimeField::new(1);
+    const B: Self::BaseField = PrimeField::new(0);
+    const GENERATOR: (Self::BaseField, Self::BaseField) =
+        (PrimeField::new(62), PrimeField::new(65));
+    const COFACTOR: u64 = 4;
+
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64> {
+        vec![scalar.value()]
+    }
+}
+
+/// The curve of `Toy67` over the quadratic extension `F_{67^2}`, which has
+/// `68^2 = 272 * 17` points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Toy67Quadratic;
+
+impl WeierstrassConfig for Toy67Quadratic {
+    type BaseField = ExtensionField<PrimeField<67>, 2>;
+    type ScalarField = PrimeField<17>;
+
+    const A: Self::BaseField = ExtensionField::new([PrimeField::new(1), PrimeField::new(0)]);
+    const B: Self::BaseField = ExtensionField::new([PrimeField::new(0), PrimeField::new(0)]);
+    const GENERATOR: (Self::BaseField, Self::BaseField) = (
+        ExtensionField::new([PrimeField::new(62), PrimeField::new(0)]),
+        ExtensionField::new([PrimeField::new(65), PrimeField::new(0)]),
+    );
+    const COFACTOR: u64 = 272;
+
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64> {
+        vec![scalar.value()]
+    }
+}
+
+/// Distortion map `(x, y) -> (-x, iy)` with `i^2 = -1`. It is an
+/// endomorphism of `Toy67` taking points over `F_67` to points over
+/// `F_{67^2}` outside the group they started in, which makes pairings on
+/// `Toy67` non-degenerate even when both arguments are the same.
+pub fn distortion_map(point: &Affine<Toy67>) -> Affine<Toy67Quadratic> {
+    if point.infinity {
+        return Affine::identity();
+    }
+    // `x^2 = β` in `F_67[x] / (x^2 - β)` for a non-square `β`, so that
+    // `i = x sqrt(-1 / β)`
+    let beta = <PrimeField<67> as Extendable<2>>::NON_RESIDUE;
+    let scale = (-beta.inverse().unwrap())
+        .sqrt()
+        .expect("-1 / β is a square as both -1 and β are not");
+    let i = ExtensionField::adjoined_root() * ExtensionField::from_base(scale);
+    Affine::new_unchecked(
+        -ExtensionField::from_base(point.x),
+        i * ExtensionField::from_base(point.y),
+    )
+}
+
+/// The symmetric reduced Tate pairing `e(P, Q) = t(P, ψ(Q))` on `Toy67`,
+/// where `ψ` is the distortion map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToyTatePairing;
+
+impl Pairing for ToyTatePairing {
+    type G1 = Affine<Toy67>;
+    type G2 = Affine<Toy67>;
+    type Target = ExtensionField<PrimeField<67>, 2>;
+
+    fn pairing(p: &Self::G1, q: &Self::G2) -> Self::Target {
+        tate_pairing(&lift(p), &distortion_map(q), &[17], &[(67 * 67 - 1) / 17])
+    }
+}
+
+/// The symmetric Weil pairing `e(P, Q) = w(P, ψ(Q))` on `Toy67`, where `ψ`
+/// is the distortion map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToyWeilPairing;
+
+impl Pairing for ToyWeilPairing {
+    type G1 = Affine<Toy67>;
+    type G2 = Affine<Toy67>;
+    type Target = ExtensionField<PrimeField<67>, 2>;
+
+    fn pairing(p: &Self::G1, q: &Self::G2) -> Self::Target {
+        weil_pairing(&lift(p), &distortion_map(q), &[17])
+    }
+}
+
+/// Embeds a point of `Toy67` into the same curve over `F_{67^2}`
+fn lift(point: &Affine<Toy67>) -> Affine<Toy67Quadratic> {
+    if point.infinity {
+        return Affine::identity();
+    }
+    Affine::new_unchecked(
+        ExtensionField::from_base(point.x),
+        ExtensionField::from_base(point.y),
+    )
+}
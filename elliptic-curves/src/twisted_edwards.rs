@@ -0,0 +1,277 @@
+//! Curves in twisted Edwards form `a x^2 + y^2 = 1 + d x^2 y^2`, with
+//! points in affine and homogeneous projective coordinates.
+//!
+//! When `a` is a square and `d` is not, the addition law below has no
+//! exceptional cases: the same formula adds distinct points, doubles and
+//! handles the identity `(0, 1)`.
+
+use std::{
+    fmt::Debug,
+    ops::{Add, Neg, Sub},
+};
+
+use finite_fields::Field;
+use num_traits::{One, Zero};
+
+use crate::{Group, PrimeOrderGroup};
+
+/// Parameters of a twisted Edwards curve `a x^2 + y^2 = 1 + d x^2 y^2`
+pub trait EdwardsConfig: 'static + Copy + Debug + Eq {
+    type BaseField: Field;
+    type ScalarField: Field + From<u64>;
+
+    const A: Self::BaseField;
+    const D: Self::BaseField;
+
+    /// Affine coordinates of a generator of the prime order subgroup
+    const GENERATOR: (Self::BaseField, Self::BaseField);
+
+    /// Number of curve points divided by the order of the subgroup, always
+    /// a multiple of 4 for Edwards curves
+    const COFACTOR: u64;
+
+    /// Canonical representative of a scalar as little-endian `u64` limbs
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64>;
+}
+
+/// A point `(x, y)` on a twisted Edwards curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdwardsAffine<C: EdwardsConfig> {
+    pub x: C::BaseField,
+    pub y: C::BaseField,
+}
+
+/// A point `(X : Y : Z)` standing for the affine point `(X / Z, Y / Z)`
+#[derive(Debug, Clone, Copy)]
+pub struct EdwardsProjective<C: EdwardsConfig> {
+    pub x: C::BaseField,
+    pub y: C::BaseField,
+    pub z: C::BaseField,
+}
+
+impl<C: EdwardsConfig> EdwardsAffine<C> {
+    /// Generate a point from its coordinates, `None` if it is not on the
+    /// curve
+    pub fn new(x: C::BaseField, y: C::BaseField) -> Option<Self> {
+        let point = Self { x, y };
+        point.is_on_curve().then_some(point)
+    }
+
+    /// Checks whether `a x^2 + y^2 = 1 + d x^2 y^2`
+    pub fn is_on_curve(&self) -> bool {
+        let (xx, yy) = (self.x.square(), self.y.square());
+        C::A * xx + yy == C::BaseField::one() + C::D * xx * yy
+    }
+
+    pub fn to_projective(&self) -> EdwardsProjective<C> {
+        EdwardsProjective {
+            x: self.x,
+            y: self.y,
+            z: C::BaseField::one(),
+        }
+    }
+}
+
+impl<C: EdwardsConfig> Group for EdwardsAffine<C> {
+    fn identity() -> Self {
+        Self {
+            x: C::BaseField::zero(),
+            y: C::BaseField::one(),
+        }
+    }
+
+    fn generator() -> Self {
+        Self {
+            x: C::GENERATOR.0,
+            y: C::GENERATOR.1,
+        }
+    }
+}
+
+impl<C: EdwardsConfig> Add for EdwardsAffine<C> {
+    type Output = Self;
+
+    /// `x3 = (x1 y2 + y1 x2) / (1 + d x1 x2 y1 y2)` and
+    /// `y3 = (y1 y2 - a x1 x2) / (1 - d x1 x2 y1 y2)`
+    fn add(self, rhs: Self) -> Self {
+        let t = C::D * self.x * rhs.x * self.y * rhs.y;
+        let one = C::BaseField::one();
+        Self {
+            x: (self.x * rhs.y + self.y * rhs.x) / (one + t),
+            y: (self.y * rhs.y - C::A * self.x * rhs.x) / (one - t),
+        }
+    }
+}
+
+impl<C: EdwardsConfig> Neg for EdwardsAffine<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: self.y,
+        }
+    }
+}
+
+impl<C: EdwardsConfig> Sub for EdwardsAffine<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl<C: EdwardsConfig> EdwardsProjective<C> {
+    pub fn to_affine(&self) -> EdwardsAffine<C> {
+        let z_inv = self
+            .z
+            .inverse()
+            .expect("projective Edwards points have Z != 0");
+        EdwardsAffine {
+            x: self.x * z_inv,
+            y: self.y * z_inv,
+        }
+    }
+}
+
+impl<C: EdwardsConfig> PartialEq for EdwardsProjective<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x * other.z == other.x * self.z && self.y * other.z == other.y * self.z
+    }
+}
+
+impl<C: EdwardsConfig> Eq for EdwardsProjective<C> {}
+
+impl<C: EdwardsConfig> Group for EdwardsProjective<C> {
+    fn identity() -> Self {
+        EdwardsAffine::<C>::identity().to_projective()
+    }
+
+    fn generator() -> Self {
+        EdwardsAffine::<C>::generator().to_projective()
+    }
+
+    /// `dbl-2008-bbjlp` from the Explicit-Formulas Database
+    fn double(&self) -> Self {
+        let b = (self.x + self.y).square();
+        let c = self.x.square();
+        let d = self.y.square();
+        let e = C::A * c;
+        let f = e + d;
+        let h = self.z.square();
+        let j = f - h - h;
+        Self {
+            x: (b - c - d) * j,
+            y: f * (e - d),
+            z: f * j,
+        }
+    }
+}
+
+impl<C: EdwardsConfig> Add for EdwardsProjective<C> {
+    type Output = Self;
+
+    /// `add-2008-bbjlp` from the Explicit-Formulas Database
+    fn add(self, rhs: Self) -> Self {
+        let a = self.z * rhs.z;
+        let b = a.square();
+        let c = self.x * rhs.x;
+        let d = self.y * rhs.y;
+        let e = C::D * c * d;
+        let f = b - e;
+        let g = b + e;
+        Self {
+            x: a * f * ((self.x + self.y) * (rhs.x + rhs.y) - c - d),
+            y: a * g * (d - C::A * c),
+            z: f * g,
+        }
+    }
+}
+
+impl<C: EdwardsConfig> Neg for EdwardsProjective<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self { x: -self.x, ..self }
+    }
+}
+
+impl<C: EdwardsConfig> Sub for EdwardsProjective<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + (-rhs)
+    }
+}
+
+impl<C: EdwardsConfig> PrimeOrderGroup for EdwardsAffine<C> {
+    type ScalarField = C::ScalarField;
+
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64> {
+        C::scalar_to_limbs(scalar)
+    }
+}
+
+impl<C: EdwardsConfig> PrimeOrderGroup for EdwardsProjective<C> {
+    type ScalarField = C::ScalarField;
+
+    fn scalar_to_limbs(scalar: &Self::ScalarField) -> Vec<u64> {
+        C::scalar_to_limbs(scalar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+    use crate::toy::ToyEdwards101;
+
+    type F101 = PrimeField<101>;
+    type Point = EdwardsAffine<ToyEdwards101>;
+
+    fn all_points() -> Vec<Point> {
+        (0..101)
+            .flat_map(|x| (0..101).map(move |y| (x, y)))
+            .filter_map(|(x, y)| Point::new(F101::new(x), F101::new(y)))
+            .collect()
+    }
+
+    #[test]
+    fn curve_has_92_points() {
+        let points = all_points();
+        assert_eq!(points.len() as u64, 23 * ToyEdwards101::COFACTOR);
+
+        // The addition law is complete: no exceptional pairs of points
+        for p in points.iter().step_by(7) {
+            for q in points.iter().step_by(5) {
+                assert!((*p + *q).is_on_curve());
+            }
+        }
+    }
+
+    #[test]
+    fn generator_has_order_23() {
+        let generator = Point::generator();
+        assert!(generator.is_on_curve());
+        assert_eq!(generator.mul_bigint([23]), Point::identity());
+        assert_ne!(generator.mul_bigint([1]), Point::identity());
+
+        // (0, -1) is the point of order two
+        let two_torsion = Point::new(F101::new(0), -F101::new(1)).unwrap();
+        assert_eq!(two_torsion.double(), Point::identity());
+    }
+
+    #[test]
+    fn projective_agrees_with_affine() {
+        let affine = Point::generator();
+        let projective = EdwardsProjective::<ToyEdwards101>::generator();
+        let mut expected = Point::identity();
+        for k in 0..30u64 {
+            assert_eq!(projective.mul_bigint([k]).to_affine(), expected);
+            assert_eq!(affine.mul_bigint([k]), expected);
+            expected = expected + affine;
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! A minimal Fiat–Shamir transcript. The verifier's random challenges are
+//! replaced by hashes of everything the prover has sent so far, turning a
+//! public-coin interactive protocol into a non-interactive one.
+//!
+//! Soundness against a prover that chooses the statement itself needs the
+//! challenges to depend on the statement too, not only on the prover's
+//! messages. Leaving it out gives the "weak" Fiat–Shamir transform, which
+//! breaks the soundness of many deployed proof systems (Dao, Miller, Wright
+//! and Grubbs, eprint 2023/691). [`Binding::Weak`] exists to demonstrate
+//! exactly that.
+
+use elliptic_curves::Encoding;
+use finite_fields::Field;
+use polynomial::Polynomial;
+use sha2::{Digest, Sha256};
+
+/// What the challenges of a transcript are bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Binding {
+    /// The statement and all prover messages
+    Strong,
+    /// The prover messages only. Statements are silently dropped, so that a
+    /// prover can adapt the statement to the challenges. Insecure!
+    Weak,
+}
+
+/// Running SHA-256 hash over labelled prover messages
+#[derive(Clone)]
+pub struct Transcript {
+    hasher: Sha256,
+    binding: Binding,
+}
+
+impl Transcript {
+    /// Generate a strongly binding transcript for a protocol label
+    pub fn new(label: &[u8]) -> Self {
+        Self::with_binding(label, Binding::Strong)
+    }
+
+    pub fn with_binding(label: &[u8], binding: Binding) -> Self {
+        let mut transcript = Self {
+            hasher: Sha256::new(),
+            binding,
+        };
+        transcript.append_message(b"protocol", label);
+        transcript
+    }
+
+    pub fn binding(&self) -> Binding {
+        self.binding
+    }
+
+    /// Absorbs part of the public statement, such as a public key or the
+    /// commitment whose opening is proven. Weakly binding transcripts drop
+    /// it.
+    pub fn append_statement(&mut self, label: &[u8], statement: &[u8]) {
+        if self.binding == Binding::Strong {
+            self.append_message(label, statement);
+        }
+    }
+
+    /// Absorbs a message. Both label and message are length-prefixed so
+    /// that different sequences of messages never hash alike.
+    pub fn append_message(&mut self, label: &[u8], message: &[u8]) {
+        for part in [label, message] {
+            self.hasher.update((part.len() as u64).to_le_bytes());
+            self.hasher.update(part);
+        }
+    }
+
+    pub fn append_field<F: Field>(&mut self, label: &[u8], element: &F) {
+        let bytes: Vec<u8> = element
+            .to_limbs()
+            .iter()
+            .flat_map(|limb| limb.to_le_bytes())
+            .collect();
+        self.append_message(label, &bytes);
+    }
+
+    /// Absorbs a group element in its canonical encoding
+    pub fn append_point<G: Encoding>(&mut self, label: &[u8], point: &G) {
+        self.append_message(label, &point.to_bytes());
+    }
+
+    /// Absorbs the coefficients of a polynomial up to its leading one
+    pub fn append_polynomial<F: Field>(&mut self, label: &[u8], polynomial: &Polynomial<F>) {
+        let coeffs = polynomial.get_raw_coefficients();
+        let len = polynomial.leading_degree().map_or(0, |degree| degree + 1);
+        self.append_message(label, &(len as u64).to_le_bytes());
+        for coeff in &coeffs[..len] {
+            self.append_field(b"coefficient", coeff);
+        }
+    }
+
+    /// Squeezes a challenge out of everything absorbed so far. The digest is
+    /// absorbed back, so consecutive challenges differ.
+    pub fn challenge<F: Field>(&mut self, label: &[u8]) -> F {
+        F::from_le_bytes_mod_order(&self.challenge_bytes(label))
+    }
+
+    /// Squeezes 32 uniform bytes, e.g. to sample indices rather than field
+    /// elements
+    pub fn challenge_bytes(&mut self, label: &[u8]) -> [u8; 32] {
+        self.append_message(b"challenge", label);
+        let digest = self.hasher.clone().finalize();
+        self.hasher.update(digest);
+        digest.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+
+    type Fq = PrimeField<41>;
+
+    #[test]
+    fn challenges_depend_on_messages() {
+        let mut first = Transcript::new(b"test");
+        let mut second = Transcript::new(b"test");
+        first.append_field(b"x", &Fq::from(1u64));
+        second.append_field(b"x", &Fq::from(2u64));
+
+        let mut same = first.clone();
+        let challenge: Fq = first.challenge(b"r");
+        assert_eq!(challenge, same.challenge(b"r"));
+
+        // Over a field of 41 elements, a few of the squeezed challenges
+        // must differ between transcripts
+        let firsts: Vec<Fq> = (0..8).map(|_| first.challenge(b"r")).collect();
+        let seconds: Vec<Fq> = (0..8).map(|_| second.challenge(b"r")).collect();
+        assert_ne!(firsts, seconds);
+    }
+
+    #[test]
+    fn weak_transcripts_ignore_the_statement() {
+        let challenge = |binding, statement: &[u8]| -> Fq {
+            let mut transcript = Transcript::with_binding(b"test", binding);
+            transcript.append_statement(b"statement", statement);
+            transcript.append_message(b"message", b"prover message");
+            transcript.challenge(b"r")
+        };
+        let (strong, weak) = (Binding::Strong, Binding::Weak);
+        assert_eq!(challenge(weak, b"one"), challenge(weak, b"another"));
+        assert_eq!(challenge(strong, b"one"), challenge(strong, b"one"));
+
+        // Challenges are 1 in 41 to collide, so try a few statements
+        let strongs: Vec<Fq> = (0u8..8).map(|i| challenge(strong, &[i])).collect();
+        assert!(strongs.iter().any(|c| *c != strongs[0]));
+    }
+}
@@ -0,0 +1,27 @@
+use ark_ff::{BigInt, Field as ArkField, Fp, FpConfig, PrimeField as ArkPrimeField};
+
+use crate::Field;
+
+/// Every prime field of `ark-ff`, e.g. the `Fq` of the zero-test IOP or
+/// the base and scalar fields of BLS12-381
+impl<P: FpConfig<N>, const N: usize> Field for Fp<P, N> {
+    fn characteristic() -> Vec<u64> {
+        P::MODULUS.0.to_vec()
+    }
+
+    fn to_limbs(&self) -> Vec<u64> {
+        self.into_bigint().0.to_vec()
+    }
+
+    fn from_limbs(limbs: &[u64]) -> Option<Self> {
+        Self::from_bigint(BigInt(limbs.try_into().ok()?))
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        ArkField::inverse(self)
+    }
+
+    fn frobenius_map(&self, _power: usize) -> Self {
+        *self
+    }
+}
@@ -0,0 +1,142 @@
+use halo2_proofs::pasta::group::ff::PrimeField as Halo2PrimeField;
+use num_traits::{One, Zero};
+
+use super::forward_field_ops;
+use crate::Field;
+
+/// A prime field of `halo2_proofs`, such as the pasta fields `Fp` and `Fq`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Halo2Field<F>(pub F);
+
+forward_field_ops!(Halo2Field, Halo2PrimeField);
+
+impl<F: Halo2PrimeField> Field for Halo2Field<F> {
+    /// Parsed from the big-endian hexadecimal `MODULUS` of `ff`
+    fn characteristic() -> Vec<u64> {
+        let digits = F::MODULUS.trim_start_matches("0x");
+        let mut limbs: Vec<u64> = digits
+            .as_bytes()
+            .rchunks(16)
+            .map(|chunk| {
+                let chunk = std::str::from_utf8(chunk).expect("hexadecimal digits are ASCII");
+                u64::from_str_radix(chunk, 16).expect("modulus is hexadecimal")
+            })
+            .collect();
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        limbs
+    }
+
+    /// From the little-endian byte representation of `ff`
+    fn to_limbs(&self) -> Vec<u64> {
+        self.0
+            .to_repr()
+            .as_ref()
+            .chunks(8)
+            .map(|chunk| {
+                let mut bytes = [0u8; 8];
+                bytes[..chunk.len()].copy_from_slice(chunk);
+                u64::from_le_bytes(bytes)
+            })
+            .collect()
+    }
+
+    fn from_limbs(limbs: &[u64]) -> Option<Self> {
+        let mut repr = F::Repr::default();
+        let bytes: Vec<u8> = limbs.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+        if bytes.len() != repr.as_ref().len().next_multiple_of(8) {
+            return None;
+        }
+        let len = repr.as_ref().len();
+        if bytes[len..].iter().any(|byte| *byte != 0) {
+            return None;
+        }
+        repr.as_mut().copy_from_slice(&bytes[..len]);
+        Option::from(F::from_repr(repr)).map(Self)
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        Option::from(self.0.invert()).map(Self)
+    }
+
+    fn frobenius_map(&self, _power: usize) -> Self {
+        *self
+    }
+}
+
+impl<F: Halo2PrimeField> Zero for Halo2Field<F> {
+    fn zero() -> Self {
+        Self(F::ZERO)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero_vartime()
+    }
+}
+
+impl<F: Halo2PrimeField> One for Halo2Field<F> {
+    fn one() -> Self {
+        Self(F::ONE)
+    }
+}
+
+impl<F: Halo2PrimeField> From<u64> for Halo2Field<F> {
+    fn from(value: u64) -> Self {
+        Self(F::from(value))
+    }
+}
+
+impl<F: Halo2PrimeField> From<u32> for Halo2Field<F> {
+    fn from(value: u32) -> Self {
+        Self(F::from(value as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use halo2_proofs::pasta::{Fp, Fq};
+
+    use super::*;
+
+    #[test]
+    fn pasta_moduli() {
+        // p = 2^254 + 45560315531419706090280762371685220353
+        assert_eq!(
+            Halo2Field::<Fp>::characteristic(),
+            vec![
+                0x992d30ed00000001,
+                0x224698fc094cf91b,
+                0x0000000000000000,
+                0x4000000000000000
+            ]
+        );
+        assert_eq!(Halo2Field::<Fq>::characteristic()[0], 0x8c46eb2100000001);
+    }
+
+    #[test]
+    fn pasta_arithmetic() {
+        let x = Halo2Field::<Fp>::from(12345u64);
+        let y = Halo2Field::<Fp>::from(678u64);
+        assert_eq!(x * y / y, x);
+        assert_eq!(x - x, Halo2Field::zero());
+        assert_eq!(x.pow([3]), x * x * x);
+        assert!(Halo2Field::<Fp>::zero().inverse().is_none());
+
+        // Fermat: x^(p - 1) = 1
+        let mut exponent = Halo2Field::<Fp>::characteristic();
+        exponent[0] -= 1;
+        assert_eq!(x.pow(exponent), Halo2Field::one());
+
+        assert_eq!((x * y).to_limbs(), vec![12345 * 678, 0, 0, 0]);
+        assert_eq!(Halo2Field::<Fp>::from_limbs(&x.to_limbs()), Some(x));
+        assert_eq!(
+            Halo2Field::<Fp>::from_limbs(&Halo2Field::<Fp>::characteristic()),
+            None
+        );
+        assert_eq!(
+            Halo2Field::<Fp>::from_le_bytes_mod_order(&(12345u64 * 678).to_le_bytes()),
+            x * y
+        );
+    }
+}
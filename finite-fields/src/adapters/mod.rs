@@ -0,0 +1,90 @@
+//! Implementations of [`Field`](crate::Field) for field types of other
+//! libraries, so that generic code in this workspace can run over them as
+//! well.
+//!
+//! `ark-ff` prime fields implement the trait directly. The fields of
+//! `halo2_proofs` and `plonky2` lack `Div` and the `num_traits` identities
+//! that [`Field`](crate::Field) builds on, so they are wrapped in
+//! newtypes instead.
+
+mod ark;
+mod halo2;
+#[cfg(feature = "plonky2")]
+mod plonky2;
+
+pub use halo2::Halo2Field;
+#[cfg(feature = "plonky2")]
+pub use plonky2::Plonky2Field;
+
+/// Forwards the arithmetic operators of a newtype `$wrapper<F>(F)` to the
+/// wrapped field, with division through [`Field::inverse`](crate::Field)
+macro_rules! forward_field_ops {
+    ($wrapper:ident, $bound:path) => {
+        impl<F: $bound> std::ops::Add for $wrapper<F> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl<F: $bound> std::ops::Sub for $wrapper<F> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl<F: $bound> std::ops::Mul for $wrapper<F> {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self {
+                Self(self.0 * rhs.0)
+            }
+        }
+
+        impl<F: $bound> std::ops::Div for $wrapper<F> {
+            type Output = Self;
+
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn div(self, rhs: Self) -> Self {
+                self * crate::Field::inverse(&rhs).expect("division by zero")
+            }
+        }
+
+        impl<F: $bound> std::ops::Neg for $wrapper<F> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl<F: $bound> std::ops::AddAssign for $wrapper<F> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<F: $bound> std::ops::SubAssign for $wrapper<F> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<F: $bound> std::ops::MulAssign for $wrapper<F> {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<F: $bound> std::ops::DivAssign for $wrapper<F> {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+    };
+}
+
+use forward_field_ops;
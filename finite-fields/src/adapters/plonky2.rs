@@ -0,0 +1,106 @@
+use num_traits::{One, Zero};
+use plonky2::field::{
+    goldilocks_field::GoldilocksField,
+    types::{Field as _, Field64, PrimeField64},
+};
+
+use super::forward_field_ops;
+use crate::{Field, PrimeField};
+
+/// A 64-bit prime field of `plonky2`, such as Goldilocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Plonky2Field<F>(pub F);
+
+forward_field_ops!(Plonky2Field, PrimeField64);
+
+impl<F: PrimeField64> Field for Plonky2Field<F> {
+    fn characteristic() -> Vec<u64> {
+        vec![F::ORDER]
+    }
+
+    fn to_limbs(&self) -> Vec<u64> {
+        vec![self.0.to_canonical_u64()]
+    }
+
+    fn from_limbs(limbs: &[u64]) -> Option<Self> {
+        match limbs {
+            [value] if *value < F::ORDER => Some(Self(F::from_canonical_u64(*value))),
+            _ => None,
+        }
+    }
+
+    fn inverse(&self) -> Option<Self> {
+        self.0.try_inverse().map(Self)
+    }
+
+    fn frobenius_map(&self, _power: usize) -> Self {
+        *self
+    }
+}
+
+impl<F: PrimeField64> Zero for Plonky2Field<F> {
+    fn zero() -> Self {
+        Self(F::ZERO)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl<F: PrimeField64> One for Plonky2Field<F> {
+    fn one() -> Self {
+        Self(F::ONE)
+    }
+}
+
+impl<F: PrimeField64> From<u64> for Plonky2Field<F> {
+    fn from(value: u64) -> Self {
+        Self(F::from_noncanonical_u64(value))
+    }
+}
+
+impl<F: PrimeField64> From<u32> for Plonky2Field<F> {
+    fn from(value: u32) -> Self {
+        Self(F::from_canonical_u32(value))
+    }
+}
+
+const GOLDILOCKS: u64 = GoldilocksField::ORDER;
+
+impl From<PrimeField<GOLDILOCKS>> for Plonky2Field<GoldilocksField> {
+    fn from(value: PrimeField<GOLDILOCKS>) -> Self {
+        Self(GoldilocksField::from_canonical_u64(value.value()))
+    }
+}
+
+impl From<Plonky2Field<GoldilocksField>> for PrimeField<GOLDILOCKS> {
+    fn from(value: Plonky2Field<GoldilocksField>) -> Self {
+        PrimeField::new(value.0.to_canonical_u64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngCore, SeedableRng};
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+
+    #[test]
+    fn goldilocks_matches_self_written_field() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        for _ in 0..100 {
+            let (a, b) = (
+                PrimeField::<GOLDILOCKS>::new(rng.next_u64()),
+                PrimeField::<GOLDILOCKS>::new(rng.next_u64()),
+            );
+            let (x, y) = (Plonky2Field::from(a), Plonky2Field::from(b));
+            assert_eq!(PrimeField::from(x * y), a * b);
+            assert_eq!(PrimeField::from(x - y), a - b);
+            assert_eq!(PrimeField::from(x / y), a / b);
+            assert_eq!(PrimeField::from(x.pow([12345])), a.pow([12345]));
+            assert_eq!(Plonky2Field::from_limbs(&x.to_limbs()), Some(x));
+        }
+    }
+}
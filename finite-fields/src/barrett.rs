@@ -0,0 +1,94 @@
+//! Barrett reduction for moduli `p < 2^64`.
+//!
+//! With the precomputed `mu = floor(2^128 / p)`, the quotient of any
+//! `x < 2^128` by `p` is approximated as `q = floor(x * mu / 2^128)`,
+//! which undershoots the true quotient by at most two. Hence `x - q * p`
+//! needs at most two conditional subtractions to land in `[0, p)`.
+
+/// Precomputed constants for reducing modulo a fixed `modulus`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrettReducer {
+    modulus: u64,
+    mu: u128,
+}
+
+impl BarrettReducer {
+    /// Generate a reducer for `modulus`. The modulus must be larger than
+    /// one and not a power of two, which holds for all odd primes.
+    pub const fn new(modulus: u64) -> Self {
+        assert!(modulus > 1 && !modulus.is_power_of_two());
+        Self {
+            modulus,
+            // `2^128 / p` does not fit a u128, but for `p` not a power of
+            // two it agrees with `(2^128 - 1) / p`
+            mu: u128::MAX / modulus as u128,
+        }
+    }
+
+    /// The modulus this reducer works with
+    pub const fn modulus(&self) -> u64 {
+        self.modulus
+    }
+
+    /// Reduces `x` into `[0, modulus)`
+    pub const fn reduce(&self, x: u128) -> u64 {
+        let q = mul_hi(x, self.mu);
+        let modulus = self.modulus as u128;
+        let mut r = x - q * modulus;
+        while r >= modulus {
+            r -= modulus;
+        }
+        r as u64
+    }
+
+    /// Returns `a * b mod modulus`
+    pub const fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+}
+
+/// High 128 bits of the 256-bit product `a * b`, assembled from four
+/// 64x64 bit partial products
+const fn mul_hi(a: u128, b: u128) -> u128 {
+    let (a0, a1) = (a as u64 as u128, a >> 64);
+    let (b0, b1) = (b as u64 as u128, b >> 64);
+
+    let a0b0 = a0 * b0;
+    let a0b1 = a0 * b1;
+    let a1b0 = a1 * b0;
+    let a1b1 = a1 * b1;
+
+    let mid = (a0b0 >> 64) + (a0b1 as u64 as u128) + (a1b0 as u64 as u128);
+    a1b1 + (a0b1 >> 64) + (a1b0 >> 64) + (mid >> 64)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngCore, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn mul_hi_matches_schoolbook() {
+        assert_eq!(mul_hi(u128::MAX, u128::MAX), u128::MAX - 1);
+        assert_eq!(mul_hi(1 << 64, 1 << 64), 1);
+        assert_eq!(mul_hi(1 << 127, 2), 1);
+        assert_eq!(mul_hi(12345, 67890), 0);
+    }
+
+    #[test]
+    fn reduce_matches_remainder() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for modulus in [41u64, 99679, 0xffff_ffff_0000_0001, u64::MAX] {
+            let reducer = BarrettReducer::new(modulus);
+            for _ in 0..1000 {
+                let x = ((rng.next_u64() as u128) << 64) | rng.next_u64() as u128;
+                assert_eq!(reducer.reduce(x), (x % modulus as u128) as u64);
+            }
+            assert_eq!(
+                reducer.reduce(u128::MAX),
+                (u128::MAX % modulus as u128) as u64
+            );
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! The BLS12-381 `Fq12` tower rebuilt out of [`ExtensionField`] on top of
+//! `ark_bls12_381::Fq`. The same non-residues as `ark-bls12-381` are used,
+//! so that elements of both correspond coefficient by coefficient:
+//! - `Fq2 = Fq[u] / (u^2 + 1)`
+//! - `Fq6 = Fq2[v] / (v^3 - (u + 1))`
+//! - `Fq12 = Fq6[w] / (w^2 - v)`
+
+use ark_bls12_381::{Fq, Fq12Config, Fq2Config, Fq6Config};
+use ark_ff::{Fp12Config, Fp2Config, Fp6Config};
+
+use crate::{Extendable, ExtensionField};
+
+pub type Fq2 = ExtensionField<Fq, 2>;
+pub type Fq6 = ExtensionField<Fq2, 3>;
+pub type Fq12 = ExtensionField<Fq6, 2>;
+
+impl Extendable<2> for Fq {
+    const NON_RESIDUE: Self = Fq2Config::NONRESIDUE;
+}
+
+impl Extendable<3> for Fq2 {
+    const NON_RESIDUE: Self = {
+        let non_residue = Fq6Config::NONRESIDUE;
+        ExtensionField::new([non_residue.c0, non_residue.c1])
+    };
+}
+
+impl Extendable<2> for Fq6 {
+    const NON_RESIDUE: Self = {
+        let non_residue = Fq12Config::NONRESIDUE;
+        ExtensionField::new([
+            ExtensionField::new([non_residue.c0.c0, non_residue.c0.c1]),
+            ExtensionField::new([non_residue.c1.c0, non_residue.c1.c1]),
+            ExtensionField::new([non_residue.c2.c0, non_residue.c2.c1]),
+        ])
+    };
+}
+
+impl From<ark_bls12_381::Fq2> for Fq2 {
+    fn from(value: ark_bls12_381::Fq2) -> Self {
+        ExtensionField::new([value.c0, value.c1])
+    }
+}
+
+impl From<ark_bls12_381::Fq6> for Fq6 {
+    fn from(value: ark_bls12_381::Fq6) -> Self {
+        ExtensionField::new([value.c0.into(), value.c1.into(), value.c2.into()])
+    }
+}
+
+impl From<ark_bls12_381::Fq12> for Fq12 {
+    fn from(value: ark_bls12_381::Fq12) -> Self {
+        ExtensionField::new([value.c0.into(), value.c1.into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::{Field as ArkField, UniformRand};
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::Field;
+
+    #[test]
+    fn non_residues_match_ark() {
+        let u = Fq2::adjoined_root();
+        assert_eq!(u * u, -Fq2::from_base(Fq::from(1)));
+        let v = Fq6::adjoined_root();
+        assert_eq!(
+            v * v * v,
+            Fq6::from_base(ark_bls12_381::Fq2::new(1.into(), 1.into()).into())
+        );
+        let w = Fq12::adjoined_root();
+        assert_eq!(w * w, Fq12::from_base(v));
+    }
+
+    #[test]
+    fn tower_arithmetic_matches_ark() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..2 {
+            let (a, b) = (
+                ark_bls12_381::Fq12::rand(&mut rng),
+                ark_bls12_381::Fq12::rand(&mut rng),
+            );
+            let (x, y) = (Fq12::from(a), Fq12::from(b));
+
+            assert_eq!(x + y, (a + b).into());
+            assert_eq!(x * y, (a * b).into());
+            assert_eq!(Field::inverse(&x), ArkField::inverse(&a).map(Into::into));
+            for power in [1, 2, 6] {
+                assert_eq!(
+                    Field::frobenius_map(&x, power),
+                    ArkField::frobenius_map(&a, power).into()
+                );
+            }
+            // `ark` provides norms relative to the next field down the tower
+            assert_eq!(x.norm(), a.norm().into());
+            assert_eq!(x.coefficients()[0].norm(), a.c0.norm().into());
+        }
+    }
+}
@@ -0,0 +1,431 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use num_traits::{One, Zero};
+use polynomial::Polynomial;
+
+use crate::{prime_field::pow_mod, Field, PrimeField};
+
+/// A field that admits the binomial extension `F[x] / (x^D - NON_RESIDUE)`
+pub trait Extendable<const D: usize>: Field {
+    /// An element `β` for which `x^D - β` is irreducible over the field
+    const NON_RESIDUE: Self;
+}
+
+/// The degree `D` extension `F[x] / (x^D - β)` of the field `F`, where
+/// `β = F::NON_RESIDUE`. An element is held as its `D` coefficients
+/// `c_0 + c_1 * x + ... + c_{D-1} * x^{D-1}` over `F`.
+///
+/// Multiplication and inversion work on the coefficients as a
+/// `polynomial::Polynomial<F>`, reducing modulo the irreducible
+/// polynomial with polynomial division. Since `ExtensionField` is a
+/// `Field` itself, extensions can be stacked into towers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExtensionField<F, const D: usize> {
+    coeffs: [F; D],
+}
+
+impl<F, const D: usize> ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    /// Generate an element from its coefficients `c_0`, `c_1`, ... `c_{D-1}`
+    pub const fn new(coeffs: [F; D]) -> Self {
+        Self { coeffs }
+    }
+
+    /// Embeds an element of the base field
+    pub fn from_base(value: F) -> Self {
+        let mut coeffs = [F::zero(); D];
+        coeffs[0] = value;
+        Self { coeffs }
+    }
+
+    /// The adjoined root `x` of the irreducible polynomial
+    pub fn adjoined_root() -> Self {
+        let mut coeffs = [F::zero(); D];
+        coeffs[1 % D] = F::one();
+        Self { coeffs }
+    }
+
+    /// Get the coefficients over the base field
+    pub fn coefficients(&self) -> [F; D] {
+        self.coeffs
+    }
+
+    /// The irreducible polynomial `x^D - β` defining the extension
+    pub fn modulus() -> Polynomial<F> {
+        let mut coeffs = vec![F::zero(); D + 1];
+        coeffs[0] = -F::NON_RESIDUE;
+        coeffs[D] = F::one();
+        Polynomial::new_from_coeffs(&coeffs)
+    }
+
+    fn to_polynomial(self) -> Polynomial<F> {
+        Polynomial::new_from_coeffs(&self.coeffs)
+    }
+
+    /// Reduces an arbitrary polynomial over `F` modulo `x^D - β`
+    fn from_polynomial(polynomial: &Polynomial<F>) -> Self {
+        let (_, remainder) = polynomial.div_rem(&Self::modulus());
+        let remainder = remainder.get_raw_coefficients();
+        Self {
+            coeffs: std::array::from_fn(|idx| remainder.get(idx).copied().unwrap_or_else(F::zero)),
+        }
+    }
+
+    /// Applies the Frobenius of the base field, `a -> a^|F|`, `power`
+    /// times. It generates the Galois group of the extension over `F`.
+    pub fn relative_frobenius_map(&self, power: usize) -> Self {
+        self.frobenius_map(power * F::extension_degree())
+    }
+
+    /// The norm over the base field, the product of all Galois conjugates
+    /// `a * a^|F| * a^(|F|^2) * ... * a^(|F|^(D-1))`
+    pub fn norm(&self) -> F {
+        let norm = (1..D).fold(*self, |acc, power| acc * self.relative_frobenius_map(power));
+        debug_assert!(norm.coeffs[1..].iter().all(|c| c.is_zero()));
+        norm.coeffs[0]
+    }
+
+    /// The trace over the base field, the sum of all Galois conjugates
+    /// `a + a^|F| + a^(|F|^2) + ... + a^(|F|^(D-1))`
+    pub fn trace(&self) -> F {
+        let trace = (1..D).fold(*self, |acc, power| acc + self.relative_frobenius_map(power));
+        debug_assert!(trace.coeffs[1..].iter().all(|c| c.is_zero()));
+        trace.coeffs[0]
+    }
+}
+
+impl<F, const D: usize> Field for ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    fn characteristic() -> Vec<u64> {
+        F::characteristic()
+    }
+
+    fn extension_degree() -> usize {
+        F::extension_degree() * D
+    }
+
+    fn to_limbs(&self) -> Vec<u64> {
+        self.coeffs
+            .iter()
+            .flat_map(|coeff| coeff.to_limbs())
+            .collect()
+    }
+
+    fn from_limbs(limbs: &[u64]) -> Option<Self> {
+        let width = F::zero().to_limbs().len();
+        if limbs.len() != width * D {
+            return None;
+        }
+        let coeffs: Option<Vec<F>> = limbs.chunks(width).map(F::from_limbs).collect();
+        Some(Self::new(coeffs?.try_into().ok()?))
+    }
+
+    /// Inverse via the extended Euclidean algorithm on `a(x)` and the
+    /// irreducible `m(x)`: their gcd is a constant `g`, and the Bezout
+    /// relation `s(x) * m(x) + t(x) * a(x) = g` gives `a^(-1) = t / g`
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            return None;
+        }
+
+        let (mut r0, mut r1) = (Self::modulus(), self.to_polynomial());
+        let (mut t0, mut t1) = (
+            Polynomial::<F>::zero(),
+            Polynomial::new_from_coeffs(&[F::one()]),
+        );
+        while !r1.is_zero() {
+            let (quotient, remainder) = r0.div_rem(&r1);
+            let t = &t0 - &(&quotient * &t1);
+            (r0, r1) = (r1, remainder);
+            (t0, t1) = (t1, t);
+        }
+
+        debug_assert_eq!(r0.leading_degree(), Some(0), "modulus is reducible");
+        let gcd_inverse = r0.leading_coefficient()?.inverse()?;
+        Some(Self::from_polynomial(
+            &(&t0 * &Polynomial::new_from_coeffs(&[gcd_inverse])),
+        ))
+    }
+
+    /// Since Frobenius is a ring homomorphism fixing nothing but `F_p`,
+    /// `(sum c_i x^i)^p = sum c_i^p (x^p)^i`. With `p = qD + r` and
+    /// `x^D = β`, we get `x^p = β^q x^r` without exponentiating in the
+    /// extension.
+    fn frobenius_map(&self, power: usize) -> Self {
+        let (q, r) = div_limbs(&F::characteristic(), D as u64);
+        let mut x_p = Self::zero();
+        x_p.coeffs[r as usize] = F::NON_RESIDUE.pow(q);
+
+        (0..power).fold(*self, |acc, _| {
+            let mut result = Self::zero();
+            let mut x_p_power = Self::one();
+            for coeff in acc.coeffs {
+                result += Self::from_base(coeff.frobenius_map(1)) * x_p_power;
+                x_p_power *= x_p;
+            }
+            result
+        })
+    }
+}
+
+/// Divides a little-endian multi-limb integer by a small divisor,
+/// returning the quotient limbs and the remainder
+fn div_limbs(limbs: &[u64], divisor: u64) -> (Vec<u64>, u64) {
+    let mut quotient = vec![0u64; limbs.len()];
+    let mut remainder = 0u128;
+    for (idx, limb) in limbs.iter().enumerate().rev() {
+        let current = (remainder << 64) | *limb as u128;
+        quotient[idx] = (current / divisor as u128) as u64;
+        remainder = current % divisor as u128;
+    }
+    (quotient, remainder as u64)
+}
+
+/// Smallest `β` making `x^d - β` irreducible over `F_p`. By a classical
+/// result (Lidl & Niederreiter, Theorem 3.75) this is the case exactly
+/// when `β` is not an `r`-th power for any prime `r` dividing `d`, given
+/// that `d` divides `p - 1` and that `p = 1 mod 4` whenever `4 | d`.
+const fn binomial_non_residue(p: u64, d: u64) -> u64 {
+    assert!(d >= 2, "extension degree must be at least 2");
+    assert!(
+        (p - 1) % d == 0,
+        "binomial extensions need the degree to divide p - 1"
+    );
+    assert!(
+        d % 4 != 0 || p % 4 == 1,
+        "binomial extensions of degree divisible by 4 need p = 1 mod 4"
+    );
+
+    let mut beta = 2;
+    while beta < p {
+        let mut is_non_residue = true;
+        let (mut rest, mut r) = (d, 2);
+        while r <= rest {
+            if rest % r == 0 {
+                if pow_mod(beta, (p - 1) / r, p) == 1 {
+                    is_non_residue = false;
+                }
+                while rest % r == 0 {
+                    rest /= r;
+                }
+            }
+            r += 1;
+        }
+        if is_non_residue {
+            return beta;
+        }
+        beta += 1;
+    }
+    panic!("no binomial irreducible polynomial found")
+}
+
+impl<const P: u64, const D: usize> Extendable<D> for PrimeField<P> {
+    const NON_RESIDUE: Self = Self::new(binomial_non_residue(P, D as u64));
+}
+
+impl<F, const D: usize> Zero for ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    fn zero() -> Self {
+        Self {
+            coeffs: [F::zero(); D],
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.coeffs.iter().all(|c| c.is_zero())
+    }
+}
+
+impl<F, const D: usize> One for ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    fn one() -> Self {
+        Self::from_base(F::one())
+    }
+}
+
+impl<F, const D: usize> Add for ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            coeffs: std::array::from_fn(|idx| self.coeffs[idx] + rhs.coeffs[idx]),
+        }
+    }
+}
+
+impl<F, const D: usize> Sub for ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            coeffs: std::array::from_fn(|idx| self.coeffs[idx] - rhs.coeffs[idx]),
+        }
+    }
+}
+
+impl<F, const D: usize> Mul for ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::from_polynomial(&(self.to_polynomial() * rhs.to_polynomial()))
+    }
+}
+
+impl<F, const D: usize> Div for ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse().expect("division by zero")
+    }
+}
+
+impl<F, const D: usize> Neg for ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            coeffs: self.coeffs.map(|c| -c),
+        }
+    }
+}
+
+impl<F, const D: usize> AddAssign for ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<F, const D: usize> SubAssign for ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<F, const D: usize> MulAssign for ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<F, const D: usize> DivAssign for ExtensionField<F, D>
+where
+    F: Extendable<D>,
+{
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{RngCore, SeedableRng};
+
+    use super::*;
+
+    type F41 = PrimeField<41>;
+    type F41Quadratic = ExtensionField<F41, 2>;
+    type F41Quintic = ExtensionField<F41, 5>;
+
+    fn random<const D: usize>(rng: &mut impl RngCore) -> ExtensionField<F41, D>
+    where
+        F41: Extendable<D>,
+    {
+        ExtensionField::new(std::array::from_fn(|_| F41::new(rng.next_u64())))
+    }
+
+    #[test]
+    fn binomial_non_residues() {
+        // 3 is the smallest quadratic non-residue mod 41
+        assert_eq!(<F41 as Extendable<2>>::NON_RESIDUE.value(), 3);
+        assert_eq!(<F41 as Extendable<4>>::NON_RESIDUE.value(), 3);
+        // 2 has order 20, hence is not a fifth power either
+        assert_eq!(<F41 as Extendable<5>>::NON_RESIDUE.value(), 2);
+    }
+
+    #[test]
+    fn quadratic_extension_is_a_field() {
+        // Every non-zero element of F_{41^2} is invertible and has order
+        // dividing `41^2 - 1`
+        for a in 0..41u64 {
+            for b in 0..41u64 {
+                let x = F41Quadratic::new([F41::new(a), F41::new(b)]);
+                if x.is_zero() {
+                    assert_eq!(x.inverse(), None);
+                    continue;
+                }
+                assert_eq!(x * x.inverse().unwrap(), F41Quadratic::one());
+                assert_eq!(x.pow([41 * 41 - 1]), F41Quadratic::one());
+            }
+        }
+        let x = F41Quadratic::adjoined_root();
+        assert_eq!(x * x, F41Quadratic::from_base(F41::new(3)));
+        assert_eq!(
+            F41Quadratic::from_u64(44),
+            F41Quadratic::from_base(F41::new(3))
+        );
+    }
+
+    #[test]
+    fn frobenius_norm_and_trace() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..50 {
+            let (x, y) = (random::<5>(&mut rng), random::<5>(&mut rng));
+
+            assert_eq!(x.frobenius_map(1), x.pow([41]));
+            assert_eq!(x.frobenius_map(5), x);
+            assert_eq!(
+                x.frobenius_map(2) * y.frobenius_map(2),
+                (x * y).frobenius_map(2)
+            );
+
+            assert_eq!((x * y).norm(), x.norm() * y.norm());
+            assert_eq!((x + y).trace(), x.trace() + y.trace());
+            // The norm to the prime field is `x^((p^5 - 1) / (p - 1))`
+            let exponent = (41u64.pow(5) - 1) / 40;
+            assert_eq!(F41Quintic::from_base(x.norm()), x.pow([exponent]));
+            assert_eq!(F41Quintic::from_base(F41::new(7)).trace(), F41::new(35));
+            assert_eq!(F41Quintic::from_limbs(&x.to_limbs()), Some(x));
+        }
+        assert_eq!(F41Quintic::from_limbs(&[1, 2, 3, 4]), None);
+        assert_eq!(F41Quintic::from_limbs(&[1, 2, 3, 4, 41]), None);
+    }
+
+    #[test]
+    fn division_of_limbs() {
+        assert_eq!(div_limbs(&[41], 2), (vec![20], 1));
+        assert_eq!(div_limbs(&[0, 1], 3), (vec![0x5555_5555_5555_5555, 0], 1));
+    }
+}
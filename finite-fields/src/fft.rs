@@ -0,0 +1,91 @@
+//! Radix-2 FFTs over the subgroup `<ω>` of a field, for field elements and
+//! for anything a field element can scale, such as group elements.
+
+use std::ops::{Add, Sub};
+
+use crate::Field;
+
+/// Values `Σ_j ω^(ij) a_j` for `i < n`, where `ω` has order `n =
+/// values.len()` and `scale` multiplies an entry by a field element
+pub fn fft<T, F>(values: &[T], generator: F, scale: &impl Fn(&T, F) -> T) -> Vec<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T>,
+    F: Field,
+{
+    let n = values.len();
+    assert!(n.is_power_of_two(), "domain size must be a power of two");
+    if n == 1 {
+        return values.to_vec();
+    }
+    let even: Vec<T> = values.iter().step_by(2).copied().collect();
+    let odd: Vec<T> = values.iter().skip(1).step_by(2).copied().collect();
+    let (even, odd) = (
+        fft(&even, generator.square(), scale),
+        fft(&odd, generator.square(), scale),
+    );
+
+    let mut evals = even.clone();
+    evals.extend_from_slice(&even);
+    let mut power = F::one();
+    for i in 0..n / 2 {
+        let twiddled = scale(&odd[i], power);
+        evals[i] = even[i] + twiddled;
+        evals[i + n / 2] = even[i] - twiddled;
+        power = power * generator;
+    }
+    evals
+}
+
+/// The inverse of [`fft`]: `a_j = (1 / n) Σ_i ω^(-ij) v_i`
+pub fn ifft<T, F>(values: &[T], generator: F, scale: &impl Fn(&T, F) -> T) -> Vec<T>
+where
+    T: Copy + Add<Output = T> + Sub<Output = T>,
+    F: Field,
+{
+    let n_inverse = F::from_u64(values.len() as u64)
+        .inverse()
+        .expect("domain size is invertible");
+    let inverse = generator.inverse().expect("non-zero");
+    fft(values, inverse, scale)
+        .iter()
+        .map(|value| scale(value, n_inverse))
+        .collect()
+}
+
+/// Evaluations at the powers of `generator` of the polynomial with
+/// coefficients `values`
+pub fn field_fft<F: Field>(values: &[F], generator: F) -> Vec<F> {
+    fft(values, generator, &|value, scalar| *value * scalar)
+}
+
+/// Coefficients of the polynomial taking `values` at the powers of
+/// `generator`
+pub fn field_ifft<F: Field>(values: &[F], generator: F) -> Vec<F> {
+    ifft(values, generator, &|value, scalar| *value * scalar)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::{One, Zero};
+
+    use super::*;
+    use crate::PrimeField;
+
+    type F = PrimeField<97>;
+
+    #[test]
+    fn transforms_invert_each_other() {
+        // 5 generates the multiplicative group of order 96
+        let omega = F::from_u64(5).pow([12]);
+        assert_ne!(omega.pow([4]), F::one());
+        let values = [3u64, 1, 4, 1, 5, 9, 2, 6].map(F::from_u64);
+        let evals = field_fft(&values, omega);
+        for (i, eval) in evals.iter().enumerate() {
+            let expected = (0..8).fold(F::zero(), |acc, j| {
+                acc + values[j] * omega.pow([(i * j) as u64])
+            });
+            assert_eq!(*eval, expected);
+        }
+        assert_eq!(field_ifft(&evals, omega), values);
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    fmt::Debug,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use num_traits::{One, Zero};
+
+/// A finite field. Arithmetic is exposed through the usual operator
+/// traits, alongwith `Zero` and `One` from `num_traits` so that types
+/// implementing `Field` can be used directly as coefficients of a
+/// `polynomial::Polynomial`.
+pub trait Field:
+    Copy
+    + Debug
+    + Eq
+    + Zero
+    + One
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Characteristic `p` of the field as little-endian `u64` limbs
+    fn characteristic() -> Vec<u64>;
+
+    /// Degree of the field over its prime subfield `F_p`, so that the
+    /// field has `p^extension_degree()` elements
+    fn extension_degree() -> usize {
+        1
+    }
+
+    /// Embeds an integer into the field by double-and-add over `one`
+    fn from_u64(value: u64) -> Self {
+        (0..64).rev().fold(Self::zero(), |acc, bit| {
+            let acc = acc + acc;
+            if (value >> bit) & 1 == 1 {
+                acc + Self::one()
+            } else {
+                acc
+            }
+        })
+    }
+
+    /// Interprets `bytes` as a little-endian integer and reduces it into
+    /// the field, e.g. to turn a hash digest into a challenge
+    fn from_le_bytes_mod_order(bytes: &[u8]) -> Self {
+        let radix = Self::from_u64(256);
+        bytes.iter().rev().fold(Self::zero(), |acc, byte| {
+            acc * radix + Self::from_u64(*byte as u64)
+        })
+    }
+
+    /// Canonical representation of the element as little-endian `u64`
+    /// limbs. For extension fields, the limbs of the coefficients over the
+    /// base field are concatenated.
+    fn to_limbs(&self) -> Vec<u64>;
+
+    /// Inverse of `to_limbs`, `None` unless `limbs` is the canonical
+    /// representation of some element
+    fn from_limbs(limbs: &[u64]) -> Option<Self>;
+
+    /// Multiplicative inverse of the element, `None` for zero
+    fn inverse(&self) -> Option<Self>;
+
+    /// Returns `self * self`
+    fn square(&self) -> Self {
+        *self * *self
+    }
+
+    /// Raises the element to `exp` given as little-endian `u64` limbs
+    /// using square-and-multiply from the most significant bit down.
+    fn pow<S: AsRef<[u64]>>(&self, exp: S) -> Self {
+        let mut result = Self::one();
+        for limb in exp.as_ref().iter().rev() {
+            for bit in (0..64).rev() {
+                result = result.square();
+                if (limb >> bit) & 1 == 1 {
+                    result = result * *self;
+                }
+            }
+        }
+        result
+    }
+
+    /// Applies the Frobenius endomorphism `a -> a^p` `power` times. It is
+    /// the identity on the prime subfield.
+    fn frobenius_map(&self, power: usize) -> Self {
+        let characteristic = Self::characteristic();
+        (0..power).fold(*self, |acc, _| acc.pow(&characteristic))
+    }
+}
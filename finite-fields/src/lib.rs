@@ -0,0 +1,31 @@
+//! Finite fields written from scratch, without relying on `ark-ff`.
+//!
+//! [`PrimeField`] implements `F_p` for any odd prime `p < 2^64` chosen
+//! at compile time, e.g. `PrimeField<41>` mirrors the `Fq` used in the
+//! zero-test IOP and `PrimeField<99679>` the one in Feldman's VSS.
+//! [`ExtensionField`] builds `F[x] / (x^D - β)` over any [`Field`],
+//! including the `ark-ff` prime fields, which [`bls12_381`] uses to
+//! rebuild the BLS12-381 `Fq12` tower.
+//!
+//! [`Field`] is the field abstraction shared across the workspace. Besides
+//! the fields here, it is implemented for `ark-ff` prime fields and, via
+//! [`Halo2Field`] and `Plonky2Field` (behind the `plonky2` feature), for
+//! the fields of `halo2_proofs` and `plonky2`. [`fft`] has the radix-2
+//! FFTs over power of two subgroups that FRI and Caulk share.
+
+mod adapters;
+mod barrett;
+pub mod bls12_381;
+mod extension_field;
+pub mod fft;
+mod field;
+mod montgomery;
+mod prime_field;
+
+pub use adapters::Halo2Field;
+#[cfg(feature = "plonky2")]
+pub use adapters::Plonky2Field;
+pub use barrett::BarrettReducer;
+pub use extension_field::{Extendable, ExtensionField};
+pub use field::Field;
+pub use prime_field::{LegendreSymbol, PrimeField};
@@ -0,0 +1,76 @@
+//! Montgomery arithmetic for odd moduli `p < 2^64` with `R = 2^64`.
+//!
+//! An element `a` is held as `a * R mod p`. Multiplying two such values
+//! gives `a * b * R^2`, and a single Montgomery reduction (REDC) divides
+//! out one factor of `R` without ever dividing by `p`.
+
+/// Computes `-p^{-1} mod 2^64` for an odd `p`. Each Newton iteration
+/// `inv = inv * (2 - p * inv)` doubles the number of correct low bits,
+/// starting from one correct bit, hence six iterations.
+pub const fn neg_inverse(p: u64) -> u64 {
+    let mut inv = 1u64;
+    let mut i = 0;
+    while i < 6 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv.wrapping_neg()
+}
+
+/// `R mod p` where `R = 2^64`; this is the Montgomery form of `1`
+pub const fn r_mod(p: u64) -> u64 {
+    ((1u128 << 64) % p as u128) as u64
+}
+
+/// `R^2 mod p`, used to move canonical values into Montgomery form
+pub const fn r2_mod(p: u64) -> u64 {
+    let r = r_mod(p) as u128;
+    ((r * r) % p as u128) as u64
+}
+
+/// Montgomery reduction: given `t < p * R`, returns `t * R^{-1} mod p`.
+///
+/// We add `m * p` to `t` where `m = t * (-p^{-1}) mod R`, making the low
+/// 64 bits vanish so that the division by `R` is a plain shift. Since
+/// `p` may be as large as `2^64 - 1`, the 129-bit sum is never formed
+/// explicitly; the low halves always sum to either `0` or `2^64`.
+#[inline]
+pub const fn redc(t: u128, p: u64, neg_inv: u64) -> u64 {
+    let lo = t as u64;
+    let hi = (t >> 64) as u64;
+    let m = lo.wrapping_mul(neg_inv);
+    let mp = m as u128 * p as u128;
+    let carry = (lo != 0) as u128;
+    let reduced = hi as u128 + (mp >> 64) + carry;
+    if reduced >= p as u128 {
+        (reduced - p as u128) as u64
+    } else {
+        reduced as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GOLDILOCKS: u64 = 0xffff_ffff_0000_0001;
+
+    #[test]
+    fn neg_inverse_is_correct() {
+        for p in [41u64, 99679, GOLDILOCKS, u64::MAX] {
+            assert_eq!(p.wrapping_mul(neg_inverse(p)), u64::MAX);
+        }
+    }
+
+    #[test]
+    fn redc_round_trips() {
+        for p in [41u64, 99679, GOLDILOCKS] {
+            let inv = neg_inverse(p);
+            for a in [0u64, 1, 2, p / 2, p - 1] {
+                let mont = ((a as u128 * r_mod(p) as u128) % p as u128) as u64;
+                assert_eq!(redc(mont as u128, p, inv), a);
+                assert_eq!(redc(a as u128 * r2_mod(p) as u128, p, inv), mont);
+            }
+        }
+    }
+}
@@ -0,0 +1,571 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use num_traits::{One, Zero};
+
+use crate::{barrett::BarrettReducer, montgomery, Field};
+
+/// An element of the prime field `F_p` for an odd prime `P < 2^64`.
+///
+/// Internally the element is held in Montgomery form `a * 2^64 mod P`
+/// so that multiplication needs no division. Barrett reduction is used
+/// wherever a canonical (non-Montgomery) value needs reducing, such as
+/// construction from wide integers.
+///
+/// Primality of `P` is checked at compile time when the type is used.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PrimeField<const P: u64> {
+    mont: u64,
+}
+
+/// The Legendre symbol `(a / p)` of an element
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegendreSymbol {
+    Zero,
+    QuadraticResidue,
+    QuadraticNonResidue,
+}
+
+impl<const P: u64> PrimeField<P> {
+    /// The field modulus `P`
+    pub const MODULUS: u64 = P;
+
+    /// `-P^{-1} mod 2^64`, required by Montgomery reduction
+    const NEG_INV: u64 = montgomery::neg_inverse(P);
+
+    /// `2^128 mod P`, moves canonical values into Montgomery form. Also
+    /// performs the compile time check on `P`
+    const R2: u64 = {
+        assert!(P > 2 && is_prime(P), "modulus must be an odd prime");
+        montgomery::r2_mod(P)
+    };
+
+    /// Barrett reducer for canonical values modulo `P`
+    pub const BARRETT: BarrettReducer = BarrettReducer::new(P);
+
+    /// The additive identity
+    pub const ZERO: Self = Self { mont: 0 };
+
+    /// The multiplicative identity, `2^64 mod P` in Montgomery form
+    pub const ONE: Self = Self {
+        mont: montgomery::r_mod(P),
+    };
+
+    /// Generate a field element from any `u64`, reducing it modulo `P`
+    pub const fn new(value: u64) -> Self {
+        Self::from_canonical(value % P)
+    }
+
+    /// Generate a field element from any `u128` using Barrett reduction
+    pub const fn from_u128(value: u128) -> Self {
+        Self::from_canonical(Self::BARRETT.reduce(value))
+    }
+
+    /// Generate a field element from a value known to be in `[0, P)`
+    const fn from_canonical(value: u64) -> Self {
+        Self {
+            mont: montgomery::redc(value as u128 * Self::R2 as u128, P, Self::NEG_INV),
+        }
+    }
+
+    /// Get the canonical representative in `[0, P)`
+    pub const fn value(&self) -> u64 {
+        montgomery::redc(self.mont as u128, P, Self::NEG_INV)
+    }
+
+    /// Multiplies two elements by reducing the product of their canonical
+    /// representatives with Barrett reduction instead of Montgomery's.
+    /// Always agrees with `*`, and exists to compare the two techniques.
+    pub const fn mul_barrett(&self, rhs: &Self) -> Self {
+        Self::from_canonical(Self::BARRETT.mul(self.value(), rhs.value()))
+    }
+
+    /// Raises the element to a `u64` power using square-and-multiply
+    pub fn pow_u64(&self, mut exp: u64) -> Self {
+        let mut base = *self;
+        let mut result = Self::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Computes the Legendre symbol via Euler's criterion:
+    /// `a^((P - 1) / 2)` is `1` for non-zero squares and `-1` otherwise
+    pub fn legendre(&self) -> LegendreSymbol {
+        if self.is_zero() {
+            return LegendreSymbol::Zero;
+        }
+        if self.pow_u64((P - 1) / 2) == Self::ONE {
+            LegendreSymbol::QuadraticResidue
+        } else {
+            LegendreSymbol::QuadraticNonResidue
+        }
+    }
+
+    /// Computes a square root using the Tonelli-Shanks algorithm, returns
+    /// `None` if the element is a quadratic non-residue. The other root
+    /// is the negation of the returned one.
+    pub fn sqrt(&self) -> Option<Self> {
+        match self.legendre() {
+            LegendreSymbol::Zero => return Some(Self::ZERO),
+            LegendreSymbol::QuadraticNonResidue => return None,
+            LegendreSymbol::QuadraticResidue => {}
+        }
+
+        // Write `P - 1 = q * 2^s` with `q` odd
+        let s = (P - 1).trailing_zeros();
+        let q = (P - 1) >> s;
+
+        // Any non-residue `z` gives `c = z^q`, a generator of the 2-Sylow
+        // subgroup of order `2^s`
+        let z = (2..P)
+            .map(Self::new)
+            .find(|z| z.legendre() == LegendreSymbol::QuadraticNonResidue)
+            .expect("an odd prime field always has a non-residue");
+
+        let mut m = s;
+        let mut c = z.pow_u64(q);
+        let mut t = self.pow_u64(q);
+        let mut r = self.pow_u64((q + 1) / 2);
+
+        // Invariant: `r^2 = a * t`, and `t` has order dividing `2^(m-1)`.
+        // Each round strictly lowers the order of `t` until `t = 1`.
+        while t != Self::ONE {
+            let mut i = 0;
+            let mut t_pow = t;
+            while t_pow != Self::ONE {
+                t_pow = t_pow.square();
+                i += 1;
+            }
+
+            let b = c.pow_u64(1 << (m - i - 1));
+            m = i;
+            c = b.square();
+            t *= c;
+            r *= b;
+        }
+
+        Some(r)
+    }
+
+    /// Distinct prime factors of `P - 1` found by trial division. Cheap
+    /// for the toy and FFT-friendly moduli this crate targets, whose
+    /// `P - 1` is smooth.
+    pub fn group_order_factors() -> Vec<u64> {
+        let mut factors = vec![];
+        let mut n = P - 1;
+        let mut d = 2u64;
+        while d.saturating_mul(d) <= n {
+            if n % d == 0 {
+                factors.push(d);
+                while n % d == 0 {
+                    n /= d;
+                }
+            }
+            d += 1;
+        }
+        if n > 1 {
+            factors.push(n);
+        }
+        factors
+    }
+
+    /// Finds the smallest generator of the multiplicative group `F_p^*`.
+    /// An element `g` generates the group exactly when `g^((P - 1) / q)`
+    /// is not `1` for every prime `q` dividing `P - 1`.
+    pub fn primitive_root() -> Self {
+        let factors = Self::group_order_factors();
+        (2..P)
+            .map(Self::new)
+            .find(|g| factors.iter().all(|q| g.pow_u64((P - 1) / q) != Self::ONE))
+            .expect("multiplicative group of a prime field is cyclic")
+    }
+
+    /// Returns a primitive `order`-th root of unity, which exists exactly
+    /// when `order` divides `P - 1`
+    pub fn root_of_unity(order: u64) -> Option<Self> {
+        if order == 0 || (P - 1) % order != 0 {
+            return None;
+        }
+        Some(Self::primitive_root().pow_u64((P - 1) / order))
+    }
+}
+
+/// Deterministic Miller-Rabin, the first twelve primes as witnesses are
+/// sufficient for all `n < 2^64`
+const fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+    let mut i = 0;
+    while i < WITNESSES.len() {
+        if n % WITNESSES[i] == 0 {
+            return n == WITNESSES[i];
+        }
+        i += 1;
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    let mut i = 0;
+    while i < WITNESSES.len() {
+        let mut x = pow_mod(WITNESSES[i], d, n);
+        if x != 1 && x != n - 1 {
+            let mut r = 1;
+            while r < s && x != n - 1 {
+                x = mul_mod(x, x, n);
+                r += 1;
+            }
+            if x != n - 1 {
+                return false;
+            }
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn mul_mod(a: u64, b: u64, n: u64) -> u64 {
+    ((a as u128 * b as u128) % n as u128) as u64
+}
+
+pub(crate) const fn pow_mod(mut base: u64, mut exp: u64, n: u64) -> u64 {
+    let mut result = 1;
+    base %= n;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, n);
+        }
+        base = mul_mod(base, base, n);
+        exp >>= 1;
+    }
+    result
+}
+
+impl<const P: u64> Field for PrimeField<P> {
+    fn characteristic() -> Vec<u64> {
+        vec![P]
+    }
+
+    fn to_limbs(&self) -> Vec<u64> {
+        vec![self.value()]
+    }
+
+    fn from_limbs(limbs: &[u64]) -> Option<Self> {
+        match limbs {
+            [value] if *value < P => Some(Self::new(*value)),
+            _ => None,
+        }
+    }
+
+    /// Inverse by Fermat's little theorem, `a^(P - 2) = a^(-1)`
+    fn inverse(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self.pow_u64(P - 2))
+        }
+    }
+
+    fn frobenius_map(&self, _power: usize) -> Self {
+        *self
+    }
+}
+
+impl<const P: u64> Zero for PrimeField<P> {
+    fn zero() -> Self {
+        Self::ZERO
+    }
+
+    fn is_zero(&self) -> bool {
+        self.mont == 0
+    }
+}
+
+impl<const P: u64> One for PrimeField<P> {
+    fn one() -> Self {
+        Self::ONE
+    }
+}
+
+impl<const P: u64> From<u64> for PrimeField<P> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const P: u64> From<u32> for PrimeField<P> {
+    fn from(value: u32) -> Self {
+        Self::new(value as u64)
+    }
+}
+
+impl<const P: u64> Add for PrimeField<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        // Montgomery form is linear, so addition happens as-is
+        let (sum, overflow) = self.mont.overflowing_add(rhs.mont);
+        let mont = if overflow || sum >= P {
+            sum.wrapping_sub(P)
+        } else {
+            sum
+        };
+        Self { mont }
+    }
+}
+
+impl<const P: u64> Sub for PrimeField<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let mont = if self.mont >= rhs.mont {
+            self.mont - rhs.mont
+        } else {
+            P - (rhs.mont - self.mont)
+        };
+        Self { mont }
+    }
+}
+
+impl<const P: u64> Mul for PrimeField<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            mont: montgomery::redc(self.mont as u128 * rhs.mont as u128, P, Self::NEG_INV),
+        }
+    }
+}
+
+impl<const P: u64> Div for PrimeField<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inverse().expect("division by zero")
+    }
+}
+
+impl<const P: u64> Neg for PrimeField<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl<const P: u64> AddAssign for PrimeField<P> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const P: u64> SubAssign for PrimeField<P> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const P: u64> MulAssign for PrimeField<P> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const P: u64> DivAssign for PrimeField<P> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const P: u64> Debug for PrimeField<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (mod {})", self.value(), P)
+    }
+}
+
+impl<const P: u64> Display for PrimeField<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::{
+        FftField, Field as ArkField, LegendreSymbol as ArkLegendre, PrimeField as ArkPrimeField,
+    };
+    use feldman_verifiable_secret_sharing::Fq as Fq99679;
+    use rand::{RngCore, SeedableRng};
+    use univariate_polynomial_iop_zerotest::Fq as Fq41;
+
+    use super::*;
+
+    type F41 = PrimeField<41>;
+    type F99679 = PrimeField<99679>;
+    type Goldilocks = PrimeField<0xffff_ffff_0000_0001>;
+
+    fn ark_value<F: ArkPrimeField>(x: F) -> u64 {
+        x.into_bigint().as_ref()[0]
+    }
+
+    fn legendre_matches(ours: LegendreSymbol, ark: ArkLegendre) -> bool {
+        matches!(
+            (ours, ark),
+            (LegendreSymbol::Zero, ArkLegendre::Zero)
+                | (
+                    LegendreSymbol::QuadraticResidue,
+                    ArkLegendre::QuadraticResidue
+                )
+                | (
+                    LegendreSymbol::QuadraticNonResidue,
+                    ArkLegendre::QuadraticNonResidue
+                )
+        )
+    }
+
+    #[test]
+    fn primality_check() {
+        assert!(is_prime(41));
+        assert!(is_prime(99679));
+        assert!(is_prime(0xffff_ffff_0000_0001));
+        assert!(!is_prime(1));
+        assert!(!is_prime(99681));
+        // Carmichael number, fools the Fermat test
+        assert!(!is_prime(561));
+    }
+
+    #[test]
+    fn exhaustive_cross_check_mod_41() {
+        for a in 0..41u64 {
+            assert!(legendre_matches(
+                F41::new(a).legendre(),
+                Fq41::from(a).legendre()
+            ));
+            for b in 0..41u64 {
+                let (x, y) = (F41::new(a), F41::new(b));
+                let (ax, ay) = (Fq41::from(a), Fq41::from(b));
+                assert_eq!((x + y).value(), ark_value(ax + ay));
+                assert_eq!((x - y).value(), ark_value(ax - ay));
+                assert_eq!((x * y).value(), ark_value(ax * ay));
+                assert_eq!(x.mul_barrett(&y), x * y);
+                if b != 0 {
+                    assert_eq!((x / y).value(), ark_value(ax / ay));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_cross_check_mod_99679() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let (a, b) = (rng.next_u64(), rng.next_u64());
+            let (x, y) = (F99679::new(a), F99679::new(b));
+            let (ax, ay) = (Fq99679::from(a), Fq99679::from(b));
+            assert_eq!(x.value(), ark_value(ax));
+            assert_eq!((x + y).value(), ark_value(ax + ay));
+            assert_eq!((x - y).value(), ark_value(ax - ay));
+            assert_eq!((x * y).value(), ark_value(ax * ay));
+            assert_eq!(x.mul_barrett(&y), x * y);
+            assert_eq!(
+                x.inverse().map(|i| i.value()),
+                ArkField::inverse(&ax).map(ark_value)
+            );
+            assert_eq!(x.pow_u64(b).value(), ark_value(ArkField::pow(&ax, [b])));
+            assert!(legendre_matches(x.legendre(), ax.legendre()));
+            // Square roots are unique up to sign
+            match (x.sqrt(), ax.sqrt()) {
+                (Some(r), Some(ar)) => {
+                    assert!(r.value() == ark_value(ar) || (-r).value() == ark_value(ar))
+                }
+                (None, None) => {}
+                _ => panic!("square root existence disagrees for {}", a),
+            }
+        }
+    }
+
+    #[test]
+    fn wide_reduction_mod_goldilocks() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        for _ in 0..1000 {
+            let (a, b) = (rng.next_u64(), rng.next_u64());
+            let wide = a as u128 * b as u128;
+            assert_eq!(
+                Goldilocks::from_u128(wide),
+                Goldilocks::new(a) * Goldilocks::new(b)
+            );
+            let x = Goldilocks::new(a);
+            assert_eq!(x * x.inverse().unwrap(), Goldilocks::ONE);
+            assert_eq!(x + (-x), Goldilocks::ZERO);
+            assert_eq!(
+                Goldilocks::from_le_bytes_mod_order(&wide.to_le_bytes()),
+                Goldilocks::from_u128(wide)
+            );
+            assert_eq!(x.to_limbs(), vec![a % Goldilocks::MODULUS]);
+            assert_eq!(Goldilocks::from_limbs(&x.to_limbs()), Some(x));
+        }
+        assert_eq!(Goldilocks::from_limbs(&[Goldilocks::MODULUS]), None);
+        assert_eq!(Goldilocks::from_limbs(&[1, 0]), None);
+    }
+
+    #[test]
+    fn tonelli_shanks_with_large_two_adicity() {
+        // Goldilocks has `P - 1 = q * 2^32`, exercising many rounds
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(2);
+        for _ in 0..100 {
+            let x = Goldilocks::new(rng.next_u64());
+            let square = x.square();
+            let root = square.sqrt().unwrap();
+            assert!(root == x || root == -x);
+        }
+        // 7 is the generator used by plonky2 for Goldilocks, hence a
+        // non-residue
+        assert_eq!(Goldilocks::new(7).sqrt(), None);
+    }
+
+    #[test]
+    fn primitive_roots() {
+        // 2 is configured as the generator for the ark-ff field mod 41,
+        // but only has order 20 since `2^10 = -1 mod 41`
+        assert_eq!(F41::primitive_root().value(), 6);
+        assert_eq!(F41::new(ark_value(Fq41::GENERATOR)).pow_u64(20), F41::ONE);
+        // 13, used as the commitment base in Feldman's VSS, does generate
+        // the group mod 99679 though it is not the smallest generator
+        assert_eq!(F99679::primitive_root().value(), 6);
+        let feldman_generator = F99679::new(ark_value(Fq99679::GENERATOR));
+        assert!(F99679::group_order_factors()
+            .iter()
+            .all(|q| feldman_generator.pow_u64((99679 - 1) / q) != F99679::ONE));
+        assert_eq!(Goldilocks::primitive_root().value(), 7);
+
+        assert_eq!(F41::group_order_factors(), vec![2, 5]);
+        assert_eq!(
+            Goldilocks::group_order_factors(),
+            vec![2, 3, 5, 17, 257, 65537]
+        );
+    }
+
+    #[test]
+    fn roots_of_unity() {
+        let omega = F41::root_of_unity(8).unwrap();
+        assert_eq!(omega.pow_u64(8), F41::ONE);
+        assert_ne!(omega.pow_u64(4), F41::ONE);
+        assert_eq!(F41::root_of_unity(3), None);
+
+        let omega = Goldilocks::root_of_unity(1 << 32).unwrap();
+        assert_eq!(omega.pow([1 << 32]), Goldilocks::ONE);
+        assert_eq!(omega.pow([1 << 31]), -Goldilocks::ONE);
+    }
+}
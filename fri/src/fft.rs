@@ -0,0 +1,62 @@
+//! Radix-2 FFTs over cosets `shift · <ω>` of power of two subgroups, on
+//! top of those over the subgroups in [`finite_fields::fft`].
+
+use finite_fields::{
+    fft::{field_fft, field_ifft},
+    Field,
+};
+
+/// Evaluations of the polynomial with coefficients `coeffs` at
+/// `shift · ω^i` for `i < n`, where `ω` has order `n`
+pub fn coset_fft<F: Field>(coeffs: &[F], shift: F, generator: F, n: usize) -> Vec<F> {
+    assert!(n.is_power_of_two(), "domain size must be a power of two");
+    assert!(coeffs.len() <= n, "more coefficients than domain elements");
+    let mut scaled: Vec<F> = coeffs
+        .iter()
+        .zip(std::iter::successors(Some(F::one()), |power| {
+            Some(*power * shift)
+        }))
+        .map(|(coeff, power)| *coeff * power)
+        .collect();
+    scaled.resize(n, F::zero());
+    field_fft(&scaled, generator)
+}
+
+/// Coefficients of the polynomial of degree less than `evals.len()` that
+/// takes value `evals[i]` at `shift · ω^i`
+pub fn coset_ifft<F: Field>(evals: &[F], shift: F, generator: F) -> Vec<F> {
+    let shift_inverse = shift.inverse().expect("non-zero");
+    field_ifft(evals, generator)
+        .into_iter()
+        .zip(std::iter::successors(Some(F::one()), |factor| {
+            Some(*factor * shift_inverse)
+        }))
+        .map(|(coeff, factor)| coeff * factor)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::Zero;
+    use polynomial::Polynomial;
+
+    use super::*;
+    use crate::{Goldilocks, TwoAdicField};
+
+    #[test]
+    fn matches_naive_evaluation() {
+        let coeffs: Vec<Goldilocks> = [3u64, 1, 4, 1, 5].map(Goldilocks::from).to_vec();
+        let polynomial = Polynomial::new_from_coeffs(&coeffs);
+        let shift = Goldilocks::multiplicative_generator();
+        let generator = Goldilocks::primitive_root_of_unity(3);
+
+        let evals = coset_fft(&coeffs, shift, generator, 8);
+        for (i, eval) in evals.iter().enumerate() {
+            assert_eq!(*eval, polynomial.eval(shift * generator.pow([i as u64])));
+        }
+
+        let mut padded = coeffs.clone();
+        padded.resize(8, Goldilocks::zero());
+        assert_eq!(coset_ifft(&evals, shift, generator), padded);
+    }
+}
@@ -0,0 +1,76 @@
+use finite_fields::{Field, PrimeField};
+
+/// The Goldilocks prime `2^64 - 2^32 + 1` that `plonky2` works over
+pub const GOLDILOCKS: u64 = 0xffff_ffff_0000_0001;
+
+pub type Goldilocks = PrimeField<GOLDILOCKS>;
+
+/// A field whose multiplicative group has a large power of two subgroup,
+/// so that polynomials can be evaluated on it with radix-2 FFTs
+pub trait TwoAdicField: Field {
+    /// The largest `s` such that `2^s` divides the order of the
+    /// multiplicative group
+    const TWO_ADICITY: usize;
+
+    /// A generator of the multiplicative group. It lies in no proper
+    /// subgroup, so it shifts evaluation domains off their subgroup.
+    fn multiplicative_generator() -> Self;
+
+    /// A primitive `2^bits`-th root of unity
+    fn primitive_root_of_unity(bits: usize) -> Self;
+}
+
+impl TwoAdicField for Goldilocks {
+    const TWO_ADICITY: usize = 32;
+
+    fn multiplicative_generator() -> Self {
+        Self::new(7)
+    }
+
+    /// `7^((p - 1) / 2^32)` squared `32 - bits` times
+    fn primitive_root_of_unity(bits: usize) -> Self {
+        assert!(
+            bits <= Self::TWO_ADICITY,
+            "no root of unity of order 2^{bits}"
+        );
+        let root = Self::multiplicative_generator().pow([(GOLDILOCKS - 1) >> Self::TWO_ADICITY]);
+        (bits..Self::TWO_ADICITY).fold(root, |root, _| root.square())
+    }
+}
+
+#[cfg(feature = "plonky2")]
+impl<F> TwoAdicField for finite_fields::Plonky2Field<F>
+where
+    F: plonky2::field::types::PrimeField64,
+{
+    const TWO_ADICITY: usize = F::TWO_ADICITY;
+
+    fn multiplicative_generator() -> Self {
+        Self(F::MULTIPLICATIVE_GROUP_GENERATOR)
+    }
+
+    fn primitive_root_of_unity(bits: usize) -> Self {
+        Self(F::primitive_root_of_unity(bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_traits::One;
+
+    use super::*;
+
+    #[test]
+    fn goldilocks_roots_of_unity() {
+        for bits in [0, 1, 5, 32] {
+            let root = Goldilocks::primitive_root_of_unity(bits);
+            assert_eq!(root.pow([1 << bits]), Goldilocks::one());
+            if bits > 0 {
+                assert_ne!(root.pow([1 << (bits - 1)]), Goldilocks::one());
+            }
+        }
+        // 7 generates the whole group, so it is not a square
+        let generator = Goldilocks::multiplicative_generator();
+        assert_ne!(generator.pow([(GOLDILOCKS - 1) / 2]), Goldilocks::one());
+    }
+}
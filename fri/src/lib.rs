@@ -0,0 +1,335 @@
+//! FRI, the Fast Reed–Solomon IOP of Proximity, as a low-degree test over
+//! two-adic fields such as Goldilocks.
+//!
+//! The prover commits to the evaluations of `f` of degree less than
+//! `2^degree_bits` on a coset `g · <ω>` that is `2^rate_bits` times larger,
+//! i.e. to a Reed–Solomon codeword, with a Merkle tree. In every round it
+//! receives a challenge `β` and folds the codeword by the arity `k`: writing
+//! `f(X) = Σ_j X^j f_j(X^k)`, the folded polynomial is
+//! `f'(Y) = Σ_j β^j f_j(Y)`, of `k` times smaller degree on a `k` times
+//! smaller domain. Its value at `x^k` only depends on the values of `f` on
+//! the `k` points `x · ζ^j` with `ζ^k = 1`, which is why the tree has those
+//! in one leaf. Once the degree is small enough, the prover sends the final
+//! polynomial in the clear.
+//!
+//! The verifier then queries random leaves of the first codeword, folds
+//! them itself and checks the results against the leaves of the next
+//! codeword, and finally against the final polynomial. A codeword far from
+//! every low-degree polynomial fails some query with high probability.
+
+mod fft;
+mod field;
+mod prover;
+mod verifier;
+
+pub use fft::{coset_fft, coset_ifft};
+pub use field::{Goldilocks, TwoAdicField, GOLDILOCKS};
+pub use prover::prove;
+pub use verifier::verify;
+
+use fiat_shamir::Transcript;
+use finite_fields::Field;
+use merkle_tree::{MerkleHasher, MultiProof};
+
+/// Parameters shared by prover and verifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FriConfig {
+    /// The codeword is `2^rate_bits` times longer than the degree bound
+    pub rate_bits: usize,
+    /// Every round folds `2^arity_bits` evaluations into one
+    pub arity_bits: usize,
+    /// Folding stops once the degree bound is at most `2^final_poly_bits`
+    pub final_poly_bits: usize,
+    pub num_query_rounds: usize,
+}
+
+impl FriConfig {
+    /// The FRI parameters of `plonky2`'s `standard_recursion_config`, which
+    /// `PoseidonGoldilocksConfig` proofs use by default: rate `1/8`, arity
+    /// 16 and 28 queries. `plonky2` adds 16 bits of proof of work to reach
+    /// 100 bits of conjectured security, which is not implemented here.
+    pub fn plonky2_standard() -> Self {
+        Self {
+            rate_bits: 3,
+            arity_bits: 4,
+            final_poly_bits: 5,
+            num_query_rounds: 28,
+        }
+    }
+
+    /// The arity of every folding round for polynomials of degree less
+    /// than `2^degree_bits`, as `plonky2`'s `ConstantArityBits` strategy
+    /// computes them. The last round may overshoot the final degree, but
+    /// never folds below a constant.
+    pub fn reduction_arity_bits(&self, mut degree_bits: usize) -> Vec<usize> {
+        let mut arities = vec![];
+        while degree_bits > self.final_poly_bits {
+            let arity = self.arity_bits.min(degree_bits);
+            arities.push(arity);
+            degree_bits -= arity;
+        }
+        arities
+    }
+
+    /// The rounds in which a codeword is committed to. If no folding is
+    /// needed, the initial codeword is still committed to and queried, in
+    /// a round that folds nothing.
+    fn rounds<F: TwoAdicField>(&self, degree_bits: usize) -> (Vec<Round<F>>, Round<F>) {
+        let mut arities = self.reduction_arity_bits(degree_bits);
+        if arities.is_empty() {
+            arities.push(0);
+        }
+        let mut domain = Round {
+            bits: degree_bits + self.rate_bits,
+            arity: 0,
+            shift: F::multiplicative_generator(),
+        };
+        let mut rounds = vec![];
+        for arity in arities {
+            rounds.push(Round { arity, ..domain });
+            domain.bits -= arity;
+            domain.shift = domain.shift.pow([1 << arity]);
+        }
+        (rounds, domain)
+    }
+
+    fn transcript(&self, degree_bits: usize) -> Transcript {
+        let mut transcript = Transcript::new(b"fri");
+        for (label, value) in [
+            (&b"rate_bits"[..], self.rate_bits),
+            (b"arity_bits", self.arity_bits),
+            (b"final_poly_bits", self.final_poly_bits),
+            (b"num_query_rounds", self.num_query_rounds),
+            (b"degree_bits", degree_bits),
+        ] {
+            transcript.append_message(label, &(value as u64).to_le_bytes());
+        }
+        transcript
+    }
+}
+
+/// A codeword on the coset `shift · <ω>` of size `2^bits`, committed to
+/// with `2^arity` evaluations per leaf
+#[derive(Debug, Clone, Copy)]
+struct Round<F> {
+    bits: usize,
+    arity: usize,
+    shift: F,
+}
+
+impl<F: TwoAdicField> Round<F> {
+    fn num_leaves(&self) -> usize {
+        1 << (self.bits - self.arity)
+    }
+
+    /// The leaf `index` holds the evaluations at `x · ζ^j` for this `x`
+    fn point(&self, index: usize) -> F {
+        self.shift * F::primitive_root_of_unity(self.bits).pow([index as u64])
+    }
+}
+
+/// The value at `β` of the polynomial interpolating `row` on `x · ζ^j`,
+/// which is the value of the folded polynomial at `x^k`
+fn fold_row<F: TwoAdicField>(row: &[F], x: F, beta: F) -> F {
+    let zeta = F::primitive_root_of_unity(row.len().trailing_zeros() as usize);
+    coset_ifft(row, x, zeta)
+        .iter()
+        .rev()
+        .fold(F::zero(), |acc, coeff| acc * beta + *coeff)
+}
+
+/// Evaluates the final polynomial by Horner's rule
+fn evaluate<F: Field>(coeffs: &[F], point: F) -> F {
+    coeffs
+        .iter()
+        .rev()
+        .fold(F::zero(), |acc, coeff| acc * point + *coeff)
+}
+
+/// Absorbs the final polynomial and samples the queried leaves of the
+/// first codeword
+fn query_indices<F: Field>(
+    transcript: &mut Transcript,
+    final_polynomial: &[F],
+    num_leaves: usize,
+    num_queries: usize,
+) -> Vec<usize> {
+    for coeff in final_polynomial {
+        transcript.append_field(b"final", coeff);
+    }
+    (0..num_queries)
+        .map(|_| {
+            let bytes = transcript.challenge_bytes(b"query");
+            let value = u64::from_le_bytes(bytes[..8].try_into().unwrap());
+            value as usize % num_leaves
+        })
+        .collect()
+}
+
+/// Distinct leaves the queries open in a round, by increasing index
+fn opened_leaves(queries: &[usize], num_leaves: usize) -> Vec<usize> {
+    let mut leaves: Vec<usize> = queries.iter().map(|query| query % num_leaves).collect();
+    leaves.sort_unstable();
+    leaves.dedup();
+    leaves
+}
+
+/// A non-interactive FRI proof for a committed codeword
+#[derive(Debug, Clone)]
+pub struct FriProof<F, H: MerkleHasher> {
+    /// Roots of the folded codewords, i.e. of all rounds but the first
+    pub commitments: Vec<H::Digest>,
+    pub query_rounds: Vec<FriQueryRound<F, H>>,
+    /// Coefficients of the polynomial the last folding results in
+    pub final_polynomial: Vec<F>,
+}
+
+/// The leaves the queries open in one round
+#[derive(Debug, Clone)]
+pub struct FriQueryRound<F, H: MerkleHasher> {
+    /// The opened leaves, by increasing index
+    pub rows: Vec<Vec<F>>,
+    pub proof: MultiProof<H>,
+}
+
+#[cfg(test)]
+mod tests {
+    use merkle_tree::{Blake2sHasher, Sha256Hasher};
+    use num_traits::One;
+    use polynomial::Polynomial;
+
+    use super::*;
+    use crate::prover::prove_codeword;
+
+    type Sha = Sha256Hasher;
+
+    fn polynomial(len: u64) -> Polynomial<Goldilocks> {
+        let coeffs: Vec<Goldilocks> = (0..len).map(|i| Goldilocks::from(i * i + 7)).collect();
+        Polynomial::new_from_coeffs(&coeffs)
+    }
+
+    const CONFIG: FriConfig = FriConfig {
+        rate_bits: 2,
+        arity_bits: 2,
+        final_poly_bits: 1,
+        num_query_rounds: 12,
+    };
+
+    #[test]
+    fn honest_proofs_verify() {
+        assert_eq!(CONFIG.reduction_arity_bits(6), vec![2, 2, 2]);
+        let f = polynomial(64);
+        let (root, proof) = prove::<_, Sha>(&CONFIG, 6, &f);
+        assert_eq!(proof.commitments.len(), 2);
+        assert_eq!(proof.final_polynomial.len(), 1);
+        assert!(verify(&CONFIG, 6, &root, &proof));
+
+        // Lower degrees pass a higher bound
+        let (root, proof) = prove::<_, Sha>(&CONFIG, 6, &polynomial(5));
+        assert!(verify(&CONFIG, 6, &root, &proof));
+
+        let binary = FriConfig {
+            arity_bits: 1,
+            ..CONFIG
+        };
+        let (root, proof) = prove::<_, Blake2sHasher>(&binary, 5, &polynomial(32));
+        assert_eq!(proof.commitments.len(), 3);
+        assert!(verify(&binary, 5, &root, &proof));
+    }
+
+    #[test]
+    fn no_folding_below_final_degree() {
+        assert!(CONFIG.reduction_arity_bits(1).is_empty());
+        let (root, proof) = prove::<_, Sha>(&CONFIG, 1, &polynomial(2));
+        assert!(proof.commitments.is_empty());
+        assert!(verify(&CONFIG, 1, &root, &proof));
+
+        let mut tampered = proof.clone();
+        tampered.final_polynomial[0] += Goldilocks::one();
+        assert!(!verify(&CONFIG, 1, &root, &tampered));
+    }
+
+    #[test]
+    fn high_degree_codewords_are_rejected() {
+        // The codeword of a polynomial of degree 2^7 - 1 on the domain for
+        // degree bound 2^6, which is as far from the code as it gets
+        let first = CONFIG.rounds::<Goldilocks>(6).0[0];
+        let coeffs = polynomial(128).get_raw_coefficients();
+        let evals = coset_fft(
+            &coeffs,
+            first.shift,
+            Goldilocks::primitive_root_of_unity(first.bits),
+            1 << first.bits,
+        );
+        let (root, proof) = prove_codeword::<_, Sha>(&CONFIG, 6, evals);
+        assert!(!verify(&CONFIG, 6, &root, &proof));
+    }
+
+    #[test]
+    fn tampered_proofs_are_rejected() {
+        let (root, proof) = prove::<_, Sha>(&CONFIG, 6, &polynomial(64));
+
+        let mut tampered = proof.clone();
+        tampered.query_rounds[1].rows[0][1] += Goldilocks::one();
+        assert!(!verify(&CONFIG, 6, &root, &tampered));
+
+        let mut tampered = proof.clone();
+        tampered.final_polynomial[0] += Goldilocks::one();
+        assert!(!verify(&CONFIG, 6, &root, &tampered));
+
+        let mut tampered = proof.clone();
+        tampered.commitments.swap(0, 1);
+        assert!(!verify(&CONFIG, 6, &root, &tampered));
+
+        let mut tampered = proof.clone();
+        tampered.query_rounds[0].rows.pop();
+        assert!(!verify(&CONFIG, 6, &root, &tampered));
+
+        let (other, _) = prove::<_, Sha>(&CONFIG, 6, &polynomial(63));
+        assert!(!verify(&CONFIG, 6, &other, &proof));
+        assert!(!verify(&CONFIG, 5, &root, &proof));
+    }
+
+    #[cfg(feature = "plonky2")]
+    #[test]
+    fn plonky2_standard_config() {
+        use finite_fields::Plonky2Field;
+        use merkle_tree::PoseidonHasher;
+        use plonky2::{
+            field::goldilocks_field::GoldilocksField, plonk::circuit_data::CircuitConfig,
+        };
+
+        // The same rounds as plonky2 for the degrees its recursion circuits
+        // have
+        let theirs = CircuitConfig::standard_recursion_config().fri_config;
+        let ours = FriConfig::plonky2_standard();
+        assert_eq!(ours.rate_bits, theirs.rate_bits);
+        assert_eq!(ours.num_query_rounds, theirs.num_query_rounds);
+        for degree_bits in 6..=20 {
+            assert_eq!(
+                ours.reduction_arity_bits(degree_bits),
+                theirs.reduction_strategy.reduction_arity_bits(
+                    degree_bits,
+                    theirs.rate_bits,
+                    theirs.cap_height,
+                    theirs.num_query_rounds
+                )
+            );
+        }
+
+        // Poseidon Merkle trees over Goldilocks, as `PoseidonGoldilocksConfig`
+        type F = Plonky2Field<GoldilocksField>;
+        let coeffs: Vec<F> = (0..1024u64).map(|i| F::from(i ^ 0x5a)).collect();
+        let (root, proof) =
+            prove::<F, PoseidonHasher>(&ours, 10, &Polynomial::new_from_coeffs(&coeffs));
+        assert_eq!(proof.commitments.len(), 1);
+        assert_eq!(proof.final_polynomial.len(), 4);
+        assert!(verify(&ours, 10, &root, &proof));
+
+        // The roots of unity agree with the self-written Goldilocks
+        assert_eq!(
+            F::primitive_root_of_unity(32).to_limbs(),
+            Goldilocks::primitive_root_of_unity(32).to_limbs()
+        );
+    }
+}
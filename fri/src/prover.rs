@@ -0,0 +1,107 @@
+use merkle_tree::{MerkleHasher, MerkleTree};
+use polynomial::Polynomial;
+
+use crate::{
+    coset_fft, coset_ifft, fold_row, opened_leaves, query_indices, FriConfig, FriProof,
+    FriQueryRound, TwoAdicField,
+};
+
+/// Commits to the codeword of `polynomial`, of degree less than
+/// `2^degree_bits`, and proves that it is close to a polynomial of that
+/// degree. Returns the root of the codeword with the proof.
+pub fn prove<F, H>(
+    config: &FriConfig,
+    degree_bits: usize,
+    polynomial: &Polynomial<F>,
+) -> (H::Digest, FriProof<F, H>)
+where
+    F: TwoAdicField,
+    H: MerkleHasher,
+{
+    let len = polynomial.leading_degree().map_or(0, |degree| degree + 1);
+    assert!(
+        len <= 1 << degree_bits,
+        "polynomial degree exceeds the bound of 2^{degree_bits}"
+    );
+    let (rounds, _) = config.rounds::<F>(degree_bits);
+    let first = rounds[0];
+    let coeffs = &polynomial.get_raw_coefficients()[..len];
+    let evals = coset_fft(
+        coeffs,
+        first.shift,
+        F::primitive_root_of_unity(first.bits),
+        1 << first.bits,
+    );
+    prove_codeword(config, degree_bits, evals)
+}
+
+/// Proves that `evals` on the first domain are a codeword of the degree
+/// bound, which an honest prover computes with an FFT
+pub(crate) fn prove_codeword<F, H>(
+    config: &FriConfig,
+    degree_bits: usize,
+    mut evals: Vec<F>,
+) -> (H::Digest, FriProof<F, H>)
+where
+    F: TwoAdicField,
+    H: MerkleHasher,
+{
+    let (rounds, last) = config.rounds::<F>(degree_bits);
+    let first = rounds[0];
+    let mut transcript = config.transcript(degree_bits);
+    let mut layers = vec![];
+    for round in &rounds {
+        let num_leaves = round.num_leaves();
+        let rows: Vec<Vec<F>> = (0..num_leaves)
+            .map(|leaf| {
+                (0..1 << round.arity)
+                    .map(|j| evals[leaf + j * num_leaves])
+                    .collect()
+            })
+            .collect();
+        let tree = MerkleTree::<H>::new(&rows);
+        transcript.append_message(b"commitment", &H::digest_to_bytes(&tree.root()));
+        let beta: F = transcript.challenge(b"beta");
+
+        evals = rows
+            .iter()
+            .enumerate()
+            .map(|(leaf, row)| fold_row(row, round.point(leaf), beta))
+            .collect();
+        layers.push((tree, rows));
+    }
+
+    let final_len = 1 << (last.bits - config.rate_bits);
+    let mut final_polynomial =
+        coset_ifft(&evals, last.shift, F::primitive_root_of_unity(last.bits));
+    final_polynomial.truncate(final_len);
+
+    let queries = query_indices(
+        &mut transcript,
+        &final_polynomial,
+        first.num_leaves(),
+        config.num_query_rounds,
+    );
+    let query_rounds = rounds
+        .iter()
+        .zip(&layers)
+        .map(|(round, (tree, rows))| {
+            let leaves = opened_leaves(&queries, round.num_leaves());
+            FriQueryRound {
+                rows: leaves.iter().map(|leaf| rows[*leaf].clone()).collect(),
+                proof: tree.open_multi(&leaves),
+            }
+        })
+        .collect();
+
+    let root = layers[0].0.root();
+    let commitments = layers[1..].iter().map(|(tree, _)| tree.root()).collect();
+    (
+        root,
+        FriProof {
+            commitments,
+            query_rounds,
+            final_polynomial,
+        },
+    )
+}
@@ -0,0 +1,91 @@
+use merkle_tree::MerkleHasher;
+
+use crate::{evaluate, fold_row, opened_leaves, query_indices, FriConfig, FriProof, TwoAdicField};
+
+/// Checks that the codeword with root `commitment` is close to a
+/// polynomial of degree less than `2^degree_bits`
+pub fn verify<F, H>(
+    config: &FriConfig,
+    degree_bits: usize,
+    commitment: &H::Digest,
+    proof: &FriProof<F, H>,
+) -> bool
+where
+    F: TwoAdicField,
+    H: MerkleHasher,
+{
+    let (rounds, last) = config.rounds::<F>(degree_bits);
+    if proof.commitments.len() != rounds.len() - 1
+        || proof.query_rounds.len() != rounds.len()
+        || proof.final_polynomial.len() != 1 << (last.bits - config.rate_bits)
+    {
+        return false;
+    }
+
+    let mut transcript = config.transcript(degree_bits);
+    let roots: Vec<&H::Digest> = std::iter::once(commitment)
+        .chain(&proof.commitments)
+        .collect();
+    let betas: Vec<F> = roots
+        .iter()
+        .map(|root| {
+            transcript.append_message(b"commitment", &H::digest_to_bytes(root));
+            transcript.challenge(b"beta")
+        })
+        .collect();
+    let queries = query_indices(
+        &mut transcript,
+        &proof.final_polynomial,
+        rounds[0].num_leaves(),
+        config.num_query_rounds,
+    );
+
+    // Authenticate the opened leaves of every round
+    let mut leaves_per_round = vec![];
+    for ((round, root), query_round) in rounds.iter().zip(&roots).zip(&proof.query_rounds) {
+        let leaves = opened_leaves(&queries, round.num_leaves());
+        if query_round.rows.len() != leaves.len()
+            || query_round
+                .rows
+                .iter()
+                .any(|row| row.len() != 1 << round.arity)
+        {
+            return false;
+        }
+        let opened: Vec<(usize, Vec<F>)> = leaves
+            .iter()
+            .copied()
+            .zip(query_round.rows.iter().cloned())
+            .collect();
+        if !query_round
+            .proof
+            .verify(root, round.bits - round.arity, &opened)
+        {
+            return false;
+        }
+        leaves_per_round.push(leaves);
+    }
+
+    // Fold every query through the rounds down to the final polynomial
+    queries.iter().all(|query| {
+        let mut expected: Option<(usize, F)> = None;
+        let mut folded = (0, F::zero());
+        for (r, round) in rounds.iter().enumerate() {
+            let leaf = query % round.num_leaves();
+            let position = leaves_per_round[r].binary_search(&leaf).unwrap();
+            let row = &proof.query_rounds[r].rows[position];
+            if let Some((column, value)) = expected {
+                if row[column] != value {
+                    return false;
+                }
+            }
+            let value = fold_row(row, round.point(leaf), betas[r]);
+            folded = (leaf, value);
+            expected = rounds
+                .get(r + 1)
+                .map(|next| (leaf / next.num_leaves(), value));
+        }
+        let (index, value) = folded;
+        evaluate(&proof.final_polynomial, last.point(index)) == value
+    })
+}
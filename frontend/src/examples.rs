@@ -0,0 +1,99 @@
+//! The statements of the plonky2 examples, written once for every backend
+
+/// Twelve steps of the Fibonacci recurrence from the public `a`, `b`, as in
+/// plonky2-fibonacci
+pub const FIBONACCI: &str = "
+public a;
+public b;
+public out;
+for i in 0..12 {
+    let next = a + b;
+    a = b;
+    b = next;
+}
+assert b == out;
+";
+
+/// `x · 2 · 3 ⋯ 100`, as in plonky2-factorial
+pub const FACTORIAL: &str = "
+public x;
+public out;
+let acc = x;
+for i in 2..101 {
+    acc = acc * i;
+}
+assert acc == out;
+";
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_ff::FftField;
+    use groth16::{prove, qap::Qap, setup, verify};
+    use halo2_proofs::{dev::MockProver, pasta::Fp};
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use univariate_polynomial_iop_zerotest::domain::Domain;
+
+    use super::*;
+    use crate::{compile, Halo2Program, R1csProgram};
+
+    /// Whether the mock prover accepts the halo2 circuit over Pasta, and
+    /// whether Groth16 over BLS12-381 accepts a proof from the R1CS, for
+    /// the same inputs in each field
+    fn both_accept(source: &str, fp_inputs: &[Fp], fr_inputs: &[Fr]) -> (bool, bool) {
+        let ir = compile(source).unwrap();
+
+        let circuit = Halo2Program::with_inputs(ir.clone(), fp_inputs).unwrap();
+        let halo2 = MockProver::run(circuit.min_k(), &circuit, vec![circuit.instance()])
+            .unwrap()
+            .verify()
+            .is_ok();
+
+        let program = R1csProgram::<Fr>::compile(&ir);
+        let (instance, witness) = program.assignment(&ir.evaluate(fr_inputs).unwrap());
+        let size = (program.r1cs.num_constraints() + instance.len() + 1).next_power_of_two();
+        let domain = Domain::new(Fr::get_root_of_unity(size as u64).unwrap(), size);
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let pk = setup::<Bls12_381, _>(Qap::new(&program.r1cs, domain), &mut rng);
+        let groth16 = match prove(&pk, &instance, &witness, &mut rng) {
+            Ok(proof) => verify(&pk.vk, &instance, &proof),
+            Err(_) => false,
+        };
+        (halo2, groth16)
+    }
+
+    fn fibonacci(a: u64, b: u64, out: u64) -> (bool, bool) {
+        both_accept(
+            FIBONACCI,
+            &[a, b, out].map(Fp::from),
+            &[a, b, out].map(Fr::from),
+        )
+    }
+
+    #[test]
+    fn fibonacci_on_both_backends() {
+        assert_eq!(fibonacci(0, 1, 233), (true, true));
+        assert_eq!(fibonacci(2, 3, 987), (true, true));
+        assert_eq!(fibonacci(0, 1, 234), (false, false));
+    }
+
+    #[test]
+    fn factorial_on_both_backends() {
+        // 100! overflows any literal, so the output is computed in each field
+        let fp = |x: u64, extra: u64| {
+            let out = (2..101).fold(Fp::from(x), |acc, i| acc * Fp::from(i)) + Fp::from(extra);
+            [Fp::from(x), out]
+        };
+        let fr = |x: u64, extra: u64| {
+            let out = (2..101).fold(Fr::from(x), |acc, i| acc * Fr::from(i)) + Fr::from(extra);
+            [Fr::from(x), out]
+        };
+        assert_eq!(both_accept(FACTORIAL, &fp(1, 0), &fr(1, 0)), (true, true));
+        assert_eq!(both_accept(FACTORIAL, &fp(3, 0), &fr(3, 0)), (true, true));
+        assert_eq!(both_accept(FACTORIAL, &fp(1, 1), &fr(1, 1)), (false, false));
+
+        // Multiplying by the loop counter is free in R1CS
+        let program = R1csProgram::<Fr>::compile(&compile(FACTORIAL).unwrap());
+        assert_eq!(program.r1cs.num_constraints(), 1);
+    }
+}
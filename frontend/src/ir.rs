@@ -0,0 +1,282 @@
+//! A straight-line intermediate representation. Every node computes one
+//! value from inputs, constants and earlier nodes, loops are unrolled and
+//! names resolved to the node they were last bound to, so the backends only
+//! see additions, subtractions, multiplications and equality assertions.
+
+use std::collections::HashMap;
+
+use finite_fields::Field;
+
+use crate::{
+    parser::{Expr, Statement, Visibility},
+    FrontendError,
+};
+
+/// Most loop iterations a program may unroll to in total, where a nested
+/// loop counts once per iteration of the loops around it, so that a
+/// program of a few bytes cannot ask for an unbounded number of nodes
+pub const MAX_UNROLL: u64 = 1 << 16;
+
+/// Index of a node, which may only refer to nodes before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Wire(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Node {
+    /// The input of this index, by order of declaration
+    Input(usize),
+    Constant(u64),
+    Add(Wire, Wire),
+    Sub(Wire, Wire),
+    Mul(Wire, Wire),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Input {
+    pub name: String,
+    pub visibility: Visibility,
+    pub wire: Wire,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ir {
+    pub inputs: Vec<Input>,
+    pub nodes: Vec<Node>,
+    /// Pairs of wires that must carry the same value
+    pub assertions: Vec<(Wire, Wire)>,
+}
+
+/// Resolves names while lowering, with one scope per loop iteration
+struct Lowering {
+    ir: Ir,
+    scopes: Vec<HashMap<String, Wire>>,
+    /// Loop iterations unrolled so far, nested ones included
+    unrolled: u64,
+}
+
+impl Lowering {
+    fn push(&mut self, node: Node) -> Wire {
+        self.ir.nodes.push(node);
+        Wire(self.ir.nodes.len() - 1)
+    }
+
+    fn lookup(&self, name: &str) -> Result<Wire, FrontendError> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).copied())
+            .ok_or_else(|| FrontendError::Undefined(name.to_string()))
+    }
+
+    fn bind(&mut self, name: &str, wire: Wire) -> Result<(), FrontendError> {
+        let scope = self.scopes.last_mut().expect("there is always a scope");
+        match scope.insert(name.to_string(), wire) {
+            None => Ok(()),
+            Some(_) => Err(FrontendError::AlreadyDefined(name.to_string())),
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Result<Wire, FrontendError> {
+        let node = match expr {
+            Expr::Number(value) => Node::Constant(*value),
+            Expr::Variable(name) => return self.lookup(name),
+            Expr::Add(a, b) => Node::Add(self.expr(a)?, self.expr(b)?),
+            Expr::Sub(a, b) => Node::Sub(self.expr(a)?, self.expr(b)?),
+            Expr::Mul(a, b) => Node::Mul(self.expr(a)?, self.expr(b)?),
+            Expr::Neg(a) => {
+                let zero = self.push(Node::Constant(0));
+                Node::Sub(zero, self.expr(a)?)
+            }
+        };
+        Ok(self.push(node))
+    }
+
+    fn statements(&mut self, statements: &[Statement]) -> Result<(), FrontendError> {
+        for statement in statements {
+            match statement {
+                Statement::Input(visibility, name) => {
+                    let wire = self.push(Node::Input(self.ir.inputs.len()));
+                    self.bind(name, wire)?;
+                    self.ir.inputs.push(Input {
+                        name: name.clone(),
+                        visibility: *visibility,
+                        wire,
+                    });
+                }
+                Statement::Let(name, expr) => {
+                    let wire = self.expr(expr)?;
+                    self.bind(name, wire)?;
+                }
+                Statement::Assign(name, expr) => {
+                    let wire = self.expr(expr)?;
+                    let scope = self
+                        .scopes
+                        .iter_mut()
+                        .rev()
+                        .find(|scope| scope.contains_key(name))
+                        .ok_or_else(|| FrontendError::Undefined(name.clone()))?;
+                    scope.insert(name.clone(), wire);
+                }
+                Statement::AssertEq(a, b) => {
+                    let assertion = (self.expr(a)?, self.expr(b)?);
+                    self.ir.assertions.push(assertion);
+                }
+                Statement::For {
+                    variable,
+                    start,
+                    end,
+                    body,
+                } => {
+                    let iterations = self.unrolled.saturating_add(end.saturating_sub(*start));
+                    if iterations > MAX_UNROLL {
+                        return Err(FrontendError::LoopTooLong { iterations });
+                    }
+                    self.unrolled = iterations;
+                    for i in *start..*end {
+                        self.scopes.push(HashMap::new());
+                        let wire = self.push(Node::Constant(i));
+                        self.bind(variable, wire)?;
+                        self.statements(body)?;
+                        self.scopes.pop();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Ir {
+    /// Lowers a parsed program
+    pub fn lower(program: &[Statement]) -> Result<Self, FrontendError> {
+        let mut lowering = Lowering {
+            ir: Ir {
+                inputs: vec![],
+                nodes: vec![],
+                assertions: vec![],
+            },
+            scopes: vec![HashMap::new()],
+            unrolled: 0,
+        };
+        lowering.statements(program)?;
+        Ok(lowering.ir)
+    }
+
+    /// The inputs of the given visibility, by order of declaration
+    pub fn inputs(&self, visibility: Visibility) -> impl Iterator<Item = &Input> {
+        self.inputs
+            .iter()
+            .filter(move |input| input.visibility == visibility)
+    }
+
+    /// The value of every node for the inputs in order of declaration.
+    /// Assertions are left to the backends.
+    pub fn evaluate<F: Field>(&self, inputs: &[F]) -> Result<Vec<F>, FrontendError> {
+        if inputs.len() != self.inputs.len() {
+            return Err(FrontendError::WrongInputs {
+                expected: self.inputs.len(),
+                actual: inputs.len(),
+            });
+        }
+        let mut values: Vec<F> = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let value = match *node {
+                Node::Input(i) => inputs[i],
+                Node::Constant(value) => F::from_u64(value),
+                Node::Add(a, b) => values[a.0] + values[b.0],
+                Node::Sub(a, b) => values[a.0] - values[b.0],
+                Node::Mul(a, b) => values[a.0] * values[b.0],
+            };
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    /// The first assertion that `values` violate
+    pub fn failing_assertion<F: Field>(&self, values: &[F]) -> Option<usize> {
+        self.assertions
+            .iter()
+            .position(|(a, b)| values[a.0] != values[b.0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+    use crate::parser::parse;
+
+    type Fq = PrimeField<2147483647>;
+
+    #[test]
+    fn loops_are_unrolled_and_names_rebound() {
+        let program = parse(
+            "public out;
+             private x;
+             let acc = 1;
+             for i in 0..3 { let t = acc * x; acc = t + i; }
+             assert acc == out;",
+        )
+        .unwrap();
+        let ir = Ir::lower(&program).unwrap();
+        // acc = ((x + 0) x + 1) x + 2 = 132 for x = 5
+        let inputs = [132, 5].map(Fq::from_u64);
+        let values = ir.evaluate(&inputs).unwrap();
+        let (acc, out) = ir.assertions[0];
+        assert_eq!(values[acc.0], Fq::from_u64(132));
+        assert_eq!(values[out.0], Fq::from_u64(132));
+        assert_eq!(ir.failing_assertion(&values), None);
+        assert_eq!(ir.inputs(Visibility::Private).count(), 1);
+
+        let values = ir.evaluate(&[131, 5].map(Fq::from_u64)).unwrap();
+        assert_eq!(ir.failing_assertion(&values), Some(0));
+    }
+
+    #[test]
+    fn names_must_be_bound() {
+        let lower = |source: &str| Ir::lower(&parse(source).unwrap());
+        assert_eq!(
+            lower("let x = y;"),
+            Err(FrontendError::Undefined("y".to_string()))
+        );
+        assert_eq!(
+            lower("for i in 0..2 { let t = 1; } assert t == 1;"),
+            Err(FrontendError::Undefined("t".to_string()))
+        );
+        assert_eq!(
+            lower("private x; let x = 2;"),
+            Err(FrontendError::AlreadyDefined("x".to_string()))
+        );
+        assert_eq!(
+            lower("x = 2;"),
+            Err(FrontendError::Undefined("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn long_loops_are_rejected() {
+        let lower = |source: &str| Ir::lower(&parse(source).unwrap());
+        assert_eq!(
+            lower("for i in 0..18446744073709551615 { let t = i; }"),
+            Err(FrontendError::LoopTooLong {
+                iterations: u64::MAX
+            })
+        );
+        assert!(lower("for i in 7..65543 { let t = i; }").is_ok());
+        assert!(lower("for i in 3..0 { let t = i; }").is_ok());
+
+        // Nested and consecutive loops count towards the same total
+        assert_eq!(
+            lower("for i in 0..65536 { for j in 0..65536 { let t = i; } }"),
+            Err(FrontendError::LoopTooLong {
+                iterations: 65536 + 65536
+            })
+        );
+        assert!(lower("for i in 0..256 { for j in 0..255 { let t = j; } }").is_ok());
+        assert_eq!(
+            lower("for i in 0..65536 { let t = i; } for j in 0..1 { let t = j; }"),
+            Err(FrontendError::LoopTooLong { iterations: 65537 })
+        );
+    }
+}
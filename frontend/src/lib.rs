@@ -0,0 +1,61 @@
+//! A small language for arithmetic statements, compiled to several
+//! zero-knowledge backends. A program declares its public and private
+//! inputs, binds values with `let`, rebinds them with `=`, repeats
+//! statements with `for` loops of constant bounds, at most
+//! [`ir::MAX_UNROLL`] iterations in all, and states equalities
+//! with `assert`:
+//!
+//! ```text
+//! public out;
+//! private x;
+//! let acc = 1;
+//! for i in 0..3 {
+//!     acc = acc * x + i;
+//! }
+//! assert acc == out;
+//! ```
+//!
+//! [`compile`] parses the source and lowers it to an [`Ir`], which unrolls
+//! the loops into straight-line additions, subtractions and
+//! multiplications. The IR then goes to
+//!
+//! - an R1CS with [`R1csProgram`], for Groth16 and the other R1CS-based
+//!   SNARKs of the workspace, and
+//! - a halo2 [`halo2_proofs::plonk::Circuit`] with [`Halo2Program`].
+
+pub mod examples;
+pub mod ir;
+pub mod parser;
+pub mod to_halo2;
+pub mod to_r1cs;
+
+pub use ir::Ir;
+pub use to_halo2::Halo2Program;
+pub use to_r1cs::R1csProgram;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrontendError {
+    Parse {
+        line: usize,
+        message: String,
+    },
+    /// A name used before it is bound
+    Undefined(String),
+    /// A name bound twice in the same scope
+    AlreadyDefined(String),
+    /// Evaluation got the wrong number of inputs
+    WrongInputs {
+        expected: usize,
+        actual: usize,
+    },
+    /// Loops unrolling to more than [`ir::MAX_UNROLL`] iterations in total,
+    /// `iterations` being the count once the offending loop is added
+    LoopTooLong {
+        iterations: u64,
+    },
+}
+
+/// Parses and lowers a program
+pub fn compile(source: &str) -> Result<Ir, FrontendError> {
+    Ir::lower(&parser::parse(source)?)
+}
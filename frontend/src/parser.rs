@@ -0,0 +1,337 @@
+//! Tokens, syntax tree and a recursive descent parser for
+//!
+//! ```text
+//! program   := statement*
+//! statement := ("public" | "private") ident ";"
+//!            | "let" ident "=" expr ";"
+//!            | ident "=" expr ";"
+//!            | "assert" expr "==" expr ";"
+//!            | "for" ident "in" number ".." number "{" statement* "}"
+//! expr      := term (("+" | "-") term)*
+//! term      := factor ("*" factor)*
+//! factor    := number | ident | "(" expr ")" | "-" factor
+//! ```
+//!
+//! with `//` comments to the end of the line.
+
+use crate::FrontendError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Number(u64),
+    Symbol(&'static str),
+}
+
+const SYMBOLS: [&str; 11] = ["==", "..", "=", ";", "+", "-", "*", "(", ")", "{", "}"];
+
+const KEYWORDS: [&str; 6] = ["public", "private", "let", "assert", "for", "in"];
+
+/// Splits `source` into tokens, each with its line
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, FrontendError> {
+    let mut tokens = vec![];
+    for (index, line) in source.lines().enumerate() {
+        let number = index + 1;
+        let line = line.split("//").next().unwrap_or_default();
+        let mut rest = line.trim_start();
+        while !rest.is_empty() {
+            let word_len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let (token, len) = if word_len > 0 {
+                let word = &rest[..word_len];
+                let token = if word.starts_with(|c: char| c.is_ascii_digit()) {
+                    Token::Number(word.parse().map_err(|_| FrontendError::Parse {
+                        line: number,
+                        message: format!("invalid number `{word}`"),
+                    })?)
+                } else {
+                    Token::Ident(word.to_string())
+                };
+                (token, word_len)
+            } else {
+                let symbol = SYMBOLS
+                    .into_iter()
+                    .find(|symbol| rest.starts_with(symbol))
+                    .ok_or_else(|| FrontendError::Parse {
+                        line: number,
+                        message: format!("unexpected character `{}`", rest.chars().next().unwrap()),
+                    })?;
+                (Token::Symbol(symbol), symbol.len())
+            };
+            tokens.push((token, number));
+            rest = rest[len..].trim_start();
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Number(u64),
+    Variable(String),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Neg(Box<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    Private,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Statement {
+    Input(Visibility, String),
+    Let(String, Expr),
+    Assign(String, Expr),
+    AssertEq(Expr, Expr),
+    /// `for variable in start..end { body }`, unrolled when lowering
+    For {
+        variable: String,
+        start: u64,
+        end: u64,
+        body: Vec<Statement>,
+    },
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+}
+
+impl Parser {
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .or(self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn error<T>(&self, message: String) -> Result<T, FrontendError> {
+        Err(FrontendError::Parse {
+            line: self.line(),
+            message,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), FrontendError> {
+        match self.eat(symbol) {
+            true => Ok(()),
+            false => self.error(format!("expected `{symbol}`")),
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, FrontendError> {
+        match self.next() {
+            Some(Token::Ident(name)) if !KEYWORDS.contains(&name.as_str()) => Ok(name),
+            _ => {
+                self.position -= 1;
+                self.error("expected a name".to_string())
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<u64, FrontendError> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(value),
+            _ => {
+                self.position -= 1;
+                self.error("expected a number".to_string())
+            }
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Ident(word)) if word == keyword);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    /// Statements up to the end of input or a closing brace. Inputs are
+    /// only declared at the top level.
+    fn block(&mut self, top_level: bool) -> Result<Vec<Statement>, FrontendError> {
+        let mut statements = vec![];
+        while self.peek().is_some() && self.peek() != Some(&Token::Symbol("}")) {
+            statements.push(self.statement(top_level)?);
+        }
+        Ok(statements)
+    }
+
+    fn statement(&mut self, top_level: bool) -> Result<Statement, FrontendError> {
+        let visibility = if self.keyword("public") {
+            Some(Visibility::Public)
+        } else if self.keyword("private") {
+            Some(Visibility::Private)
+        } else {
+            None
+        };
+        let statement = if let Some(visibility) = visibility {
+            if !top_level {
+                self.position -= 1;
+                return self.error("inputs are declared at the top level".to_string());
+            }
+            Statement::Input(visibility, self.ident()?)
+        } else if self.keyword("let") {
+            let name = self.ident()?;
+            self.expect("=")?;
+            Statement::Let(name, self.expr()?)
+        } else if self.keyword("assert") {
+            let left = self.expr()?;
+            self.expect("==")?;
+            Statement::AssertEq(left, self.expr()?)
+        } else if self.keyword("for") {
+            let variable = self.ident()?;
+            if !self.keyword("in") {
+                return self.error("expected `in`".to_string());
+            }
+            let start = self.number()?;
+            self.expect("..")?;
+            let end = self.number()?;
+            self.expect("{")?;
+            let body = self.block(false)?;
+            self.expect("}")?;
+            return Ok(Statement::For {
+                variable,
+                start,
+                end,
+                body,
+            });
+        } else {
+            let name = self.ident()?;
+            self.expect("=")?;
+            Statement::Assign(name, self.expr()?)
+        };
+        self.expect(";")?;
+        Ok(statement)
+    }
+
+    fn expr(&mut self) -> Result<Expr, FrontendError> {
+        let mut expr = self.term()?;
+        loop {
+            if self.eat("+") {
+                expr = Expr::Add(Box::new(expr), Box::new(self.term()?));
+            } else if self.eat("-") {
+                expr = Expr::Sub(Box::new(expr), Box::new(self.term()?));
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, FrontendError> {
+        let mut expr = self.factor()?;
+        while self.eat("*") {
+            expr = Expr::Mul(Box::new(expr), Box::new(self.factor()?));
+        }
+        Ok(expr)
+    }
+
+    fn factor(&mut self) -> Result<Expr, FrontendError> {
+        if self.eat("(") {
+            let expr = self.expr()?;
+            self.expect(")")?;
+            return Ok(expr);
+        }
+        if self.eat("-") {
+            return Ok(Expr::Neg(Box::new(self.factor()?)));
+        }
+        match self.peek() {
+            Some(Token::Number(_)) => Ok(Expr::Number(self.number()?)),
+            _ => Ok(Expr::Variable(self.ident()?)),
+        }
+    }
+}
+
+/// Parses a whole program
+pub fn parse(source: &str) -> Result<Vec<Statement>, FrontendError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        position: 0,
+    };
+    let program = parser.block(true)?;
+    match parser.peek() {
+        None => Ok(program),
+        Some(_) => parser.error("unmatched `}`".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn var(name: &str) -> Box<Expr> {
+        Box::new(Expr::Variable(name.to_string()))
+    }
+
+    #[test]
+    fn precedence_and_loops() {
+        let program = parse(
+            "private x; // the input
+             for i in 0..3 { x = x * x + -i; }
+             assert (x - 1) * 2 == 0;",
+        )
+        .unwrap();
+        assert_eq!(
+            program[1],
+            Statement::For {
+                variable: "i".to_string(),
+                start: 0,
+                end: 3,
+                body: vec![Statement::Assign(
+                    "x".to_string(),
+                    Expr::Add(
+                        Box::new(Expr::Mul(var("x"), var("x"))),
+                        Box::new(Expr::Neg(var("i")))
+                    )
+                )],
+            }
+        );
+        assert_eq!(
+            program[2],
+            Statement::AssertEq(
+                Expr::Mul(
+                    Box::new(Expr::Sub(var("x"), Box::new(Expr::Number(1)))),
+                    Box::new(Expr::Number(2))
+                ),
+                Expr::Number(0)
+            )
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_line() {
+        let error = |source: &str| match parse(source) {
+            Err(FrontendError::Parse { line, .. }) => line,
+            other => panic!("expected a parse error, got {other:?}"),
+        };
+        assert_eq!(error("let x = 1;\nlet y = x +;"), 2);
+        assert_eq!(error("let x = 1\n"), 1);
+        assert_eq!(error("for i in 0..2 {\n public x;\n}"), 2);
+        assert_eq!(error("let let = 1;"), 1);
+        assert_eq!(error("let x = 1 % 2;"), 1);
+        assert_eq!(error("}"), 1);
+    }
+}
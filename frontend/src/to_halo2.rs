@@ -0,0 +1,189 @@
+//! Lowering to a halo2 [`Circuit`] with a single PLONK-style gate
+//!
+//! `q_L a + q_R b + q_O c + q_M a b + q_C = 0`
+//!
+//! on three advice columns. Node `i` takes row `i`: inputs and constants sit
+//! in `a`, the operands of an operation are copied into `a` and `b` and its
+//! result goes to `c`. Public inputs are copied to the instance column in
+//! order of declaration, and assertions are copy constraints.
+
+use finite_fields::Halo2Field;
+use halo2_proofs::{
+    circuit::{AssignedCell, Layouter, Region, SimpleFloorPlanner, Value},
+    pasta::group::ff::PrimeField,
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Fixed, Instance},
+    poly::Rotation,
+};
+
+use crate::{
+    ir::{Ir, Node, Wire},
+    parser::Visibility,
+    FrontendError,
+};
+
+#[derive(Debug, Clone)]
+pub struct ArithmeticConfig {
+    advice: [Column<Advice>; 3],
+    /// `q_L`, `q_R`, `q_O`, `q_M`, `q_C`
+    selectors: [Column<Fixed>; 5],
+    instance: Column<Instance>,
+}
+
+/// A program as a halo2 circuit, with the values of its nodes when proving
+#[derive(Debug, Clone)]
+pub struct Halo2Program<F> {
+    ir: Ir,
+    values: Option<Vec<F>>,
+}
+
+impl<F: PrimeField> Halo2Program<F> {
+    /// The circuit without values, for key generation
+    pub fn new(ir: Ir) -> Self {
+        Self { ir, values: None }
+    }
+
+    /// The circuit with the values computed from the inputs, in order of
+    /// declaration
+    pub fn with_inputs(ir: Ir, inputs: &[F]) -> Result<Self, FrontendError> {
+        let inputs: Vec<Halo2Field<F>> = inputs.iter().copied().map(Halo2Field).collect();
+        let values = ir
+            .evaluate(&inputs)?
+            .into_iter()
+            .map(|value| value.0)
+            .collect();
+        Ok(Self {
+            ir,
+            values: Some(values),
+        })
+    }
+
+    /// The instance column: the public inputs in order of declaration
+    pub fn instance(&self) -> Vec<F> {
+        let values = self.values.as_ref().expect("the circuit has values");
+        self.ir
+            .inputs(Visibility::Public)
+            .map(|input| values[input.wire.0])
+            .collect()
+    }
+
+    /// The smallest `k` whose `2^k` rows hold every node and the rows
+    /// halo2 reserves for blinding
+    pub fn min_k(&self) -> u32 {
+        let rows = self.ir.nodes.len() + 10;
+        rows.next_power_of_two().trailing_zeros().max(4)
+    }
+}
+
+fn assign_selectors<F: PrimeField>(
+    region: &mut Region<'_, F>,
+    columns: &[Column<Fixed>; 5],
+    row: usize,
+    selectors: [F; 5],
+) -> Result<(), Error> {
+    for (column, selector) in columns.iter().zip(selectors) {
+        region.assign_fixed(|| "selector", *column, row, || Value::known(selector))?;
+    }
+    Ok(())
+}
+
+impl<F: PrimeField> Circuit<F> for Halo2Program<F> {
+    type Config = ArithmeticConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::new(self.ir.clone())
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let advice = [(); 3].map(|_| meta.advice_column());
+        let selectors = [(); 5].map(|_| meta.fixed_column());
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+        for column in advice {
+            meta.enable_equality(column);
+        }
+        meta.create_gate("arithmetic", |meta| {
+            let [a, b, c] = advice.map(|column| meta.query_advice(column, Rotation::cur()));
+            let [q_l, q_r, q_o, q_m, q_c] = selectors.map(|column| meta.query_fixed(column));
+            vec![q_l * a.clone() + q_r * b.clone() + q_o * c + q_m * a * b + q_c]
+        });
+        ArithmeticConfig {
+            advice,
+            selectors,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let value = |i: usize| match &self.values {
+            Some(values) => Value::known(values[i]),
+            None => Value::unknown(),
+        };
+        let [a_column, b_column, c_column] = config.advice;
+        let cells = layouter.assign_region(
+            || "program",
+            |mut region| {
+                let mut cells: Vec<AssignedCell<F, F>> = Vec::with_capacity(self.ir.nodes.len());
+                for (row, node) in self.ir.nodes.iter().enumerate() {
+                    let (zero, one) = (F::ZERO, F::ONE);
+                    let constant = |wire: Wire| match self.ir.nodes[wire.0] {
+                        Node::Constant(k) => Some(F::from(k)),
+                        _ => None,
+                    };
+                    let cell = match *node {
+                        Node::Input(_) => {
+                            region.assign_advice(|| "input", a_column, row, || value(row))?
+                        }
+                        Node::Constant(k) => {
+                            assign_selectors(
+                                &mut region,
+                                &config.selectors,
+                                row,
+                                [one, zero, zero, zero, -F::from(k)],
+                            )?;
+                            region.assign_advice(|| "constant", a_column, row, || value(row))?
+                        }
+                        Node::Mul(x, y) if constant(x).or(constant(y)).is_some() => {
+                            let (k, other) = match constant(x) {
+                                Some(k) => (k, y),
+                                None => (constant(y).unwrap(), x),
+                            };
+                            assign_selectors(
+                                &mut region,
+                                &config.selectors,
+                                row,
+                                [k, zero, -one, zero, zero],
+                            )?;
+                            cells[other.0].copy_advice(|| "a", &mut region, a_column, row)?;
+                            region.assign_advice(|| "k a", c_column, row, || value(row))?
+                        }
+                        Node::Add(x, y) | Node::Sub(x, y) | Node::Mul(x, y) => {
+                            let selectors = match node {
+                                Node::Add(..) => [one, one, -one, zero, zero],
+                                Node::Sub(..) => [one, -one, -one, zero, zero],
+                                _ => [zero, zero, -one, one, zero],
+                            };
+                            assign_selectors(&mut region, &config.selectors, row, selectors)?;
+                            cells[x.0].copy_advice(|| "a", &mut region, a_column, row)?;
+                            cells[y.0].copy_advice(|| "b", &mut region, b_column, row)?;
+                            region.assign_advice(|| "c", c_column, row, || value(row))?
+                        }
+                    };
+                    cells.push(cell);
+                }
+                for (x, y) in &self.ir.assertions {
+                    region.constrain_equal(cells[x.0].cell(), cells[y.0].cell())?;
+                }
+                Ok(cells)
+            },
+        )?;
+        for (row, input) in self.ir.inputs(Visibility::Public).enumerate() {
+            layouter.constrain_instance(cells[input.wire.0].cell(), config.instance, row)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+//! Lowering to R1CS. Additions, subtractions and multiplications by a
+//! constant stay inside linear combinations for free; every other
+//! multiplication allocates a witness with one constraint, and every
+//! assertion becomes `a · 1 = b`.
+
+use finite_fields::Field;
+use r1cs::{ConstraintSystem, LinearCombination, R1CS};
+
+use crate::{
+    ir::{Ir, Node, Wire},
+    parser::Visibility,
+};
+
+/// The constraint system of a program and the nodes that supply the values
+/// of its variables
+#[derive(Debug, Clone)]
+pub struct R1csProgram<F> {
+    pub r1cs: R1CS<F>,
+    instance: Vec<Wire>,
+    witness: Vec<Wire>,
+}
+
+impl<F: Field> R1csProgram<F> {
+    pub fn compile(ir: &Ir) -> Self {
+        let mut cs = ConstraintSystem::new();
+        let mut instance = vec![];
+        let mut witness = vec![];
+        let mut combinations: Vec<LinearCombination<F>> = Vec::with_capacity(ir.nodes.len());
+        for (i, node) in ir.nodes.iter().enumerate() {
+            let lc = |wire: Wire| combinations[wire.0].clone();
+            let combination = match *node {
+                Node::Input(k) => {
+                    let variable = match ir.inputs[k].visibility {
+                        Visibility::Public => {
+                            instance.push(Wire(i));
+                            cs.instance()
+                        }
+                        Visibility::Private => {
+                            witness.push(Wire(i));
+                            cs.witness()
+                        }
+                    };
+                    variable.into()
+                }
+                Node::Constant(value) => LinearCombination::constant(F::from_u64(value)),
+                Node::Add(a, b) => (lc(a) + lc(b)).simplify(),
+                Node::Sub(a, b) => (lc(a) - lc(b)).simplify(),
+                Node::Mul(a, b) => match (lc(a).as_constant(), lc(b).as_constant()) {
+                    (Some(k), _) => (lc(b) * k).simplify(),
+                    (_, Some(k)) => (lc(a) * k).simplify(),
+                    (None, None) => {
+                        witness.push(Wire(i));
+                        cs.mul(lc(a), lc(b)).into()
+                    }
+                },
+            };
+            combinations.push(combination);
+        }
+        for (a, b) in &ir.assertions {
+            cs.enforce_equal(combinations[a.0].clone(), combinations[b.0].clone());
+        }
+        Self {
+            r1cs: cs.build(),
+            instance,
+            witness,
+        }
+    }
+
+    /// The instance and witness from the values of the nodes, as computed
+    /// by [`Ir::evaluate`]
+    pub fn assignment(&self, values: &[F]) -> (Vec<F>, Vec<F>) {
+        let pick = |wires: &[Wire]| wires.iter().map(|wire| values[wire.0]).collect();
+        (pick(&self.instance), pick(&self.witness))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+    use crate::compile;
+
+    type Fq = PrimeField<2147483647>;
+
+    #[test]
+    fn only_products_of_variables_cost_constraints() {
+        let ir = compile(
+            "public out;
+             private x;
+             let acc = 1;
+             for i in 0..3 { acc = acc * x * 2 + i; }
+             assert acc == out;",
+        )
+        .unwrap();
+        let program = R1csProgram::<Fq>::compile(&ir);
+        // `1 · x` folds into a linear combination, the next two do not
+        assert_eq!(program.r1cs.num_constraints(), 3);
+        assert_eq!(
+            (program.r1cs.num_instance, program.r1cs.num_witness),
+            (1, 3)
+        );
+
+        // acc = ((2x) 2x + 1) 2x + 2 = 1012 for x = 5
+        let values = ir.evaluate(&[1012, 5].map(Fq::from_u64)).unwrap();
+        let (instance, witness) = program.assignment(&values);
+        assert_eq!(instance, [Fq::from_u64(1012)]);
+        assert_eq!(program.r1cs.is_satisfied(&instance, &witness), Ok(()));
+        let values = ir.evaluate(&[1013, 5].map(Fq::from_u64)).unwrap();
+        let (instance, witness) = program.assignment(&values);
+        assert_eq!(
+            program.r1cs.is_satisfied(&instance, &witness),
+            Err(r1cs::R1csError::Unsatisfied(2))
+        );
+    }
+}
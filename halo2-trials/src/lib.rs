@@ -1,3 +1,5 @@
+mod lookup;
+
 use std::marker::PhantomData;
 
 use halo2_proofs::{
@@ -285,7 +287,23 @@ impl<F: Field> Circuit<F> for MyCircuit<F> {
 
 #[cfg(test)]
 mod tests {
-    use halo2_proofs::{circuit::Value, dev::MockProver, pasta::Fp};
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_ff::FftField;
+    use halo2_proofs::{
+        circuit::Value,
+        dev::MockProver,
+        pasta::{EqAffine, Fp},
+        plonk::{create_proof, keygen_pk, keygen_vk, verify_proof, SingleVerifier},
+        poly::commitment::Params,
+        transcript::{Blake2bRead, Blake2bWrite, Challenge255},
+    };
+    use plonk::circuit::{scaled_square_product, scaled_square_product_witness};
+    use polynomial_commitments::{
+        kzg::{Kzg, KzgCommitterKey, KzgVerifierKey},
+        PolynomialCommitmentScheme,
+    };
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use univariate_polynomial_iop_zerotest::domain::Domain;
 
     use crate::MyCircuit;
 
@@ -321,4 +339,93 @@ mod tests {
         let prover = MockProver::run(k, &circuit, vec![public_inputs]).unwrap();
         assert!(prover.verify().is_err());
     }
+
+    /// Proves `c = constant · a^2 · b^2` with the mock prover over Pasta and
+    /// with our own PLONK over BLS12-381, and returns whether each accepts
+    fn both_accept(constant: u64, a: u64, b: u64, c: u64) -> (bool, bool) {
+        let circuit = MyCircuit {
+            constant: Fp::from(constant),
+            a: Value::known(Fp::from(a)),
+            b: Value::known(Fp::from(b)),
+        };
+        let mock = MockProver::run(4, &circuit, vec![vec![Fp::from(c)]])
+            .unwrap()
+            .verify()
+            .is_ok();
+
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (ck, kzg, pk) = plonk_keys(constant, &mut rng);
+        // An honest prover only knows `a`, `b` and computes `c` itself
+        let witness = scaled_square_product_witness(Fr::from(constant), Fr::from(a), Fr::from(b));
+        let proof = plonk::prove(&ck, &pk, &witness, &mut rng).unwrap();
+        let ours = plonk::verify(&kzg, &pk.vk, &[Fr::from(c)], &proof);
+        (mock, ours)
+    }
+
+    fn plonk_keys(
+        constant: u64,
+        rng: &mut ChaCha20Rng,
+    ) -> (
+        KzgCommitterKey<Bls12_381>,
+        KzgVerifierKey<Bls12_381>,
+        plonk::ProvingKey<Bls12_381>,
+    ) {
+        let domain = Domain::new(Fr::get_root_of_unity(8).unwrap(), 8);
+        let circuit = scaled_square_product(Fr::from(constant), domain);
+        let (ck, kzg) = Kzg::<Bls12_381>::setup(13, rng);
+        let pk = plonk::setup(&ck, circuit);
+        (ck, kzg, pk)
+    }
+
+    #[test]
+    fn plonk_agrees_with_mock_prover() {
+        assert_eq!(both_accept(7, 2, 3, 252), (true, true));
+        assert_eq!(both_accept(7, 2, 3, 253), (false, false));
+    }
+
+    #[test]
+    fn both_reject_tampered_proofs() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let c = Fp::from(252);
+
+        // A real halo2 proof over Pasta with the IPA commitment scheme
+        let params = Params::<EqAffine>::new(4);
+        let circuit = MyCircuit {
+            constant: Fp::from(7),
+            a: Value::known(Fp::from(2)),
+            b: Value::known(Fp::from(3)),
+        };
+        let vk = keygen_vk(&params, &circuit).unwrap();
+        let pk = keygen_pk(&params, vk, &circuit).unwrap();
+        let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+        create_proof(
+            &params,
+            &pk,
+            &[circuit],
+            &[&[&[c]]],
+            &mut rng,
+            &mut transcript,
+        )
+        .unwrap();
+        let proof = transcript.finalize();
+        let halo2_accepts = |proof: &[u8]| {
+            let strategy = SingleVerifier::new(&params);
+            let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(proof);
+            verify_proof(&params, pk.get_vk(), strategy, &[&[&[c]]], &mut transcript).is_ok()
+        };
+        assert!(halo2_accepts(&proof));
+        let mut tampered = proof.clone();
+        // The first byte of the last evaluation sent by the prover
+        let last = tampered.len() - 32;
+        tampered[last] ^= 1;
+        assert!(!halo2_accepts(&tampered));
+
+        let (ck, kzg, pk) = plonk_keys(7, &mut rng);
+        let witness = scaled_square_product_witness(Fr::from(7u64), Fr::from(2u64), Fr::from(3u64));
+        let proof = plonk::prove(&ck, &pk, &witness, &mut rng).unwrap();
+        assert!(plonk::verify(&kzg, &pk.vk, &[Fr::from(252u64)], &proof));
+        let mut tampered = proof;
+        tampered.evaluations.a += Fr::from(1u64);
+        assert!(!plonk::verify(&kzg, &pk.vk, &[Fr::from(252u64)], &tampered));
+    }
 }
@@ -0,0 +1,160 @@
+//! A `meta.lookup` constraint that every value of an advice column is in a
+//! fixed table, checked against the lookup argument of the
+//! `univariate-polynomial-iop-zerotest` crate on the same table.
+
+use halo2_proofs::{
+    arithmetic::Field,
+    circuit::{Layouter, SimpleFloorPlanner, Value},
+    plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Selector, TableColumn},
+    poly::Rotation,
+};
+
+#[derive(Clone, Debug)]
+struct LookupConfig {
+    input: Column<Advice>,
+    table: TableColumn,
+    // Lookups need a complex selector, since it appears inside the looked
+    // up expression rather than as a factor of a gate
+    s_lookup: Selector,
+}
+
+/// Looks up every value of `input` in `table`
+struct LookupCircuit<F: Field> {
+    table: Vec<F>,
+    input: Vec<Value<F>>,
+}
+
+impl<F: Field> Circuit<F> for LookupCircuit<F> {
+    type Config = LookupConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            table: self.table.clone(),
+            input: vec![Value::unknown(); self.input.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let input = meta.advice_column();
+        let table = meta.lookup_table_column();
+        let s_lookup = meta.complex_selector();
+
+        // Where the selector is off the looked up value is zero, so the
+        // table has to contain zero
+        meta.lookup(|meta| {
+            let s_lookup = meta.query_selector(s_lookup);
+            let value = meta.query_advice(input, Rotation::cur());
+            vec![(s_lookup * value, table)]
+        });
+
+        LookupConfig {
+            input,
+            table,
+            s_lookup,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        layouter.assign_table(
+            || "table",
+            |mut table| {
+                for (offset, value) in self.table.iter().enumerate() {
+                    table.assign_cell(
+                        || "table value",
+                        config.table,
+                        offset,
+                        || Value::known(*value),
+                    )?;
+                }
+                Ok(())
+            },
+        )?;
+
+        layouter.assign_region(
+            || "input",
+            |mut region| {
+                for (offset, value) in self.input.iter().enumerate() {
+                    config.s_lookup.enable(&mut region, offset)?;
+                    region.assign_advice(|| "input value", config.input, offset, || *value)?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::Halo2Field;
+    use halo2_proofs::{
+        arithmetic::Field as _, circuit::Value, dev::MockProver, pasta::group::ff::PrimeField as _,
+        pasta::Fp,
+    };
+    use polynomial::Polynomial;
+    use univariate_polynomial_iop_zerotest::{
+        domain::Domain,
+        lookup::{LookupError, LookupProver, LookupVerifier},
+    };
+
+    use super::LookupCircuit;
+
+    const TABLE: [u64; 8] = [0, 1, 2, 3, 5, 8, 13, 21];
+
+    /// Runs `meta.lookup` in the mock prover and our own lookup argument
+    /// over `H` of order 8 in `Fp`, and returns whether each accepts
+    fn both_accept(input: [u64; 8]) -> (bool, bool) {
+        let table = TABLE.map(Fp::from);
+        let input = input.map(Fp::from);
+
+        let circuit = LookupCircuit {
+            table: table.to_vec(),
+            input: input.iter().copied().map(Value::known).collect(),
+        };
+        let mock = MockProver::run(4, &circuit, vec![])
+            .unwrap()
+            .verify()
+            .is_ok();
+
+        let omega = Fp::ROOT_OF_UNITY.pow_vartime([1 << (Fp::S - 3)]);
+        let domain = Domain::new(Halo2Field(omega), 8);
+        let column = |values: [Fp; 8]| -> Polynomial<Halo2Field<Fp>> {
+            domain.interpolate(&values.map(Halo2Field))
+        };
+        let (input, table) = (column(input), column(table));
+        let prover = LookupProver::new(domain, input.clone(), table.clone());
+        let ours = match prover.permuted() {
+            Ok((permuted_input, permuted_table)) => {
+                let (beta, gamma) = (Halo2Field(Fp::from(1000)), Halo2Field(Fp::from(2000)));
+                let proof = prover.prove(beta, gamma).unwrap();
+                LookupVerifier::new(domain).verify(
+                    &input,
+                    &table,
+                    &permuted_input,
+                    &permuted_table,
+                    &proof,
+                    beta,
+                    gamma,
+                    Halo2Field(Fp::from(3000)),
+                )
+            }
+            Err(error) => {
+                assert_eq!(error, LookupError::NotInTable);
+                false
+            }
+        };
+        (mock, ours)
+    }
+
+    #[test]
+    fn lookup_agrees_with_mock_prover() {
+        assert_eq!(both_accept([8, 1, 21, 8, 8, 2, 13, 0]), (true, true));
+        assert_eq!(both_accept([5, 5, 5, 5, 5, 5, 5, 5]), (true, true));
+        // 4 is not in the table
+        assert_eq!(both_accept([8, 1, 21, 4, 8, 2, 13, 0]), (false, false));
+    }
+}
@@ -0,0 +1,118 @@
+//! Hash functions a [`MerkleTree`](crate::MerkleTree) can be built with.
+
+use std::{fmt::Debug, marker::PhantomData};
+
+use blake2::{Blake2b512, Blake2s256};
+use finite_fields::Field;
+use sha2::{
+    digest::{Digest, Output},
+    Sha256,
+};
+
+/// Hashes leaves of field elements and compresses pairs of digests into
+/// their parent
+pub trait MerkleHasher {
+    type Digest: Clone + Debug + PartialEq;
+
+    fn hash_leaf<F: Field>(leaf: &[F]) -> Self::Digest;
+
+    fn compress(left: &Self::Digest, right: &Self::Digest) -> Self::Digest;
+
+    /// Byte encoding of a digest, e.g. to absorb a root into a transcript
+    fn digest_to_bytes(digest: &Self::Digest) -> Vec<u8>;
+}
+
+/// Any byte-oriented hash function of the `digest` crate. Leaves and inner
+/// nodes are hashed with different prefixes, so that a leaf can never be
+/// passed off as an inner node.
+#[derive(Debug, Clone, Copy)]
+pub struct DigestHasher<D>(PhantomData<D>);
+
+pub type Sha256Hasher = DigestHasher<Sha256>;
+pub type Blake2sHasher = DigestHasher<Blake2s256>;
+pub type Blake2bHasher = DigestHasher<Blake2b512>;
+pub type Blake3Hasher = DigestHasher<blake3::Hasher>;
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+impl<D: Digest> MerkleHasher for DigestHasher<D> {
+    type Digest = Output<D>;
+
+    /// Hashes the little-endian limbs of the elements
+    fn hash_leaf<F: Field>(leaf: &[F]) -> Self::Digest {
+        let mut hasher = D::new_with_prefix([LEAF_PREFIX]);
+        for element in leaf {
+            for limb in element.to_limbs() {
+                hasher.update(limb.to_le_bytes());
+            }
+        }
+        hasher.finalize()
+    }
+
+    fn compress(left: &Self::Digest, right: &Self::Digest) -> Self::Digest {
+        D::new_with_prefix([NODE_PREFIX])
+            .chain_update(left)
+            .chain_update(right)
+            .finalize()
+    }
+
+    fn digest_to_bytes(digest: &Self::Digest) -> Vec<u8> {
+        digest.to_vec()
+    }
+}
+
+#[cfg(feature = "plonky2")]
+pub use poseidon::PoseidonHasher;
+
+#[cfg(feature = "plonky2")]
+mod poseidon {
+    use plonky2::{
+        field::{
+            goldilocks_field::GoldilocksField,
+            types::{Field as _, Field64},
+        },
+        hash::{hash_types::HashOut, poseidon::PoseidonHash},
+        plonk::config::{GenericHashOut, Hasher},
+    };
+
+    use super::*;
+
+    /// The Poseidon permutation of `plonky2` over Goldilocks, which is
+    /// cheap to verify inside a circuit over the same field. Unlike with
+    /// [`DigestHasher`], leaves and nodes are not domain separated, as in
+    /// `plonky2` itself: the verifier fixes the depth of the tree and the
+    /// width of the leaves instead.
+    #[derive(Debug, Clone, Copy)]
+    pub struct PoseidonHasher;
+
+    impl MerkleHasher for PoseidonHasher {
+        type Digest = HashOut<GoldilocksField>;
+
+        /// Elements of Goldilocks itself are absorbed as they are, the
+        /// limbs of other fields as two 32-bit halves each
+        fn hash_leaf<F: Field>(leaf: &[F]) -> Self::Digest {
+            let native = F::characteristic() == [GoldilocksField::ORDER];
+            let inputs: Vec<GoldilocksField> = leaf
+                .iter()
+                .flat_map(|element| element.to_limbs())
+                .flat_map(|limb| match native {
+                    true => vec![GoldilocksField::from_canonical_u64(limb)],
+                    false => vec![
+                        GoldilocksField::from_canonical_u64(limb & 0xffff_ffff),
+                        GoldilocksField::from_canonical_u64(limb >> 32),
+                    ],
+                })
+                .collect();
+            PoseidonHash::hash_no_pad(&inputs)
+        }
+
+        fn compress(left: &Self::Digest, right: &Self::Digest) -> Self::Digest {
+            PoseidonHash::two_to_one(*left, *right)
+        }
+
+        fn digest_to_bytes(digest: &Self::Digest) -> Vec<u8> {
+            digest.to_bytes()
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! Merkle trees over rows of field elements, the commitment that compiles
+//! the oracles of an IOP with hashes only, e.g. to Reed–Solomon codewords
+//! in FRI.
+//!
+//! The tree is generic over a [`MerkleHasher`]: SHA-256, Blake2 and Blake3
+//! through [`DigestHasher`], and Poseidon over Goldilocks (behind the
+//! `plonky2` feature). Leaves can be opened one at a time with a
+//! [`MerklePath`], or several at once with a [`MultiProof`] that leaves out
+//! every node the verifier can compute from the opened leaves.
+
+pub mod hash;
+
+#[cfg(feature = "plonky2")]
+pub use hash::PoseidonHasher;
+pub use hash::{
+    Blake2bHasher, Blake2sHasher, Blake3Hasher, DigestHasher, MerkleHasher, Sha256Hasher,
+};
+
+use finite_fields::Field;
+
+/// A complete binary tree over a power of two of leaves
+#[derive(Debug, Clone)]
+pub struct MerkleTree<H: MerkleHasher> {
+    /// The digests of each level, from the leaves up to the root
+    layers: Vec<Vec<H::Digest>>,
+}
+
+/// The siblings of the nodes on the way from a leaf to the root
+#[derive(Debug, Clone)]
+pub struct MerklePath<H: MerkleHasher> {
+    pub siblings: Vec<H::Digest>,
+}
+
+/// The siblings needed to authenticate several leaves at once, level by
+/// level and by increasing index within a level
+#[derive(Debug, Clone)]
+pub struct MultiProof<H: MerkleHasher> {
+    pub siblings: Vec<H::Digest>,
+}
+
+impl<H: MerkleHasher> MerkleTree<H> {
+    /// Commits to `leaves`, whose number must be a power of two
+    pub fn new<F: Field>(leaves: &[Vec<F>]) -> Self {
+        assert!(
+            leaves.len().is_power_of_two(),
+            "the number of leaves must be a power of two"
+        );
+        let mut layers = vec![leaves
+            .iter()
+            .map(|leaf| H::hash_leaf(leaf))
+            .collect::<Vec<_>>()];
+        while layers.last().unwrap().len() > 1 {
+            let parents = layers
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| H::compress(&pair[0], &pair[1]))
+                .collect();
+            layers.push(parents);
+        }
+        Self { layers }
+    }
+
+    /// Commits to evaluations, one per leaf
+    pub fn from_evaluations<F: Field>(evaluations: &[F]) -> Self {
+        let leaves: Vec<Vec<F>> = evaluations.iter().map(|value| vec![*value]).collect();
+        Self::new(&leaves)
+    }
+
+    pub fn root(&self) -> H::Digest {
+        self.layers.last().unwrap()[0].clone()
+    }
+
+    /// The number of levels above the leaves
+    pub fn depth(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    pub fn num_leaves(&self) -> usize {
+        self.layers[0].len()
+    }
+
+    pub fn open(&self, index: usize) -> MerklePath<H> {
+        assert!(index < self.num_leaves(), "leaf index out of range");
+        let siblings = self.layers[..self.depth()]
+            .iter()
+            .enumerate()
+            .map(|(level, layer)| layer[(index >> level) ^ 1].clone())
+            .collect();
+        MerklePath { siblings }
+    }
+
+    /// Opens all leaves at `indices`, which may be unsorted and repeat
+    pub fn open_multi(&self, indices: &[usize]) -> MultiProof<H> {
+        assert!(
+            indices.iter().all(|index| *index < self.num_leaves()),
+            "leaf index out of range"
+        );
+        let mut known = indices.to_vec();
+        known.sort_unstable();
+        known.dedup();
+
+        let mut siblings = vec![];
+        for layer in &self.layers[..self.depth()] {
+            for (i, index) in known.iter().enumerate() {
+                let sibling = index ^ 1;
+                let sibling_known = match index % 2 {
+                    0 => known.get(i + 1) == Some(&sibling),
+                    _ => i > 0 && known[i - 1] == sibling,
+                };
+                if !sibling_known {
+                    siblings.push(layer[sibling].clone());
+                }
+            }
+            known = parents(&known);
+        }
+        MultiProof { siblings }
+    }
+}
+
+/// `index >> bits`, which is zero rather than an overflow once `bits`
+/// reaches the width of `usize`, as the verifiers shift by lengths the
+/// prover chooses
+fn shift_right(index: usize, bits: usize) -> usize {
+    u32::try_from(bits)
+        .ok()
+        .and_then(|bits| index.checked_shr(bits))
+        .unwrap_or(0)
+}
+
+/// Indices of the parents of the sorted, distinct `indices`
+fn parents(indices: &[usize]) -> Vec<usize> {
+    let mut parents: Vec<usize> = indices.iter().map(|index| index / 2).collect();
+    parents.dedup();
+    parents
+}
+
+impl<H: MerkleHasher> MerklePath<H> {
+    /// Checks that `leaf` is at `index` of the tree with `root`. The depth
+    /// of the tree is that of the path, which the caller should check.
+    pub fn verify<F: Field>(&self, root: &H::Digest, index: usize, leaf: &[F]) -> bool {
+        if shift_right(index, self.siblings.len()) != 0 {
+            return false;
+        }
+        let node =
+            self.siblings
+                .iter()
+                .enumerate()
+                .fold(H::hash_leaf(leaf), |node, (level, sibling)| {
+                    match shift_right(index, level) % 2 {
+                        0 => H::compress(&node, sibling),
+                        _ => H::compress(sibling, &node),
+                    }
+                });
+        node == *root
+    }
+}
+
+impl<H: MerkleHasher> MultiProof<H> {
+    /// Checks that every `(index, leaf)` of `leaves` is in the tree of the
+    /// given depth with `root`. Leaves may come in any order, but opening
+    /// the same index to two different leaves fails.
+    pub fn verify<F: Field>(
+        &self,
+        root: &H::Digest,
+        depth: usize,
+        leaves: &[(usize, Vec<F>)],
+    ) -> bool {
+        let mut nodes: Vec<(usize, H::Digest)> = leaves
+            .iter()
+            .map(|(index, leaf)| (*index, H::hash_leaf(leaf)))
+            .collect();
+        nodes.sort_by_key(|(index, _)| *index);
+        if nodes
+            .windows(2)
+            .any(|w| w[0].0 == w[1].0 && w[0].1 != w[1].1)
+        {
+            return false;
+        }
+        nodes.dedup_by_key(|(index, _)| *index);
+        if nodes.is_empty() || shift_right(nodes.last().unwrap().0, depth) != 0 {
+            return false;
+        }
+
+        let mut siblings = self.siblings.iter();
+        for _ in 0..depth {
+            let mut next = Vec::with_capacity(nodes.len());
+            let mut i = 0;
+            while i < nodes.len() {
+                let (index, node) = &nodes[i];
+                let pair_known = index % 2 == 0 && nodes.get(i + 1).map(|n| n.0) == Some(index + 1);
+                let parent = if pair_known {
+                    i += 1;
+                    H::compress(node, &nodes[i].1)
+                } else {
+                    let Some(sibling) = siblings.next() else {
+                        return false;
+                    };
+                    match index % 2 {
+                        0 => H::compress(node, sibling),
+                        _ => H::compress(sibling, node),
+                    }
+                };
+                next.push((index / 2, parent));
+                i += 1;
+            }
+            nodes = next;
+        }
+        siblings.next().is_none() && nodes[0].1 == *root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+
+    type F = PrimeField<97>;
+
+    fn leaves(n: u64) -> Vec<Vec<F>> {
+        (0..n)
+            .map(|i| vec![F::from(i * i), F::from(i + 1)])
+            .collect()
+    }
+
+    fn paths_verify<H: MerkleHasher>() {
+        let leaves = leaves(16);
+        let tree = MerkleTree::<H>::new(&leaves);
+        assert_eq!(tree.depth(), 4);
+        let root = tree.root();
+        for (index, leaf) in leaves.iter().enumerate() {
+            let path = tree.open(index);
+            assert!(path.verify(&root, index, leaf));
+            assert!(!path.verify(&root, index ^ 1, leaf));
+            assert!(!path.verify(&root, index + 16, leaf));
+            assert!(!path.verify(&root, index, &[leaf[0], leaf[1] + F::from(1u64)]));
+        }
+        let mut path = tree.open(3);
+        path.siblings[2] = path.siblings[1].clone();
+        assert!(!path.verify(&root, 3, &leaves[3]));
+    }
+
+    #[test]
+    fn sha256_paths() {
+        paths_verify::<Sha256Hasher>();
+    }
+
+    #[test]
+    fn blake2_paths() {
+        paths_verify::<Blake2sHasher>();
+        paths_verify::<Blake2bHasher>();
+    }
+
+    #[test]
+    fn blake3_paths() {
+        paths_verify::<Blake3Hasher>();
+    }
+
+    #[cfg(feature = "plonky2")]
+    #[test]
+    fn poseidon_paths() {
+        use finite_fields::Plonky2Field;
+        use plonky2::field::goldilocks_field::GoldilocksField;
+
+        paths_verify::<PoseidonHasher>();
+
+        let evaluations: Vec<Plonky2Field<GoldilocksField>> =
+            (0..8u64).map(|i| Plonky2Field::from(i.pow(3))).collect();
+        let tree = MerkleTree::<PoseidonHasher>::from_evaluations(&evaluations);
+        let proof = tree.open_multi(&[1, 6]);
+        let opened = vec![(1, vec![evaluations[1]]), (6, vec![evaluations[6]])];
+        assert!(proof.verify(&tree.root(), 3, &opened));
+    }
+
+    #[test]
+    fn multi_proofs() {
+        let leaves = leaves(16);
+        let tree = MerkleTree::<Sha256Hasher>::new(&leaves);
+        let root = tree.root();
+        let opening = |indices: &[usize]| -> Vec<(usize, Vec<F>)> {
+            indices.iter().map(|i| (*i, leaves[*i].clone())).collect()
+        };
+
+        for indices in [vec![5], vec![0, 1], vec![2, 3, 9, 15], vec![11, 4, 11, 7]] {
+            let proof = tree.open_multi(&indices);
+            assert!(proof.verify(&root, 4, &opening(&indices)));
+            assert!(!proof.verify(&root, 3, &opening(&indices)));
+        }
+
+        // Siblings shared between paths are sent once, and siblings that are
+        // themselves opened not at all
+        assert_eq!(tree.open_multi(&[0, 1]).siblings.len(), 3);
+        assert_eq!(tree.open_multi(&[0, 2]).siblings.len(), 4);
+        assert_eq!(
+            tree.open_multi(&(0..16).collect::<Vec<_>>()).siblings.len(),
+            0
+        );
+
+        let proof = tree.open_multi(&[2, 3, 9]);
+        let mut wrong = opening(&[2, 3, 9]);
+        wrong[2].1[0] += F::from(1u64);
+        assert!(!proof.verify(&root, 4, &wrong));
+        assert!(!proof.verify(&root, 4, &opening(&[2, 3])));
+        assert!(!proof.verify(&root, 4, &opening(&[2, 3, 8])));
+
+        // The same index opened to two different leaves
+        let mut duplicate = opening(&[2, 3, 9]);
+        duplicate.push((9, leaves[8].clone()));
+        assert!(!proof.verify(&root, 4, &duplicate));
+
+        let mut extra = proof.clone();
+        extra.siblings.push(root);
+        assert!(!extra.verify(&root, 4, &opening(&[2, 3, 9])));
+    }
+
+    #[test]
+    fn overlong_proofs_are_rejected() {
+        let leaves = leaves(4);
+        let tree = MerkleTree::<Sha256Hasher>::new(&leaves);
+        let root = tree.root();
+        let mut path = tree.open(2);
+        path.siblings.resize(70, root);
+        assert!(!path.verify(&root, 2, &leaves[2]));
+        assert!(!path.verify(&root, usize::MAX, &leaves[2]));
+
+        let proof = tree.open_multi(&[2]);
+        let opened = [(2, leaves[2].clone())];
+        assert!(!proof.verify(&root, 64, &opened));
+        assert!(!proof.verify(&root, usize::MAX, &opened));
+    }
+
+    #[test]
+    fn single_leaf_tree() {
+        let tree = MerkleTree::<Sha256Hasher>::from_evaluations(&[F::from(7u64)]);
+        assert_eq!(tree.depth(), 0);
+        assert_eq!(tree.root(), Sha256Hasher::hash_leaf(&[F::from(7u64)]));
+        assert!(tree.open(0).verify(&tree.root(), 0, &[F::from(7u64)]));
+        assert!(tree
+            .open_multi(&[0])
+            .verify(&tree.root(), 0, &[(0, vec![F::from(7u64)])]));
+    }
+}
@@ -0,0 +1,9 @@
+//! Interactive proofs over the boolean hypercube, written for any
+//! [`finite_fields::Field`]: multilinear polynomials given by their
+//! evaluations on `{0,1}^n`, the sumcheck protocol for combinations of
+//! them, and the LogUp lookup argument built on top. Challenges come from a
+//! Fiat–Shamir [`fiat_shamir::Transcript`].
+
+pub mod logup;
+pub mod multilinear;
+pub mod sumcheck;
@@ -0,0 +1,348 @@
+//! LogUp over the boolean hypercube: the prover convinces the verifier that
+//! every value of a witness `f` on `{0,1}^n` is contained in the values of
+//! a table `t` on `{0,1}^n`, from
+//!
+//! `Σ_x 1 / (β + f(x)) = Σ_x m(x) / (β + t(x))`
+//!
+//! for the multiplicities `m` of the table values in the witness, exactly
+//! as in the univariate `logup` of the zerotest crate. Here the helpers
+//! `h_f = 1 / (β + f)` and `h_t = m / (β + t)` on the hypercube are checked
+//! with a zero-check, a sumcheck against `eq(z, x)` for a random `z`, and
+//! folded into a single sumcheck of degree 3 with the sum of `h_f - h_t`:
+//!
+//! `Σ_x eq(z, x) (h_f (β + f) - 1 + λ (h_t (β + t) - m)) + μ (h_f - h_t) = 0`.
+//!
+//! The multiplicities and helpers are sent in the clear and queried once at
+//! the random point of the sumcheck; a multilinear polynomial commitment
+//! would take their place in a SNARK. The prover is linear in the size of
+//! the table, without any FFT.
+
+use std::collections::HashMap;
+
+use fiat_shamir::Transcript;
+use finite_fields::Field;
+
+use crate::{
+    multilinear::{append_multilinear, eq, MultilinearPolynomial},
+    sumcheck::{SumcheckProof, SumcheckProver, SumcheckVerifier},
+};
+
+const LABEL: &[u8] = b"logup-hypercube";
+
+/// Degree of the summand in the variables of the hypercube
+const DEGREE: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogUpError {
+    /// Some witness value does not appear in the table
+    NotInTable,
+    /// `β` is minus a value of the witness or the table; the verifier must
+    /// resample
+    DegenerateChallenge,
+}
+
+/// How often each entry of `table` occurs in `witness`. A value repeated in
+/// the table is counted at its first occurrence only.
+pub fn multiplicities<F: Field>(witness: &[F], table: &[F]) -> Result<Vec<F>, LogUpError> {
+    let mut counts: HashMap<Vec<u64>, u64> = HashMap::new();
+    for value in witness {
+        *counts.entry(value.to_limbs()).or_default() += 1;
+    }
+    let multiplicities = table
+        .iter()
+        .map(|value| F::from_u64(counts.remove(&value.to_limbs()).unwrap_or(0)))
+        .collect();
+    match counts.is_empty() {
+        true => Ok(multiplicities),
+        false => Err(LogUpError::NotInTable),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogUpProof<F> {
+    /// `m`
+    pub multiplicities: MultilinearPolynomial<F>,
+    /// `h_f`
+    pub witness_helper: MultilinearPolynomial<F>,
+    /// `h_t`
+    pub table_helper: MultilinearPolynomial<F>,
+    pub sumcheck: SumcheckProof<F>,
+}
+
+/// The challenges `β`, `λ`, `μ` and `z`
+struct Challenges<F> {
+    beta: F,
+    lambda: F,
+    mu: F,
+    zero_check: Vec<F>,
+}
+
+impl<F: Field> Challenges<F> {
+    /// The summand at `[eq(z, x), f, t, m, h_f, h_t]`
+    fn summand(&self, values: &[F]) -> F {
+        let [eq, f, t, m, witness_helper, table_helper] = [0, 1, 2, 3, 4, 5].map(|i| values[i]);
+        let witness = witness_helper * (self.beta + f) - F::one();
+        let table = table_helper * (self.beta + t) - m;
+        eq * (witness + self.lambda * table) + self.mu * (witness_helper - table_helper)
+    }
+}
+
+fn transcript<F: Field>(f: &MultilinearPolynomial<F>, t: &MultilinearPolynomial<F>) -> Transcript {
+    let mut transcript = Transcript::new(LABEL);
+    append_multilinear(&mut transcript, b"witness", f);
+    append_multilinear(&mut transcript, b"table", t);
+    transcript
+}
+
+/// Draws `β` after the multiplicities, and the other challenges after the
+/// helpers
+fn challenges<F: Field>(
+    transcript: &mut Transcript,
+    num_vars: usize,
+    multiplicities: &MultilinearPolynomial<F>,
+    witness_helper: &MultilinearPolynomial<F>,
+    table_helper: &MultilinearPolynomial<F>,
+) -> Challenges<F> {
+    append_multilinear(transcript, b"multiplicities", multiplicities);
+    let beta = transcript.challenge(b"beta");
+    append_multilinear(transcript, b"witness helper", witness_helper);
+    append_multilinear(transcript, b"table helper", table_helper);
+    Challenges {
+        beta,
+        lambda: transcript.challenge(b"lambda"),
+        mu: transcript.challenge(b"mu"),
+        zero_check: (0..num_vars).map(|_| transcript.challenge(b"z")).collect(),
+    }
+}
+
+/// Proves that every value of `f` is a value of `t`, both on the same
+/// hypercube
+pub fn prove<F: Field>(
+    f: &MultilinearPolynomial<F>,
+    t: &MultilinearPolynomial<F>,
+) -> Result<LogUpProof<F>, LogUpError> {
+    assert_eq!(
+        f.num_vars(),
+        t.num_vars(),
+        "witness and table on the same hypercube"
+    );
+    let counts = multiplicities(f.evaluations(), t.evaluations())?;
+    let multiplicities = MultilinearPolynomial::new(counts.clone());
+    // The same `β` as `challenges` draws after the multiplicities
+    let mut transcript = transcript(f, t);
+    append_multilinear(&mut transcript, b"multiplicities", &multiplicities);
+    let beta: F = transcript.challenge(b"beta");
+
+    let inverses = |p: &MultilinearPolynomial<F>| -> Result<Vec<F>, LogUpError> {
+        p.evaluations()
+            .iter()
+            .map(|value| {
+                (beta + *value)
+                    .inverse()
+                    .ok_or(LogUpError::DegenerateChallenge)
+            })
+            .collect()
+    };
+    let witness_helper = MultilinearPolynomial::new(inverses(f)?);
+    let table_helper = MultilinearPolynomial::new(
+        inverses(t)?
+            .into_iter()
+            .zip(counts)
+            .map(|(inverse, count)| inverse * count)
+            .collect(),
+    );
+    prove_with_helpers(f, t, multiplicities, witness_helper, table_helper)
+}
+
+fn prove_with_helpers<F: Field>(
+    f: &MultilinearPolynomial<F>,
+    t: &MultilinearPolynomial<F>,
+    multiplicities: MultilinearPolynomial<F>,
+    witness_helper: MultilinearPolynomial<F>,
+    table_helper: MultilinearPolynomial<F>,
+) -> Result<LogUpProof<F>, LogUpError> {
+    let mut transcript = transcript(f, t);
+    let challenges = challenges(
+        &mut transcript,
+        f.num_vars(),
+        &multiplicities,
+        &witness_helper,
+        &table_helper,
+    );
+    let prover = SumcheckProver::new(
+        vec![
+            MultilinearPolynomial::eq(&challenges.zero_check),
+            f.clone(),
+            t.clone(),
+            multiplicities.clone(),
+            witness_helper.clone(),
+            table_helper.clone(),
+        ],
+        DEGREE,
+        |values: &[F]| challenges.summand(values),
+    );
+    if prover.sum() != F::zero() {
+        return Err(LogUpError::NotInTable);
+    }
+    let (sumcheck, _) = prover.prove(&mut transcript);
+    Ok(LogUpProof {
+        multiplicities,
+        witness_helper,
+        table_helper,
+        sumcheck,
+    })
+}
+
+/// Checks the sumcheck and its subclaim with one query to each of the
+/// witness, the table, the multiplicities and the helpers
+pub fn verify<F: Field>(
+    f: &MultilinearPolynomial<F>,
+    t: &MultilinearPolynomial<F>,
+    proof: &LogUpProof<F>,
+) -> bool {
+    let num_vars = f.num_vars();
+    let sent = [
+        &proof.multiplicities,
+        &proof.witness_helper,
+        &proof.table_helper,
+    ];
+    if t.num_vars() != num_vars || sent.iter().any(|p| p.num_vars() != num_vars) {
+        return false;
+    }
+    let mut transcript = transcript(f, t);
+    let challenges = challenges(
+        &mut transcript,
+        num_vars,
+        &proof.multiplicities,
+        &proof.witness_helper,
+        &proof.table_helper,
+    );
+    let Some(subclaim) =
+        SumcheckVerifier::new(num_vars, DEGREE).verify(&mut transcript, F::zero(), &proof.sumcheck)
+    else {
+        return false;
+    };
+    let point = &subclaim.point;
+    let values = [
+        eq(&challenges.zero_check, point),
+        f.evaluate(point),
+        t.evaluate(point),
+        proof.multiplicities.evaluate(point),
+        proof.witness_helper.evaluate(point),
+        proof.table_helper.evaluate(point),
+    ];
+    challenges.summand(&values) == subclaim.value
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+
+    type Fq = PrimeField<2147483647>;
+
+    const TABLE: [u64; 8] = [0, 1, 2, 3, 5, 8, 13, 21];
+
+    fn column(values: [u64; 8]) -> MultilinearPolynomial<Fq> {
+        MultilinearPolynomial::new(values.map(Fq::from_u64).to_vec())
+    }
+
+    #[test]
+    fn contained_witness_is_accepted() {
+        let f = column([8, 1, 21, 8, 8, 2, 13, 8]);
+        let t = column(TABLE);
+        let proof = prove(&f, &t).unwrap();
+        assert_eq!(proof.multiplicities, column([0, 1, 1, 0, 0, 4, 1, 1]));
+        assert!(verify(&f, &t, &proof));
+
+        // The proof is bound to the witness
+        let other = column([8, 1, 21, 8, 8, 2, 13, 13]);
+        assert!(!verify(&other, &t, &proof));
+    }
+
+    #[test]
+    fn missing_value_is_rejected() {
+        let f = column([8, 1, 21, 8, 4, 2, 13, 8]);
+        let t = column(TABLE);
+        assert_eq!(prove(&f, &t).unwrap_err(), LogUpError::NotInTable);
+
+        // Counting the 4 as a 5 gives honest helpers, so the zero-check
+        // part vanishes, but the sums differ by `1 / (β + 4) - 1 / (β + 5)`
+        let multiplicities = column([0, 1, 1, 0, 1, 3, 1, 1]);
+        let beta: Fq = {
+            let mut transcript = transcript(&f, &t);
+            append_multilinear(&mut transcript, b"multiplicities", &multiplicities);
+            transcript.challenge(b"beta")
+        };
+        let helper = |p: &MultilinearPolynomial<Fq>, weights: &MultilinearPolynomial<Fq>| {
+            MultilinearPolynomial::new(
+                p.evaluations()
+                    .iter()
+                    .zip(weights.evaluations())
+                    .map(|(value, weight)| *weight * (beta + *value).inverse().unwrap())
+                    .collect(),
+            )
+        };
+        let witness_helper = helper(&f, &column([1; 8]));
+        let table_helper = helper(&t, &multiplicities);
+        assert_eq!(
+            prove_with_helpers(
+                &f,
+                &t,
+                multiplicities.clone(),
+                witness_helper.clone(),
+                table_helper.clone()
+            )
+            .unwrap_err(),
+            LogUpError::NotInTable
+        );
+
+        // Running the sumcheck anyway for the actual, non-zero sum fails
+        // its first round
+        let mut transcript = transcript(&f, &t);
+        let challenges = challenges(
+            &mut transcript,
+            3,
+            &multiplicities,
+            &witness_helper,
+            &table_helper,
+        );
+        let prover = SumcheckProver::new(
+            vec![
+                MultilinearPolynomial::eq(&challenges.zero_check),
+                f.clone(),
+                t.clone(),
+                multiplicities.clone(),
+                witness_helper.clone(),
+                table_helper.clone(),
+            ],
+            DEGREE,
+            |values: &[Fq]| challenges.summand(values),
+        );
+        assert_ne!(prover.sum(), Fq::from_u64(0));
+        let (sumcheck, _) = prover.prove(&mut transcript);
+        let proof = LogUpProof {
+            multiplicities,
+            witness_helper,
+            table_helper,
+            sumcheck,
+        };
+        assert!(!verify(&f, &t, &proof));
+    }
+
+    #[test]
+    fn tampered_helper_is_rejected() {
+        let f = column([8, 1, 21, 8, 8, 2, 13, 8]);
+        let t = column(TABLE);
+        let mut proof = prove(&f, &t).unwrap();
+
+        // Adding one to a helper at one point and subtracting it at another
+        // keeps the sum of `h_f - h_t`, but not the zero-check
+        let mut values = proof.witness_helper.evaluations().to_vec();
+        values[0] += Fq::from_u64(1);
+        values[1] -= Fq::from_u64(1);
+        proof.witness_helper = MultilinearPolynomial::new(values);
+        assert!(!verify(&f, &t, &proof));
+    }
+}
@@ -0,0 +1,145 @@
+use fiat_shamir::Transcript;
+use finite_fields::Field;
+
+/// A multilinear polynomial in `n` variables, given by its evaluations on
+/// the boolean hypercube `{0, 1}^n`. The point `(x_1, ..., x_n)` sits at
+/// index `Σ_j x_j 2^(n-j)`, so `x_1` is the most significant bit and fixing
+/// it keeps either half of the evaluations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultilinearPolynomial<F> {
+    evaluations: Vec<F>,
+    num_vars: usize,
+}
+
+impl<F: Field> MultilinearPolynomial<F> {
+    /// The multilinear extension of `evaluations` on the hypercube
+    pub fn new(evaluations: Vec<F>) -> Self {
+        assert!(
+            evaluations.len().is_power_of_two(),
+            "one evaluation per point of the hypercube"
+        );
+        let num_vars = evaluations.len().trailing_zeros() as usize;
+        Self {
+            evaluations,
+            num_vars,
+        }
+    }
+
+    /// `eq(z, x) = Π_j (z_j x_j + (1 - z_j)(1 - x_j))`, which is one at
+    /// `x = z` and zero elsewhere on the hypercube if `z` is boolean
+    pub fn eq(point: &[F]) -> Self {
+        Self::new(eq_evaluations(point))
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    pub fn evaluations(&self) -> &[F] {
+        &self.evaluations
+    }
+
+    /// Sum over the hypercube
+    pub fn sum(&self) -> F {
+        self.evaluations
+            .iter()
+            .fold(F::zero(), |acc, value| acc + *value)
+    }
+
+    /// The polynomial in `x_2, ..., x_n` with `x_1 = r`, interpolating
+    /// linearly between the halves with `x_1 = 0` and `x_1 = 1`
+    pub fn fix_first(&self, r: F) -> Self {
+        assert!(self.num_vars > 0, "no variable left to fix");
+        let (low, high) = self.evaluations.split_at(self.evaluations.len() / 2);
+        Self::new(
+            low.iter()
+                .zip(high)
+                .map(|(low, high)| *low + r * (*high - *low))
+                .collect(),
+        )
+    }
+
+    /// Evaluates at any point of `F^n` by fixing one variable after the
+    /// other, in `O(2^n)`
+    pub fn evaluate(&self, point: &[F]) -> F {
+        assert_eq!(point.len(), self.num_vars, "one coordinate per variable");
+        point
+            .iter()
+            .fold(self.clone(), |p, r| p.fix_first(*r))
+            .evaluations[0]
+    }
+}
+
+/// `eq(r, x) = Π_j (r_j x_j + (1 - r_j)(1 - x_j))` for every `x` of the
+/// hypercube, with `x_1` the most significant bit of the index
+pub fn eq_evaluations<F: Field>(point: &[F]) -> Vec<F> {
+    point.iter().rev().fold(vec![F::one()], |acc, r| {
+        acc.iter()
+            .map(|value| *value * (F::one() - *r))
+            .chain(acc.iter().map(|value| *value * *r))
+            .collect()
+    })
+}
+
+/// `eq(a, b)` at two points of `F^n`, in `O(n)`
+pub fn eq<F: Field>(a: &[F], b: &[F]) -> F {
+    assert_eq!(a.len(), b.len());
+    a.iter().zip(b).fold(F::one(), |acc, (a, b)| {
+        acc * (*a * *b + (F::one() - *a) * (F::one() - *b))
+    })
+}
+
+/// Absorbs the evaluations of a polynomial sent in the clear
+pub(crate) fn append_multilinear<F: Field>(
+    transcript: &mut Transcript,
+    label: &[u8],
+    polynomial: &MultilinearPolynomial<F>,
+) {
+    transcript.append_message(label, &(polynomial.num_vars as u64).to_le_bytes());
+    for value in &polynomial.evaluations {
+        transcript.append_field(b"evaluation", value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+
+    type Fq = PrimeField<41>;
+
+    fn poly(values: &[u64]) -> MultilinearPolynomial<Fq> {
+        MultilinearPolynomial::new(values.iter().map(|v| Fq::from_u64(*v)).collect())
+    }
+
+    #[test]
+    fn evaluation_extends_the_hypercube() {
+        // p(x_1, x_2) = 3 + 2 x_2 + 5 x_1 + 7 x_1 x_2
+        let p = poly(&[3, 5, 8, 17]);
+        let at = |x: u64, y: u64| p.evaluate(&[Fq::from_u64(x), Fq::from_u64(y)]);
+        assert_eq!(at(1, 0), Fq::from_u64(8));
+        assert_eq!(at(2, 3), Fq::from_u64(3 + 6 + 10 + 42));
+        assert_eq!(
+            p.fix_first(Fq::from_u64(2)).evaluate(&[Fq::from_u64(3)]),
+            at(2, 3)
+        );
+        assert_eq!(p.sum(), Fq::from_u64(33));
+    }
+
+    #[test]
+    fn eq_selects_a_point() {
+        let z = [5u64, 9, 2].map(Fq::from_u64);
+        let table = MultilinearPolynomial::eq(&z);
+        assert_eq!(
+            table.evaluations()[0b110],
+            eq(&z, &[1u64, 1, 0].map(Fq::from_u64))
+        );
+        assert_eq!(table.sum(), Fq::from_u64(1));
+
+        let x = [3u64, 4, 7].map(Fq::from_u64);
+        assert_eq!(table.evaluate(&x), eq(&z, &x));
+        let boolean = MultilinearPolynomial::eq(&[1u64, 0].map(Fq::from_u64));
+        assert_eq!(boolean, poly(&[0, 0, 1, 0]));
+    }
+}
@@ -0,0 +1,276 @@
+//! The sumcheck protocol of Lund, Fortnow, Karloff and Nisan: the prover
+//! convinces the verifier that
+//!
+//! `Σ_(x ∈ {0,1}^n) g(p_1(x), ..., p_k(x)) = s`
+//!
+//! for multilinear `p_i` and a combination `g` of total degree `d`, such as
+//! a product. In round `j` the prover sends the univariate
+//!
+//! `g_j(X) = Σ_(x ∈ {0,1}^(n-j)) g(p_1(r_1, ..., r_(j-1), X, x), ...)`
+//!
+//! of degree `d` as its values at `0, 1, ..., d`. The verifier checks
+//! `g_j(0) + g_j(1)` against the previous claim, replies with a random
+//! `r_j` and continues with the claim `g_j(r_j)`. After `n` rounds, what is
+//! left is the claim `g(p_1(r), ..., p_k(r)) = g_n(r_n)` at a single random
+//! point, which the caller settles with one query to each `p_i`. A cheating
+//! prover survives a round with probability at most `d / |F|`.
+
+use fiat_shamir::Transcript;
+use finite_fields::Field;
+
+use crate::multilinear::MultilinearPolynomial;
+
+/// The round polynomials `g_1, ..., g_n`, each by its values at
+/// `0, 1, ..., d`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SumcheckProof<F> {
+    pub rounds: Vec<Vec<F>>,
+}
+
+/// What the verifier is left with: `g(p_1(r), ..., p_k(r)) = value`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subclaim<F> {
+    pub point: Vec<F>,
+    pub value: F,
+}
+
+pub struct SumcheckProver<F, G> {
+    polynomials: Vec<MultilinearPolynomial<F>>,
+    degree: usize,
+    combine: G,
+}
+
+impl<F, G> SumcheckProver<F, G>
+where
+    F: Field,
+    G: Fn(&[F]) -> F,
+{
+    /// Sumcheck of `combine` applied to `polynomials`, all in the same
+    /// number of variables. `degree` bounds the degree of `combine` in each
+    /// of its arguments taken together.
+    pub fn new(polynomials: Vec<MultilinearPolynomial<F>>, degree: usize, combine: G) -> Self {
+        assert!(!polynomials.is_empty());
+        let num_vars = polynomials[0].num_vars();
+        assert!(
+            polynomials.iter().all(|p| p.num_vars() == num_vars),
+            "polynomials must have the same number of variables"
+        );
+        Self {
+            polynomials,
+            degree,
+            combine,
+        }
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.polynomials[0].num_vars()
+    }
+
+    /// `Σ_x g(p_1(x), ..., p_k(x))`
+    pub fn sum(&self) -> F {
+        (0..1 << self.num_vars()).fold(F::zero(), |acc, i| {
+            let values: Vec<F> = self
+                .polynomials
+                .iter()
+                .map(|p| p.evaluations()[i])
+                .collect();
+            acc + (self.combine)(&values)
+        })
+    }
+
+    /// The values at `0, 1, ..., d` of the next round polynomial. Every
+    /// `p_i` is linear in the first variable, so its value at `X = t` is
+    /// `low + t (high - low)` for the halves with `X = 0` and `X = 1`.
+    pub fn round_polynomial(&self) -> Vec<F> {
+        let half = 1 << (self.num_vars() - 1);
+        let mut round = vec![F::zero(); self.degree + 1];
+        let mut values = vec![F::zero(); self.polynomials.len()];
+        for i in 0..half {
+            let mut t = F::zero();
+            for value in round.iter_mut() {
+                for (slot, p) in values.iter_mut().zip(&self.polynomials) {
+                    let (low, high) = (p.evaluations()[i], p.evaluations()[i + half]);
+                    *slot = low + t * (high - low);
+                }
+                *value = *value + (self.combine)(&values);
+                t = t + F::one();
+            }
+        }
+        round
+    }
+
+    /// Fixes the first variable to the verifier's challenge
+    pub fn fix_first(&mut self, challenge: F) {
+        for p in self.polynomials.iter_mut() {
+            *p = p.fix_first(challenge);
+        }
+    }
+
+    /// Runs all rounds with the challenges drawn from `transcript`. Returns
+    /// the proof and the random point `r`.
+    pub fn prove(mut self, transcript: &mut Transcript) -> (SumcheckProof<F>, Vec<F>) {
+        let mut rounds = Vec::with_capacity(self.num_vars());
+        let mut point = Vec::with_capacity(self.num_vars());
+        while self.num_vars() > 0 {
+            let round = self.round_polynomial();
+            for value in &round {
+                transcript.append_field(b"round", value);
+            }
+            let challenge = transcript.challenge(b"r");
+            self.fix_first(challenge);
+            rounds.push(round);
+            point.push(challenge);
+        }
+        (SumcheckProof { rounds }, point)
+    }
+}
+
+/// Value at `point` of the polynomial of degree `d` with the given values
+/// at `0, 1, ..., d`, by Lagrange interpolation
+pub fn interpolate_at<F: Field>(values: &[F], point: F) -> F {
+    let nodes: Vec<F> = (0..values.len() as u64).map(F::from_u64).collect();
+    values
+        .iter()
+        .zip(&nodes)
+        .fold(F::zero(), |acc, (value, node)| {
+            let (numerator, denominator) = nodes
+                .iter()
+                .filter(|other| *other != node)
+                .fold((F::one(), F::one()), |(num, den), other| {
+                    (num * (point - *other), den * (*node - *other))
+                });
+            acc + *value
+                * numerator
+                * denominator
+                    .inverse()
+                    .expect("the characteristic exceeds the degree")
+        })
+}
+
+pub struct SumcheckVerifier {
+    num_vars: usize,
+    degree: usize,
+}
+
+impl SumcheckVerifier {
+    pub fn new(num_vars: usize, degree: usize) -> Self {
+        Self { num_vars, degree }
+    }
+
+    /// Checks every round against the claim so far, drawing the same
+    /// challenges as the prover. Returns the subclaim at the random point,
+    /// which the caller still has to check, or `None` if a round fails.
+    pub fn verify<F: Field>(
+        &self,
+        transcript: &mut Transcript,
+        claimed_sum: F,
+        proof: &SumcheckProof<F>,
+    ) -> Option<Subclaim<F>> {
+        if proof.rounds.len() != self.num_vars
+            || proof
+                .rounds
+                .iter()
+                .any(|round| round.len() != self.degree + 1)
+        {
+            return None;
+        }
+        let mut claim = claimed_sum;
+        let mut point = Vec::with_capacity(self.num_vars);
+        for round in &proof.rounds {
+            if round[0] + round[1] != claim {
+                return None;
+            }
+            for value in round {
+                transcript.append_field(b"round", value);
+            }
+            let challenge = transcript.challenge(b"r");
+            claim = interpolate_at(round, challenge);
+            point.push(challenge);
+        }
+        Some(Subclaim {
+            point,
+            value: claim,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+
+    type Fq = PrimeField<2147483647>;
+
+    fn poly(values: &[u64]) -> MultilinearPolynomial<Fq> {
+        MultilinearPolynomial::new(values.iter().map(|v| Fq::from_u64(*v)).collect())
+    }
+
+    /// `p q + r` over three variables
+    fn prover() -> SumcheckProver<Fq, impl Fn(&[Fq]) -> Fq> {
+        SumcheckProver::new(
+            vec![
+                poly(&[1, 2, 3, 4, 5, 6, 7, 8]),
+                poly(&[3, 0, 2, 9, 4, 4, 1, 6]),
+                poly(&[5, 5, 0, 1, 2, 7, 3, 3]),
+            ],
+            2,
+            |v: &[Fq]| v[0] * v[1] + v[2],
+        )
+    }
+
+    #[test]
+    fn interpolation_through_integer_nodes() {
+        // 2 + 3X + X^2 at 0, 1, 2 and then at 7
+        let values = [2u64, 6, 12].map(Fq::from_u64);
+        assert_eq!(interpolate_at(&values, Fq::from_u64(7)), Fq::from_u64(72));
+    }
+
+    #[test]
+    fn honest_prover_is_accepted() {
+        let prover = prover();
+        let sum = prover.sum();
+        // 3 + 0 + 6 + 36 + 20 + 24 + 7 + 48 plus 26
+        assert_eq!(sum, Fq::from_u64(170));
+        let polynomials = prover.polynomials.clone();
+        let (proof, point) = prover.prove(&mut Transcript::new(b"test"));
+
+        let subclaim = SumcheckVerifier::new(3, 2)
+            .verify(&mut Transcript::new(b"test"), sum, &proof)
+            .unwrap();
+        assert_eq!(subclaim.point, point);
+        let values: Vec<Fq> = polynomials.iter().map(|p| p.evaluate(&point)).collect();
+        assert_eq!(subclaim.value, values[0] * values[1] + values[2]);
+    }
+
+    #[test]
+    fn wrong_sum_is_rejected() {
+        let prover = prover();
+        let sum = prover.sum();
+        let (proof, _) = prover.prove(&mut Transcript::new(b"test"));
+        let verifier = SumcheckVerifier::new(3, 2);
+        assert_eq!(
+            verifier.verify(&mut Transcript::new(b"test"), sum + Fq::from_u64(1), &proof),
+            None
+        );
+
+        // Shifting the first round polynomial by a constant `c / 2` makes it
+        // sum to the wrong claim, but then it no longer agrees with the
+        // second round at the challenge
+        let mut cheat = proof.clone();
+        let half = Fq::from_u64(2).inverse().unwrap();
+        for value in cheat.rounds[0].iter_mut() {
+            *value += half;
+        }
+        let subclaim =
+            verifier.verify(&mut Transcript::new(b"test"), sum + Fq::from_u64(1), &cheat);
+        assert_eq!(subclaim, None);
+
+        // Truncated proofs are rejected outright
+        cheat.rounds.pop();
+        assert_eq!(
+            verifier.verify(&mut Transcript::new(b"test"), sum, &cheat),
+            None
+        );
+    }
+}
@@ -0,0 +1,643 @@
+//! Inner product argument (IPA) polynomial commitments, as in Bulletproofs
+//! and Halo, over any prime order group.
+//!
+//! A polynomial with coefficient vector `a` of length `n = 2^k` is
+//! committed to with the Pedersen vector commitment `C = <a, G>` for
+//! independent generators `G`. Its evaluation at `z` is the inner product
+//! `v = <a, b>` with `b = (1, z, z^2, ..., z^(n-1))`. After binding `v` to a
+//! further generator `U` with a challenge `ξ`, the prover shows that
+//! `P = C + v · ξU` is of the form `<a, G> + <a, b> · ξU` by halving `a`,
+//! `b` and `G` in `k` rounds. In each round it sends
+//!
+//! `L = <a_lo, G_hi> + <a_lo, b_hi> · ξU` and
+//! `R = <a_hi, G_lo> + <a_hi, b_lo> · ξU`,
+//!
+//! receives a challenge `u` and folds `a' = u a_lo + u^-1 a_hi`,
+//! `b' = u^-1 b_lo + u b_hi` and `G' = u^-1 G_lo + u G_hi`, so that
+//! `P' = P + u^2 L + u^-2 R` has the same form. At the end it sends the
+//! single remaining scalar `a`. Proofs have `2k + 1` elements, but the
+//! verifier folds `G` itself, which takes linear time.
+//!
+//! The same argument commits to a multilinear polynomial by its `2^k`
+//! evaluations `a` on the hypercube, whose value at `r` is `<a, b>` for
+//! `b = (eq(r, x))_x`.
+//!
+//! No trusted setup is needed: the generators only have to have unknown
+//! discrete logarithms relative to each other, e.g. by hashing to the curve.
+
+use std::marker::PhantomData;
+
+use elliptic_curves::{
+    encoding::{field_from_bytes, field_to_bytes, write_prefixed, PrefixedReader},
+    scalar_mul::msm,
+    Encoding, PrimeOrderGroup,
+};
+use fiat_shamir::Transcript;
+use finite_fields::Field;
+use multivariate_sumcheck::multilinear::eq_evaluations;
+use num_traits::Zero;
+use polynomial::Polynomial;
+use rand::RngCore;
+
+use crate::{random_field_element, MultilinearCommitmentScheme, PolynomialCommitmentScheme};
+
+/// The IPA scheme over the group `G`
+#[derive(Debug, Clone, Copy)]
+pub struct Ipa<G>(PhantomData<G>);
+
+/// The IPA scheme for multilinear polynomials over the group `G`, with the
+/// parameters of [`Ipa`]
+#[derive(Debug, Clone, Copy)]
+pub struct MultilinearIpa<G>(PhantomData<G>);
+
+/// Public parameters, shared by prover and verifier
+#[derive(Debug, Clone)]
+pub struct IpaParams<G> {
+    /// One generator per coefficient, a power of two of them
+    pub generators: Vec<G>,
+    /// Generator binding the evaluation
+    pub u: G,
+}
+
+impl<G: PrimeOrderGroup> IpaParams<G> {
+    /// Parameters from independent generators, the last of which becomes
+    /// `U`
+    pub fn from_generators(mut generators: Vec<G>) -> Self {
+        let u = generators.pop().expect("at least two generators");
+        assert!(
+            generators.len().is_power_of_two(),
+            "need a power of two generators besides U"
+        );
+        Self { generators, u }
+    }
+}
+
+/// The cross terms of every round and the final folded coefficient
+#[derive(Debug, Clone)]
+pub struct IpaProof<G: PrimeOrderGroup> {
+    pub left: Vec<G>,
+    pub right: Vec<G>,
+    pub a: G::ScalarField,
+}
+
+fn inner_product<F: Field>(a: &[F], b: &[F]) -> F {
+    a.iter().zip(b).fold(F::zero(), |acc, (x, y)| acc + *x * *y)
+}
+
+/// `x · lo + y · hi` element-wise
+fn fold_scalars<F: Field>(lo: &[F], hi: &[F], x: F, y: F) -> Vec<F> {
+    lo.iter().zip(hi).map(|(l, h)| x * *l + y * *h).collect()
+}
+
+fn fold_points<G: PrimeOrderGroup>(
+    lo: &[G],
+    hi: &[G],
+    x: G::ScalarField,
+    y: G::ScalarField,
+) -> Vec<G> {
+    lo.iter()
+        .zip(hi)
+        .map(|(l, h)| l.mul_scalar(&x) + h.mul_scalar(&y))
+        .collect()
+}
+
+/// `(1, z, z^2, ..., z^(n-1))`
+fn powers<F: Field>(z: F, n: usize) -> Vec<F> {
+    std::iter::successors(Some(F::one()), |power| Some(*power * z))
+        .take(n)
+        .collect()
+}
+
+impl<G: PrimeOrderGroup + Encoding> Ipa<G> {
+    /// Coefficients of `polynomial` padded with zeros to the number of
+    /// generators
+    fn coefficients(
+        params: &IpaParams<G>,
+        polynomial: &Polynomial<G::ScalarField>,
+    ) -> Vec<G::ScalarField> {
+        let len = polynomial.leading_degree().map_or(0, |degree| degree + 1);
+        let n = params.generators.len();
+        assert!(
+            len <= n,
+            "polynomial degree exceeds the supported maximum of {}",
+            n - 1
+        );
+        let mut coeffs = polynomial.get_raw_coefficients();
+        coeffs.resize(n, G::ScalarField::zero());
+        coeffs
+    }
+
+    /// Binds the commitment and the claimed inner product to `transcript`
+    /// and returns `ξU`
+    fn bind_evaluation(
+        params: &IpaParams<G>,
+        transcript: &mut Transcript,
+        commitment: &G,
+        value: G::ScalarField,
+    ) -> G {
+        transcript.append_message(b"commitment", &commitment.to_bytes());
+        transcript.append_field(b"value", &value);
+        let xi: G::ScalarField = transcript.challenge(b"xi");
+        params.u.mul_scalar(&xi)
+    }
+
+    /// Absorbs the cross terms of a round and returns `u` with its inverse,
+    /// or `None` if `u` is zero, which a prover can force by grinding its
+    /// cross terms over a small field
+    fn round_challenge(
+        transcript: &mut Transcript,
+        left: &G,
+        right: &G,
+    ) -> Option<(G::ScalarField, G::ScalarField)> {
+        transcript.append_message(b"left", &left.to_bytes());
+        transcript.append_message(b"right", &right.to_bytes());
+        let u: G::ScalarField = transcript.challenge(b"u");
+        Some((u, u.inverse()?))
+    }
+
+    /// Proves `v = <a, b>` for the `a` behind `C = <a, G>` and a public `b`
+    /// of the same length, continuing a transcript that the caller has
+    /// bound `b` to. Returns `v` and the proof.
+    pub fn prove_inner_product(
+        params: &IpaParams<G>,
+        transcript: &mut Transcript,
+        mut a: Vec<G::ScalarField>,
+        mut b: Vec<G::ScalarField>,
+    ) -> (G::ScalarField, IpaProof<G>) {
+        assert_eq!(a.len(), params.generators.len(), "one scalar per generator");
+        assert_eq!(a.len(), b.len(), "vectors of the same length");
+        let mut generators = params.generators.clone();
+        let value = inner_product(&a, &b);
+        let u = Self::bind_evaluation(params, transcript, &msm(&generators, &a), value);
+
+        let (mut left, mut right) = (vec![], vec![]);
+        while a.len() > 1 {
+            let half = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(half);
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = generators.split_at(half);
+
+            let l = msm(g_hi, a_lo) + u.mul_scalar(&inner_product(a_lo, b_hi));
+            let r = msm(g_lo, a_hi) + u.mul_scalar(&inner_product(a_hi, b_lo));
+            let (x, x_inverse) = Self::round_challenge(transcript, &l, &r)
+                .expect("an honest round challenge is non-zero but with negligible probability");
+
+            a = fold_scalars(a_lo, a_hi, x, x_inverse);
+            b = fold_scalars(b_lo, b_hi, x_inverse, x);
+            generators = fold_points(g_lo, g_hi, x_inverse, x);
+            left.push(l);
+            right.push(r);
+        }
+        (
+            value,
+            IpaProof {
+                left,
+                right,
+                a: a[0],
+            },
+        )
+    }
+
+    /// Checks a proof of [`Ipa::prove_inner_product`] against the same `b`
+    /// and transcript
+    pub fn verify_inner_product(
+        params: &IpaParams<G>,
+        transcript: &mut Transcript,
+        commitment: &G,
+        mut b: Vec<G::ScalarField>,
+        value: G::ScalarField,
+        proof: &IpaProof<G>,
+    ) -> bool {
+        let rounds = params.generators.len().trailing_zeros() as usize;
+        if proof.left.len() != rounds
+            || proof.right.len() != rounds
+            || b.len() != params.generators.len()
+        {
+            return false;
+        }
+        let u = Self::bind_evaluation(params, transcript, commitment, value);
+        let mut p = *commitment + u.mul_scalar(&value);
+        let mut generators = params.generators.clone();
+        for (l, r) in proof.left.iter().zip(&proof.right) {
+            let Some((x, x_inverse)) = Self::round_challenge(transcript, l, r) else {
+                return false;
+            };
+            p = p + l.mul_scalar(&(x * x)) + r.mul_scalar(&(x_inverse * x_inverse));
+
+            let half = b.len() / 2;
+            b = fold_scalars(&b[..half], &b[half..], x_inverse, x);
+            generators = fold_points(&generators[..half], &generators[half..], x_inverse, x);
+        }
+        p == generators[0].mul_scalar(&proof.a) + u.mul_scalar(&(proof.a * b[0]))
+    }
+}
+
+impl<G: PrimeOrderGroup + Encoding> PolynomialCommitmentScheme<G::ScalarField> for Ipa<G> {
+    type CommitterKey = IpaParams<G>;
+    type VerifierKey = IpaParams<G>;
+    type Commitment = G;
+    type Proof = IpaProof<G>;
+
+    /// Random multiples of the group generator. Their discrete logarithms
+    /// are dropped on return, but for a truly transparent setup build the
+    /// parameters with [`IpaParams::from_generators`] from points hashed to
+    /// the curve.
+    fn setup<R: RngCore>(
+        max_degree: usize,
+        rng: &mut R,
+    ) -> (Self::CommitterKey, Self::VerifierKey) {
+        let n = (max_degree + 1).next_power_of_two();
+        let generators: Vec<G> = (0..=n)
+            .map(|_| G::generator().mul_scalar(&random_field_element(rng)))
+            .collect();
+        let params = IpaParams::from_generators(generators);
+        (params.clone(), params)
+    }
+
+    fn commit(
+        ck: &Self::CommitterKey,
+        polynomial: &Polynomial<G::ScalarField>,
+    ) -> Self::Commitment {
+        msm(&ck.generators, &Self::coefficients(ck, polynomial))
+    }
+
+    fn open(
+        ck: &Self::CommitterKey,
+        polynomial: &Polynomial<G::ScalarField>,
+        point: G::ScalarField,
+    ) -> (G::ScalarField, Self::Proof) {
+        let a = Self::coefficients(ck, polynomial);
+        let b = powers(point, a.len());
+        let mut transcript = Transcript::new(b"ipa");
+        transcript.append_field(b"point", &point);
+        Self::prove_inner_product(ck, &mut transcript, a, b)
+    }
+
+    fn verify(
+        vk: &Self::VerifierKey,
+        commitment: &Self::Commitment,
+        point: G::ScalarField,
+        value: G::ScalarField,
+        proof: &Self::Proof,
+    ) -> bool {
+        let b = powers(point, vk.generators.len());
+        let mut transcript = Transcript::new(b"ipa");
+        transcript.append_field(b"point", &point);
+        Self::verify_inner_product(vk, &mut transcript, commitment, b, value, proof)
+    }
+}
+
+impl<G: PrimeOrderGroup + Encoding> MultilinearCommitmentScheme<G::ScalarField>
+    for MultilinearIpa<G>
+{
+    type CommitterKey = IpaParams<G>;
+    type VerifierKey = IpaParams<G>;
+    type Commitment = G;
+    type Proof = IpaProof<G>;
+
+    /// `2^num_vars` generators besides `U`, with the same caveat as the
+    /// univariate setup
+    fn setup<R: RngCore>(num_vars: usize, rng: &mut R) -> (Self::CommitterKey, Self::VerifierKey) {
+        Ipa::<G>::setup((1 << num_vars) - 1, rng)
+    }
+
+    fn commit(ck: &Self::CommitterKey, evaluations: &[G::ScalarField]) -> Self::Commitment {
+        assert_eq!(
+            evaluations.len(),
+            ck.generators.len(),
+            "one evaluation per generator"
+        );
+        msm(&ck.generators, evaluations)
+    }
+
+    fn open(
+        ck: &Self::CommitterKey,
+        evaluations: &[G::ScalarField],
+        point: &[G::ScalarField],
+    ) -> (G::ScalarField, Self::Proof) {
+        let mut transcript = Transcript::new(b"ipa-multilinear");
+        for coordinate in point {
+            transcript.append_field(b"point", coordinate);
+        }
+        Ipa::prove_inner_product(
+            ck,
+            &mut transcript,
+            evaluations.to_vec(),
+            eq_evaluations(point),
+        )
+    }
+
+    fn verify(
+        vk: &Self::VerifierKey,
+        commitment: &Self::Commitment,
+        point: &[G::ScalarField],
+        value: G::ScalarField,
+        proof: &Self::Proof,
+    ) -> bool {
+        // The generators are a power of two of them
+        if point.len() != vk.generators.len().trailing_zeros() as usize {
+            return false;
+        }
+        let mut transcript = Transcript::new(b"ipa-multilinear");
+        for coordinate in point {
+            transcript.append_field(b"point", coordinate);
+        }
+        Ipa::verify_inner_product(
+            vk,
+            &mut transcript,
+            commitment,
+            eq_evaluations(point),
+            value,
+            proof,
+        )
+    }
+}
+
+/// The number of rounds as a little-endian `u32`, then every element
+/// prefixed with its length as a little-endian `u32`
+impl<G: PrimeOrderGroup + Encoding> Encoding for IpaProof<G> {
+    fn to_bytes(&self) -> Vec<u8> {
+        let parts = self
+            .left
+            .iter()
+            .chain(&self.right)
+            .map(Encoding::to_bytes)
+            .chain([field_to_bytes(&self.a)]);
+        let mut bytes = (self.left.len() as u32).to_le_bytes().to_vec();
+        for part in parts {
+            write_prefixed(&mut bytes, &part);
+        }
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = PrefixedReader::new(bytes);
+        let rounds = reader.read_u32()? as usize;
+        let mut points = vec![];
+        for _ in 0..2 * rounds {
+            points.push(G::from_bytes(reader.next_part()?)?);
+        }
+        let a = field_from_bytes(reader.next_part()?)?;
+        if !reader.is_empty() {
+            return None;
+        }
+        let right = points.split_off(rounds);
+        Some(Self {
+            left: points,
+            right,
+            a,
+        })
+    }
+}
+
+/// The number of generators besides `U` as a little-endian `u32`, then the
+/// generators and `U`, each prefixed with its length as a little-endian
+/// `u32`
+impl<G: PrimeOrderGroup + Encoding> Encoding for IpaParams<G> {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = (self.generators.len() as u32).to_le_bytes().to_vec();
+        for generator in self.generators.iter().chain([&self.u]) {
+            write_prefixed(&mut bytes, &generator.to_bytes());
+        }
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = PrefixedReader::new(bytes);
+        let n = reader.read_u32()? as usize;
+        if !n.is_power_of_two() {
+            return None;
+        }
+        let mut generators = vec![];
+        for _ in 0..=n {
+            generators.push(G::from_bytes(reader.next_part()?)?);
+        }
+        if !reader.is_empty() {
+            return None;
+        }
+        Some(Self::from_generators(generators))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use elliptic_curves::{short_weierstrass::Projective, toy::Toy10007, Group};
+    use finite_fields::{Halo2Field, PrimeField};
+    use halo2_proofs::{arithmetic::CurveExt, pasta::pallas};
+    use num_traits::One;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+
+    type Pallas = Ipa<pallas::Point>;
+    type Scalar = Halo2Field<pallas::Scalar>;
+
+    fn poly(coeffs: &[u64]) -> Polynomial<Scalar> {
+        Polynomial::new_from_coeffs(&coeffs.iter().map(|c| Scalar::from(*c)).collect::<Vec<_>>())
+    }
+
+    /// Generators hashed to the curve, with no known discrete logarithms
+    fn transparent_params(n: usize) -> IpaParams<pallas::Point> {
+        let hasher = pallas::Point::hash_to_curve("CryptographyResearch:ipa");
+        IpaParams::from_generators((0..=n as u64).map(|i| hasher(&i.to_le_bytes())).collect())
+    }
+
+    #[test]
+    fn open_and_verify() {
+        let params = transparent_params(8);
+        // 3 + 2x + x^4 + 9x^7
+        let f = poly(&[3, 2, 0, 0, 1, 0, 0, 9]);
+        let commitment = Pallas::commit(&params, &f);
+
+        let point = Scalar::from(5u64);
+        let (value, proof) = Pallas::open(&params, &f, point);
+        assert_eq!(value, f.eval(point));
+        assert_eq!(proof.left.len(), 3);
+        assert!(Pallas::verify(&params, &commitment, point, value, &proof));
+
+        assert!(!Pallas::verify(
+            &params,
+            &commitment,
+            point,
+            value + Scalar::one(),
+            &proof
+        ));
+        assert!(!Pallas::verify(
+            &params,
+            &commitment,
+            Scalar::from(6u64),
+            value,
+            &proof
+        ));
+        let other = Pallas::commit(&params, &poly(&[3, 2, 0, 0, 1, 0, 0, 8]));
+        assert!(!Pallas::verify(&params, &other, point, value, &proof));
+
+        let mut tampered = proof.clone();
+        tampered.left[1] = tampered.left[1].double();
+        assert!(!Pallas::verify(
+            &params,
+            &commitment,
+            point,
+            value,
+            &tampered
+        ));
+        let mut tampered = proof.clone();
+        tampered.a += Scalar::one();
+        assert!(!Pallas::verify(
+            &params,
+            &commitment,
+            point,
+            value,
+            &tampered
+        ));
+        let mut truncated = proof;
+        truncated.left.pop();
+        truncated.right.pop();
+        assert!(!Pallas::verify(
+            &params,
+            &commitment,
+            point,
+            value,
+            &truncated
+        ));
+    }
+
+    #[test]
+    fn proof_encoding_roundtrip() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (ck, vk) = Pallas::setup(5, &mut rng);
+        assert_eq!(ck.generators.len(), 8);
+        let f = poly(&[1, 4, 1, 5, 9, 2]);
+        let (value, proof) = Pallas::open(&ck, &f, Scalar::from(7u64));
+
+        let bytes = proof.to_bytes();
+        let decoded = IpaProof::<pallas::Point>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.to_bytes(), bytes);
+        assert!(Pallas::verify(
+            &vk,
+            &Pallas::commit(&ck, &f),
+            Scalar::from(7u64),
+            value,
+            &decoded
+        ));
+        assert!(IpaProof::<pallas::Point>::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+
+        let bytes = vk.to_bytes();
+        let decoded = IpaParams::<pallas::Point>::from_bytes(&bytes).unwrap();
+        assert_eq!((decoded.generators, decoded.u), (vk.generators, vk.u));
+        assert!(IpaParams::<pallas::Point>::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+    }
+
+    #[test]
+    fn multilinear_open_and_verify() {
+        let params = transparent_params(8);
+        let evaluations = [3u64, 1, 4, 1, 5, 9, 2, 6].map(Scalar::from);
+        let commitment = MultilinearIpa::<pallas::Point>::commit(&params, &evaluations);
+
+        // On the hypercube the value is an evaluation: (1, 0, 1) is index 5
+        let one = Scalar::one();
+        let zero = Scalar::from(0u64);
+        let (value, _) =
+            MultilinearIpa::<pallas::Point>::open(&params, &evaluations, &[one, zero, one]);
+        assert_eq!(value, Scalar::from(9u64));
+
+        // f(r_1, r_2, r_3) by interpolating along x_1, then x_2, then x_3
+        let point = [2u64, 3, 5].map(Scalar::from);
+        let expected = point.iter().fold(evaluations.to_vec(), |values, r| {
+            let (low, high) = values.split_at(values.len() / 2);
+            low.iter()
+                .zip(high)
+                .map(|(l, h)| *l + *r * (*h - *l))
+                .collect()
+        })[0];
+        let (value, proof) = MultilinearIpa::<pallas::Point>::open(&params, &evaluations, &point);
+        assert_eq!(value, expected);
+        assert!(MultilinearIpa::<pallas::Point>::verify(
+            &params,
+            &commitment,
+            &point,
+            value,
+            &proof
+        ));
+        assert!(!MultilinearIpa::<pallas::Point>::verify(
+            &params,
+            &commitment,
+            &point,
+            value + one,
+            &proof
+        ));
+        assert!(!MultilinearIpa::<pallas::Point>::verify(
+            &params,
+            &commitment,
+            &[2u64, 3, 6].map(Scalar::from),
+            value,
+            &proof
+        ));
+        assert!(!MultilinearIpa::<pallas::Point>::verify(
+            &params,
+            &commitment,
+            &point[..2],
+            value,
+            &proof
+        ));
+        assert!(!MultilinearIpa::<pallas::Point>::verify(
+            &params,
+            &commitment,
+            &[Scalar::from(1u64); 64],
+            value,
+            &proof
+        ));
+    }
+
+    #[test]
+    fn toy_curve() {
+        type Toy = Ipa<Projective<Toy10007>>;
+        type F = PrimeField<10039>;
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let (ck, vk) = Toy::setup(3, &mut rng);
+        let f = Polynomial::new_from_coeffs(&[5u64, 0, 2, 7].map(F::from));
+        let commitment = Toy::commit(&ck, &f);
+        for z in [0u64, 1, 1234] {
+            let (value, proof) = Toy::open(&ck, &f, F::from(z));
+            assert!(Toy::verify(&vk, &commitment, F::from(z), value, &proof));
+            assert!(!Toy::verify(
+                &vk,
+                &commitment,
+                F::from(z),
+                value + F::one(),
+                &proof
+            ));
+        }
+        assert!(!Toy::verify(
+            &vk,
+            &Projective::identity(),
+            F::from(1u64),
+            F::from(14u64),
+            &Toy::open(&ck, &f, F::from(1u64)).1
+        ));
+    }
+
+    #[test]
+    fn zero_round_challenge_is_rejected() {
+        type Toy = Ipa<Projective<Toy10007>>;
+        type F = PrimeField<10039>;
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let (ck, vk) = Toy::setup(1, &mut rng);
+        let f = Polynomial::new_from_coeffs(&[5u64, 2].map(F::from));
+        let commitment = Toy::commit(&ck, &f);
+        let point = F::from(3u64);
+        let (value, mut proof) = Toy::open(&ck, &f, point);
+
+        // Grind the cross term until the challenge of the only round is zero
+        let mut bound = Transcript::new(b"ipa");
+        bound.append_field(b"point", &point);
+        Toy::bind_evaluation(&vk, &mut bound, &commitment, value);
+        let generator = Projective::<Toy10007>::generator();
+        proof.left[0] = (1..)
+            .map(|k: u64| generator.mul_bigint([k]))
+            .find(|left| Toy::round_challenge(&mut bound.clone(), left, &proof.right[0]).is_none())
+            .unwrap();
+        assert!(!Toy::verify(&vk, &commitment, point, value, &proof));
+    }
+}
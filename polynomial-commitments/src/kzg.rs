@@ -0,0 +1,430 @@
+//! KZG polynomial commitments [KZG10] over any [`Pairing`].
+//!
+//! A trusted setup samples a secret `τ` and publishes `[τ^i]_1` and
+//! `[τ^i]_2`, where `[x]_1 = x · G1` and `[x]_2 = x · G2`. The commitment to
+//! `f` is `[f(τ)]_1`, computed from the published powers without knowing
+//! `τ`. Since `f(X) - f(z)` is divisible by `X - z`, the proof that
+//! `f(z) = v` is `π = [q(τ)]_1` for `q = (f - v) / (X - z)`, which the
+//! verifier checks with a single pairing equation:
+//!
+//! `e(C - [v]_1, [1]_2) = e(π, [τ]_2 - [z]_2)`
+//!
+//! Anyone who learns `τ` can forge openings, so it has to be discarded
+//! after setup (the "toxic waste").
+
+use std::marker::PhantomData;
+
+use elliptic_curves::{pairing::Pairing, scalar_mul::msm, Group, PrimeOrderGroup};
+use finite_fields::Field;
+use num_traits::One;
+use polynomial::Polynomial;
+use rand::RngCore;
+
+use crate::{random_field_element, PolynomialCommitmentScheme};
+
+type Scalar<E> = <<E as Pairing>::G1 as PrimeOrderGroup>::ScalarField;
+
+/// The KZG scheme over the pairing `E`
+#[derive(Debug, Clone, Copy)]
+pub struct Kzg<E>(PhantomData<E>);
+
+/// Powers `[τ^0]_1, [τ^1]_1, ..., [τ^d]_1`
+#[derive(Debug, Clone)]
+pub struct KzgCommitterKey<E: Pairing> {
+    pub powers_of_tau: Vec<E::G1>,
+}
+
+/// Generator of `G1` and powers `[τ^0]_2, ..., [τ^d]_2`. Single-point
+/// openings only need `[1]_2` and `[τ]_2`; opening at `n` points at once
+/// needs the first `n + 1` powers.
+#[derive(Debug, Clone)]
+pub struct KzgVerifierKey<E: Pairing> {
+    pub g1: E::G1,
+    pub powers_of_tau_g2: Vec<E::G2>,
+}
+
+/// `[τ^0], [τ^1], ..., [τ^max_degree]` in `G`
+fn powers_of<G: PrimeOrderGroup>(tau: G::ScalarField, max_degree: usize) -> Vec<G> {
+    let generator = G::generator();
+    std::iter::successors(Some(G::ScalarField::one()), |power| Some(*power * tau))
+        .take(max_degree + 1)
+        .map(|power| generator.mul_scalar(&power))
+        .collect()
+}
+
+/// `[p(τ)]` in `G` from the powers of `τ`
+fn evaluate_in_exponent<G: PrimeOrderGroup>(
+    powers: &[G],
+    polynomial: &Polynomial<G::ScalarField>,
+) -> G {
+    let len = polynomial.leading_degree().map_or(0, |degree| degree + 1);
+    assert!(
+        len <= powers.len(),
+        "polynomial degree exceeds the supported maximum of {}",
+        powers.len() - 1
+    );
+    msm(powers, &polynomial.get_raw_coefficients()[..len])
+}
+
+/// `Π_i (X - z_i)`, the polynomial vanishing exactly on `points`
+fn vanishing_polynomial<F: Field>(points: &[F]) -> Polynomial<F> {
+    points
+        .iter()
+        .fold(Polynomial::new_from_coeffs(&[F::one()]), |acc, point| {
+            &acc * &Polynomial::new_from_coeffs(&[-*point, F::one()])
+        })
+}
+
+/// `Σ_i γ^i p_i` for polynomials or field elements
+fn linear_combination<T, F>(
+    items: &[T],
+    challenge: F,
+    scale: impl Fn(&T, F) -> T,
+    add: impl Fn(T, T) -> T,
+) -> Option<T>
+where
+    F: Field,
+{
+    let mut power = F::one();
+    items.iter().fold(None, |acc, item| {
+        let term = scale(item, power);
+        power = power * challenge;
+        Some(match acc {
+            Some(acc) => add(acc, term),
+            None => term,
+        })
+    })
+}
+
+impl<E: Pairing> PolynomialCommitmentScheme<Scalar<E>> for Kzg<E> {
+    type CommitterKey = KzgCommitterKey<E>;
+    type VerifierKey = KzgVerifierKey<E>;
+    type Commitment = E::G1;
+    type Proof = E::G1;
+
+    /// Powers of tau for a `τ` drawn from `rng`, which is dropped on return
+    fn setup<R: RngCore>(
+        max_degree: usize,
+        rng: &mut R,
+    ) -> (Self::CommitterKey, Self::VerifierKey) {
+        let tau: Scalar<E> = random_field_element(rng);
+        (
+            KzgCommitterKey {
+                powers_of_tau: powers_of(tau, max_degree),
+            },
+            KzgVerifierKey {
+                g1: E::G1::generator(),
+                powers_of_tau_g2: powers_of(tau, max_degree),
+            },
+        )
+    }
+
+    fn commit(ck: &Self::CommitterKey, polynomial: &Polynomial<Scalar<E>>) -> Self::Commitment {
+        evaluate_in_exponent(&ck.powers_of_tau, polynomial)
+    }
+
+    fn open(
+        ck: &Self::CommitterKey,
+        polynomial: &Polynomial<Scalar<E>>,
+        point: Scalar<E>,
+    ) -> (Scalar<E>, Self::Proof) {
+        let value = polynomial.eval(point);
+        let proof = Self::open_multi_point(ck, polynomial, &[point]).1;
+        (value, proof)
+    }
+
+    fn verify(
+        vk: &Self::VerifierKey,
+        commitment: &Self::Commitment,
+        point: Scalar<E>,
+        value: Scalar<E>,
+        proof: &Self::Proof,
+    ) -> bool {
+        // A key for constant polynomials has no `[τ]_2`
+        let [one, tau, ..] = vk.powers_of_tau_g2[..] else {
+            return false;
+        };
+        E::pairing(&(*commitment - vk.g1.mul_scalar(&value)), &one)
+            == E::pairing(proof, &(tau - one.mul_scalar(&point)))
+    }
+}
+
+impl<E: Pairing> Kzg<E> {
+    /// Opens several polynomials at the same point with a single proof.
+    /// After the values are fixed, the verifier sends a random `γ` and the
+    /// prover opens `Σ γ^i f_i`, whose commitment is `Σ γ^i C_i` by
+    /// linearity.
+    pub fn open_batch(
+        ck: &KzgCommitterKey<E>,
+        polynomials: &[Polynomial<Scalar<E>>],
+        point: Scalar<E>,
+        challenge: Scalar<E>,
+    ) -> (Vec<Scalar<E>>, E::G1) {
+        let values = polynomials.iter().map(|p| p.eval(point)).collect();
+        let combined = linear_combination(
+            polynomials,
+            challenge,
+            |p, power| p * &Polynomial::new_from_coeffs(&[power]),
+            |a, b| &a + &b,
+        )
+        .expect("at least one polynomial to open");
+        (values, Self::open(ck, &combined, point).1)
+    }
+
+    pub fn verify_batch(
+        vk: &KzgVerifierKey<E>,
+        commitments: &[E::G1],
+        point: Scalar<E>,
+        values: &[Scalar<E>],
+        proof: &E::G1,
+        challenge: Scalar<E>,
+    ) -> bool {
+        if commitments.len() != values.len() {
+            return false;
+        }
+        let Some(commitment) = linear_combination(
+            commitments,
+            challenge,
+            |c, power| c.mul_scalar(&power),
+            |a, b| a + b,
+        ) else {
+            return false;
+        };
+        let value = linear_combination(values, challenge, |v, power| *v * power, |a, b| a + b)
+            .expect("as many values as commitments");
+        Self::verify(vk, &commitment, point, value, proof)
+    }
+
+    /// Opens one polynomial at several points `z_i` with a single proof:
+    /// with `I` interpolating the values and `Z = Π (X - z_i)`, the proof is
+    /// `[q(τ)]_1` for `q = (f - I) / Z`
+    pub fn open_multi_point(
+        ck: &KzgCommitterKey<E>,
+        polynomial: &Polynomial<Scalar<E>>,
+        points: &[Scalar<E>],
+    ) -> (Vec<Scalar<E>>, E::G1) {
+        let values: Vec<Scalar<E>> = points.iter().map(|z| polynomial.eval(*z)).collect();
+        let interpolation = interpolate(points, &values);
+        let (quotient, remainder) =
+            (polynomial - &interpolation).div_rem(&vanishing_polynomial(points));
+        debug_assert!(remainder.is_zero());
+        (values, evaluate_in_exponent(&ck.powers_of_tau, &quotient))
+    }
+
+    /// Checks `e(C, [1]_2) = e(π, [Z(τ)]_2) · e([1]_1, [I(τ)]_2)`, i.e.
+    /// `f(τ) = q(τ) Z(τ) + I(τ)` in the exponent
+    pub fn verify_multi_point(
+        vk: &KzgVerifierKey<E>,
+        commitment: &E::G1,
+        points: &[Scalar<E>],
+        values: &[Scalar<E>],
+        proof: &E::G1,
+    ) -> bool {
+        if points.len() != values.len() || points.len() >= vk.powers_of_tau_g2.len() {
+            return false;
+        }
+        // Interpolating through a repeated point would divide by zero
+        if points
+            .iter()
+            .enumerate()
+            .any(|(i, z)| points[..i].contains(z))
+        {
+            return false;
+        }
+        let interpolation =
+            evaluate_in_exponent(&vk.powers_of_tau_g2, &interpolate(points, values));
+        let vanishing = evaluate_in_exponent(&vk.powers_of_tau_g2, &vanishing_polynomial(points));
+        E::pairing(commitment, &vk.powers_of_tau_g2[0])
+            == E::pairing(proof, &vanishing) * E::pairing(&vk.g1, &interpolation)
+    }
+}
+
+fn interpolate<F: Field>(points: &[F], values: &[F]) -> Polynomial<F> {
+    let evals: Vec<(F, F)> = points.iter().copied().zip(values.iter().copied()).collect();
+    Polynomial::new_from_evals_lagrange(&evals)
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use elliptic_curves::toy::ToyTatePairing;
+    use finite_fields::PrimeField;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+
+    type Bls = Kzg<Bls12_381>;
+
+    fn poly(coeffs: &[u64]) -> Polynomial<Fr> {
+        Polynomial::new_from_coeffs(&coeffs.iter().map(|c| Fr::from(*c)).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn open_and_verify() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (ck, vk) = Bls::setup(4, &mut rng);
+        // 3 + 2x + x^4
+        let f = poly(&[3, 2, 0, 0, 1]);
+        let commitment = Bls::commit(&ck, &f);
+        assert_eq!(commitment, Bls::commit(&ck, &poly(&[3, 2, 0, 0, 1, 0, 0])));
+
+        let point = Fr::from(5u64);
+        let (value, proof) = Bls::open(&ck, &f, point);
+        assert_eq!(value, Fr::from(3 + 10 + 625u64));
+        assert!(Bls::verify(&vk, &commitment, point, value, &proof));
+        assert!(!Bls::verify(
+            &vk,
+            &commitment,
+            point,
+            value + Fr::from(1u64),
+            &proof
+        ));
+        assert!(!Bls::verify(
+            &vk,
+            &commitment,
+            Fr::from(6u64),
+            value,
+            &proof
+        ));
+        let other = Bls::commit(&ck, &poly(&[3, 2, 0, 1]));
+        assert!(!Bls::verify(&vk, &other, point, value, &proof));
+    }
+
+    #[test]
+    #[should_panic(expected = "polynomial degree exceeds")]
+    fn rejects_polynomial_above_max_degree() {
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (ck, _) = Bls::setup(2, &mut rng);
+        let _ = Bls::commit(&ck, &poly(&[1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn batched_opening() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let (ck, vk) = Bls::setup(3, &mut rng);
+        let polynomials = [poly(&[1, 2, 3]), poly(&[7, 0, 0, 5]), poly(&[4, 1])];
+        let commitments: Vec<_> = polynomials.iter().map(|p| Bls::commit(&ck, p)).collect();
+
+        let (point, challenge) = (Fr::from(9u64), Fr::from(1234u64));
+        let (values, proof) = Bls::open_batch(&ck, &polynomials, point, challenge);
+        assert_eq!(values[2], Fr::from(13u64));
+        assert!(Bls::verify_batch(
+            &vk,
+            &commitments,
+            point,
+            &values,
+            &proof,
+            challenge
+        ));
+
+        let mut wrong = values.clone();
+        wrong.swap(0, 1);
+        assert!(!Bls::verify_batch(
+            &vk,
+            &commitments,
+            point,
+            &wrong,
+            &proof,
+            challenge
+        ));
+        assert!(!Bls::verify_batch(
+            &vk,
+            &commitments[..2],
+            point,
+            &values[..2],
+            &proof,
+            challenge
+        ));
+    }
+
+    #[test]
+    fn multi_point_opening() {
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let (ck, vk) = Bls::setup(5, &mut rng);
+        let f = poly(&[8, 1, 0, 6, 2, 9]);
+        let commitment = Bls::commit(&ck, &f);
+
+        let points = [1u64, 2, 3].map(Fr::from);
+        let (values, proof) = Bls::open_multi_point(&ck, &f, &points);
+        assert!(Bls::verify_multi_point(
+            &vk,
+            &commitment,
+            &points,
+            &values,
+            &proof
+        ));
+
+        let mut wrong = values.clone();
+        wrong[1] += Fr::from(1u64);
+        assert!(!Bls::verify_multi_point(
+            &vk,
+            &commitment,
+            &points,
+            &wrong,
+            &proof
+        ));
+        assert!(!Bls::verify_multi_point(
+            &vk,
+            &commitment,
+            &points[..2],
+            &values[..2],
+            &proof
+        ));
+        let repeated = [points[0], points[1], points[0]];
+        let repeated_values = [values[0], values[1], values[0]];
+        assert!(!Bls::verify_multi_point(
+            &vk,
+            &commitment,
+            &repeated,
+            &repeated_values,
+            &proof
+        ));
+    }
+
+    #[test]
+    fn constant_key_rejects_openings() {
+        let mut rng = ChaCha20Rng::seed_from_u64(4);
+        let (ck, vk) = Bls::setup(0, &mut rng);
+        let f = poly(&[6]);
+        let (value, proof) = Bls::open(&ck, &f, Fr::from(2u64));
+        assert!(!Bls::verify(
+            &vk,
+            &Bls::commit(&ck, &f),
+            Fr::from(2u64),
+            value,
+            &proof
+        ));
+    }
+
+    #[test]
+    fn toy_pairing() {
+        // Scalars live in F_17, so a polynomial of degree 4 is plenty
+        type Toy = Kzg<ToyTatePairing>;
+        type F17 = PrimeField<17>;
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        let (ck, vk) = Toy::setup(4, &mut rng);
+        let f = Polynomial::new_from_coeffs(&[2u64, 0, 5, 1].map(F17::from));
+        let commitment = Toy::commit(&ck, &f);
+        for z in [0u64, 3, 16] {
+            let (value, proof) = Toy::open(&ck, &f, F17::from(z));
+            assert!(Toy::verify(&vk, &commitment, F17::from(z), value, &proof));
+            assert!(!Toy::verify(
+                &vk,
+                &commitment,
+                F17::from(z),
+                value + F17::from(1u64),
+                &proof
+            ));
+        }
+        let points = [1u64, 2].map(F17::from);
+        let (values, proof) = Toy::open_multi_point(&ck, &f, &points);
+        assert!(Toy::verify_multi_point(
+            &vk,
+            &commitment,
+            &points,
+            &values,
+            &proof
+        ));
+    }
+}
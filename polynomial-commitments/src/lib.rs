@@ -0,0 +1,78 @@
+//! Polynomial commitment schemes: a prover commits to a polynomial with a
+//! short commitment and later proves evaluations of it at points chosen
+//! by the verifier. They are what turns the polynomial oracles of an IOP
+//! into something a prover can actually send.
+
+use std::fmt::Debug;
+
+use finite_fields::Field;
+use polynomial::Polynomial;
+use rand::RngCore;
+
+pub mod ipa;
+pub mod kzg;
+
+/// A commitment scheme for univariate polynomials over `F` with evaluation
+/// proofs
+pub trait PolynomialCommitmentScheme<F: Field> {
+    type CommitterKey;
+    type VerifierKey;
+    type Commitment: Clone + Debug + PartialEq;
+    type Proof: Clone + Debug;
+
+    /// Generate keys supporting polynomials of degree at most `max_degree`
+    fn setup<R: RngCore>(max_degree: usize, rng: &mut R)
+        -> (Self::CommitterKey, Self::VerifierKey);
+
+    fn commit(ck: &Self::CommitterKey, polynomial: &Polynomial<F>) -> Self::Commitment;
+
+    /// Evaluates `polynomial` at `point` and proves the evaluation
+    fn open(ck: &Self::CommitterKey, polynomial: &Polynomial<F>, point: F) -> (F, Self::Proof);
+
+    /// Checks that the polynomial behind `commitment` evaluates to `value`
+    /// at `point`
+    fn verify(
+        vk: &Self::VerifierKey,
+        commitment: &Self::Commitment,
+        point: F,
+        value: F,
+        proof: &Self::Proof,
+    ) -> bool;
+}
+
+/// A commitment scheme for multilinear polynomials over `F` with
+/// evaluation proofs. A polynomial in `n` variables is given by its `2^n`
+/// evaluations on the boolean hypercube, the point `(x_1, ..., x_n)` at
+/// index `Σ_j x_j 2^(n-j)` as in `multivariate-sumcheck`.
+pub trait MultilinearCommitmentScheme<F: Field> {
+    type CommitterKey;
+    type VerifierKey;
+    type Commitment: Clone + Debug + PartialEq;
+    type Proof: Clone + Debug;
+
+    /// Generate keys supporting polynomials in `num_vars` variables
+    fn setup<R: RngCore>(num_vars: usize, rng: &mut R) -> (Self::CommitterKey, Self::VerifierKey);
+
+    fn commit(ck: &Self::CommitterKey, evaluations: &[F]) -> Self::Commitment;
+
+    /// Evaluates the polynomial at `point` and proves the evaluation
+    fn open(ck: &Self::CommitterKey, evaluations: &[F], point: &[F]) -> (F, Self::Proof);
+
+    /// Checks that the polynomial behind `commitment` evaluates to `value`
+    /// at `point`
+    fn verify(
+        vk: &Self::VerifierKey,
+        commitment: &Self::Commitment,
+        point: &[F],
+        value: F,
+        proof: &Self::Proof,
+    ) -> bool;
+}
+
+/// Samples a uniformly random field element, up to a bias negligible for
+/// fields of less than 256 bits
+pub fn random_field_element<F: Field, R: RngCore>(rng: &mut R) -> F {
+    let mut bytes = [0u8; 64];
+    rng.fill_bytes(&mut bytes);
+    F::from_le_bytes_mod_order(&bytes)
+}
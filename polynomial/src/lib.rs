@@ -2,12 +2,13 @@ use std::{
     collections::HashSet,
     fmt::Debug,
     hash::Hash,
-    ops::{Mul, Sub},
+    ops::{Add, Div, Mul, Sub},
 };
 extern crate nalgebra as na;
 use na::{ComplexField, DMatrix, RowDVector, Scalar};
 use num_traits::{One, Zero};
 
+#[derive(Debug, Clone)]
 pub enum PolynomialRepr<T> {
     Points(Vec<(T, T)>),
     Roots(Vec<T>),
@@ -17,6 +18,7 @@ pub enum PolynomialRepr<T> {
 }
 
 /// A polynomial
+#[derive(Debug, Clone)]
 pub struct Polynomial<T> {
     repr: PolynomialRepr<T>,
 }
@@ -130,6 +132,44 @@ where
     }
 }
 
+impl<T> Polynomial<T>
+where
+    T: Zero + One + Clone + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    /// Generate a polynomial from its evaluations `(a, b)` such that
+    /// `poly(a) = b` by Lagrange interpolation:
+    /// `sum_i b_i * prod_{j != i} (x - a_j) / (a_i - a_j)`.
+    /// Unlike `new_from_evals`, only field operations are needed, so it
+    /// works over finite fields as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polynomial::Polynomial;
+    /// let polynomial = Polynomial::<f64>::new_from_evals_lagrange(&[(0.0, 1.0), (1.0, 3.0), (2.0, 7.0)]);
+    /// assert_eq!(polynomial.get_raw_coefficients(), vec![1.0, 1.0, 1.0]);
+    /// ```
+    pub fn new_from_evals_lagrange(evals: &[(T, T)]) -> Self {
+        evals
+            .iter()
+            .enumerate()
+            .fold(Self::zero(), |acc, (i, (a_i, b_i))| {
+                let basis = evals.iter().enumerate().filter(|(j, _)| *j != i).fold(
+                    Self::new_from_coeffs(&[b_i.clone()]),
+                    |basis, (_, (a_j, _))| {
+                        let denominator = a_i.clone() - a_j.clone();
+                        &basis
+                            * &Self::new_from_coeffs(&[
+                                T::zero() - a_j.clone() / denominator.clone(),
+                                T::one() / denominator,
+                            ])
+                    },
+                );
+                &acc + &basis
+            })
+    }
+}
+
 impl<T> Polynomial<T>
 where
     T: Zero + One + Mul<Output = T> + Sub<Output = T> + Clone + Debug,
@@ -167,6 +207,192 @@ where
     }
 }
 
+impl<T> Polynomial<T>
+where
+    T: Zero + Clone,
+{
+    /// Generate the zero polynomial
+    pub fn zero() -> Self {
+        Self::new_from_coeffs(&[])
+    }
+
+    /// Coefficients with the trailing zero coefficients stripped, so that
+    /// the last coefficient, if any, is the leading one
+    fn trimmed_coefficients(&self) -> Vec<T> {
+        let mut coeffs = self.get_raw_coefficients();
+        while coeffs.last().is_some_and(|c| c.is_zero()) {
+            coeffs.pop();
+        }
+        coeffs
+    }
+
+    /// Same polynomial with trailing zero coefficients removed
+    fn trimmed(&self) -> Self {
+        Self::new_from_coeffs(&self.trimmed_coefficients())
+    }
+
+    /// Checks whether every coefficient of the polynomial is zero
+    pub fn is_zero(&self) -> bool {
+        self.trimmed_coefficients().is_empty()
+    }
+
+    /// Gets the mathematical degree of the polynomial, i.e. the index of
+    /// the highest non-zero coefficient. Unlike `degree`, trailing zero
+    /// coefficients are not counted. `None` for the zero polynomial.
+    pub fn leading_degree(&self) -> Option<usize> {
+        self.trimmed_coefficients().len().checked_sub(1)
+    }
+
+    /// Gets the coefficient of the highest power of `x`, `None` for the
+    /// zero polynomial
+    pub fn leading_coefficient(&self) -> Option<T> {
+        self.trimmed_coefficients().pop()
+    }
+}
+
+impl<T> Polynomial<T>
+where
+    T: Zero + Clone + Sub<Output = T> + Mul<Output = T> + Div<Output = T>,
+{
+    /// Euclidean division of polynomials. Returns `(q, r)` such that
+    /// `self = q * divisor + r` where degree of `r` is strictly lower
+    /// than that of `divisor`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use polynomial::Polynomial;
+    /// // (x^2 + 3x + 5) = (x + 1)(x + 2) + 3
+    /// let dividend = Polynomial::<i32>::new_from_coeffs(&[5, 3, 1]);
+    /// let divisor = Polynomial::<i32>::new_from_coeffs(&[1, 1]);
+    /// let (quotient, remainder) = dividend.div_rem(&divisor);
+    /// assert_eq!(quotient.get_raw_coefficients(), vec![2, 1]);
+    /// assert_eq!(remainder.get_raw_coefficients(), vec![3]);
+    /// ```
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        let divisor = divisor.trimmed_coefficients();
+        let leading = divisor
+            .last()
+            .cloned()
+            .expect("division by zero polynomial");
+
+        let mut remainder = self.trimmed_coefficients();
+        if remainder.len() < divisor.len() {
+            return (Self::zero(), Self::new_from_coeffs(&remainder));
+        }
+
+        // Schoolbook long division, eliminating the highest power of the
+        // remainder one at a time
+        let mut quotient = vec![T::zero(); remainder.len() - divisor.len() + 1];
+        for idx in (0..quotient.len()).rev() {
+            let factor = remainder[idx + divisor.len() - 1].clone() / leading.clone();
+            for (offset, coeff) in divisor.iter().enumerate() {
+                remainder[idx + offset] =
+                    remainder[idx + offset].clone() - factor.clone() * coeff.clone();
+            }
+            quotient[idx] = factor;
+        }
+        remainder.truncate(divisor.len() - 1);
+
+        (
+            Self::new_from_coeffs(&quotient),
+            Self::new_from_coeffs(&remainder).trimmed(),
+        )
+    }
+}
+
+impl<T> Add for &Polynomial<T>
+where
+    T: Zero + Clone + Add<Output = T>,
+{
+    type Output = Polynomial<T>;
+
+    fn add(self, rhs: Self) -> Polynomial<T> {
+        let (lhs, rhs) = (self.get_raw_coefficients(), rhs.get_raw_coefficients());
+        let coeffs: Vec<T> = (0..lhs.len().max(rhs.len()))
+            .map(|idx| {
+                let a = lhs.get(idx).cloned().unwrap_or_else(T::zero);
+                let b = rhs.get(idx).cloned().unwrap_or_else(T::zero);
+                a + b
+            })
+            .collect();
+        Polynomial::new_from_coeffs(&coeffs).trimmed()
+    }
+}
+
+impl<T> Sub for &Polynomial<T>
+where
+    T: Zero + Clone + Sub<Output = T>,
+{
+    type Output = Polynomial<T>;
+
+    fn sub(self, rhs: Self) -> Polynomial<T> {
+        let (lhs, rhs) = (self.get_raw_coefficients(), rhs.get_raw_coefficients());
+        let coeffs: Vec<T> = (0..lhs.len().max(rhs.len()))
+            .map(|idx| {
+                let a = lhs.get(idx).cloned().unwrap_or_else(T::zero);
+                let b = rhs.get(idx).cloned().unwrap_or_else(T::zero);
+                a - b
+            })
+            .collect();
+        Polynomial::new_from_coeffs(&coeffs).trimmed()
+    }
+}
+
+impl<T> Mul for &Polynomial<T>
+where
+    T: Zero + Clone + Mul<Output = T>,
+{
+    type Output = Polynomial<T>;
+
+    fn mul(self, rhs: Self) -> Polynomial<T> {
+        let (lhs, rhs) = (self.trimmed_coefficients(), rhs.trimmed_coefficients());
+        if lhs.is_empty() || rhs.is_empty() {
+            return Polynomial::zero();
+        }
+        let mut coeffs = vec![T::zero(); lhs.len() + rhs.len() - 1];
+        for (i, a) in lhs.iter().enumerate() {
+            for (j, b) in rhs.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j].clone() + a.clone() * b.clone();
+            }
+        }
+        Polynomial::new_from_coeffs(&coeffs)
+    }
+}
+
+impl<T> Add for Polynomial<T>
+where
+    T: Zero + Clone + Add<Output = T>,
+{
+    type Output = Polynomial<T>;
+
+    fn add(self, rhs: Self) -> Polynomial<T> {
+        &self + &rhs
+    }
+}
+
+impl<T> Sub for Polynomial<T>
+where
+    T: Zero + Clone + Sub<Output = T>,
+{
+    type Output = Polynomial<T>;
+
+    fn sub(self, rhs: Self) -> Polynomial<T> {
+        &self - &rhs
+    }
+}
+
+impl<T> Mul for Polynomial<T>
+where
+    T: Zero + Clone + Mul<Output = T>,
+{
+    type Output = Polynomial<T>;
+
+    fn mul(self, rhs: Self) -> Polynomial<T> {
+        &self * &rhs
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,4 +432,57 @@ mod tests {
         let polynomial = Polynomial::new_from_evals(&evals);
         assert_eq!(polynomial.eval(10.0) as i64, 141);
     }
+
+    #[test]
+    fn polynomial_arithmetic() {
+        let a = Polynomial::<i64>::new_from_coeffs(&[1, 2, 3]);
+        let b = Polynomial::<i64>::new_from_coeffs(&[4, 5]);
+        assert_eq!((&a + &b).get_raw_coefficients(), vec![5, 7, 3]);
+        assert_eq!((&a - &b).get_raw_coefficients(), vec![-3, -3, 3]);
+        assert_eq!((&a * &b).get_raw_coefficients(), vec![4, 13, 22, 15]);
+        assert!((&a - &a).is_zero());
+        assert_eq!((&a - &a).leading_degree(), None);
+    }
+
+    #[test]
+    fn lagrange_interpolation() {
+        // 2x^3 - x + 5
+        let expected = Polynomial::<f64>::new_from_coeffs(&[5.0, -1.0, 0.0, 2.0]);
+        let evals: Vec<(f64, f64)> = [-2.0, 0.5, 1.0, 4.0]
+            .into_iter()
+            .map(|x| (x, expected.eval(x)))
+            .collect();
+        let interpolated = Polynomial::new_from_evals_lagrange(&evals);
+        for (got, want) in interpolated
+            .get_raw_coefficients()
+            .iter()
+            .zip(expected.get_raw_coefficients())
+        {
+            assert!((got - want).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn polynomial_division() {
+        // x^3 - 1 = (x - 1)(x^2 + x + 1)
+        let dividend = Polynomial::<f64>::new_from_coeffs(&[-1.0, 0.0, 0.0, 1.0]);
+        let divisor = Polynomial::<f64>::new_from_coeffs(&[-1.0, 1.0]);
+        let (quotient, remainder) = dividend.div_rem(&divisor);
+        assert_eq!(quotient.get_raw_coefficients(), vec![1.0, 1.0, 1.0]);
+        assert!(remainder.is_zero());
+
+        // Dividing by a higher degree polynomial leaves everything in the
+        // remainder
+        let (quotient, remainder) = divisor.div_rem(&dividend);
+        assert!(quotient.is_zero());
+        assert_eq!(remainder.get_raw_coefficients(), vec![-1.0, 1.0]);
+    }
+
+    #[test]
+    fn leading_degree_ignores_trailing_zeros() {
+        let polynomial = Polynomial::<u32>::new_from_coeffs(&[3, 2, 0, 0]);
+        assert_eq!(polynomial.degree(), 4);
+        assert_eq!(polynomial.leading_degree(), Some(1));
+        assert_eq!(polynomial.leading_coefficient(), Some(2));
+    }
 }
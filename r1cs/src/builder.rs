@@ -0,0 +1,224 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use finite_fields::Field;
+
+use crate::{matrix::SparseMatrix, R1CS};
+
+/// A variable of the constraint system. The constant `One` is the first
+/// entry of every assignment `z = (1, x, w)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Variable {
+    One,
+    /// A public input, by order of allocation
+    Instance(usize),
+    /// A private input or intermediate value, by order of allocation
+    Witness(usize),
+}
+
+/// `Σ_i c_i v_i`, one side of a constraint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinearCombination<F> {
+    pub terms: Vec<(Variable, F)>,
+}
+
+impl<F: Field> LinearCombination<F> {
+    pub fn zero() -> Self {
+        Self { terms: vec![] }
+    }
+
+    /// `value · 1`
+    pub fn constant(value: F) -> Self {
+        Self {
+            terms: vec![(Variable::One, value)],
+        }
+    }
+
+    /// Sums the coefficients of repeated variables and drops zero terms
+    pub fn simplify(self) -> Self {
+        let mut terms: Vec<(Variable, F)> = Vec::with_capacity(self.terms.len());
+        for (variable, coefficient) in self.terms {
+            match terms.iter_mut().find(|(other, _)| *other == variable) {
+                Some((_, sum)) => *sum = *sum + coefficient,
+                None => terms.push((variable, coefficient)),
+            }
+        }
+        terms.retain(|(_, coefficient)| !coefficient.is_zero());
+        Self { terms }
+    }
+
+    /// The constant value, if no other variable appears
+    pub fn as_constant(&self) -> Option<F> {
+        self.terms
+            .iter()
+            .all(|(variable, _)| *variable == Variable::One)
+            .then(|| self.evaluate(|_| F::one()))
+    }
+
+    /// Value under the assignment of each variable by `value`
+    pub fn evaluate(&self, value: impl Fn(Variable) -> F) -> F {
+        self.terms
+            .iter()
+            .fold(F::zero(), |acc, (variable, coefficient)| {
+                acc + *coefficient * value(*variable)
+            })
+    }
+}
+
+impl<F: Field> From<Variable> for LinearCombination<F> {
+    fn from(variable: Variable) -> Self {
+        Self {
+            terms: vec![(variable, F::one())],
+        }
+    }
+}
+
+impl<F: Field, T: Into<LinearCombination<F>>> Add<T> for LinearCombination<F> {
+    type Output = Self;
+
+    fn add(mut self, rhs: T) -> Self {
+        self.terms.extend(rhs.into().terms);
+        self
+    }
+}
+
+impl<F: Field, T: Into<LinearCombination<F>>> Sub<T> for LinearCombination<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: T) -> Self {
+        self + -rhs.into()
+    }
+}
+
+impl<F: Field> Neg for LinearCombination<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self * -F::one()
+    }
+}
+
+impl<F: Field> Mul<F> for LinearCombination<F> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: F) -> Self {
+        for (_, coefficient) in self.terms.iter_mut() {
+            *coefficient = *coefficient * rhs;
+        }
+        self
+    }
+}
+
+/// Allocates variables and collects constraints `<A_i, z> · <B_i, z> =
+/// <C_i, z>`. Instance and witness variables can be allocated in any
+/// order; [`ConstraintSystem::build`] lays them out as `z = (1, x, w)`.
+#[derive(Debug, Clone)]
+pub struct ConstraintSystem<F> {
+    num_instance: usize,
+    num_witness: usize,
+    constraints: Vec<[LinearCombination<F>; 3]>,
+}
+
+impl<F: Field> Default for ConstraintSystem<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Field> ConstraintSystem<F> {
+    pub fn new() -> Self {
+        Self {
+            num_instance: 0,
+            num_witness: 0,
+            constraints: vec![],
+        }
+    }
+
+    /// A variable whose value the verifier knows
+    pub fn instance(&mut self) -> Variable {
+        self.num_instance += 1;
+        Variable::Instance(self.num_instance - 1)
+    }
+
+    /// A variable only the prover knows
+    pub fn witness(&mut self) -> Variable {
+        self.num_witness += 1;
+        Variable::Witness(self.num_witness - 1)
+    }
+
+    /// `a · b = c`
+    pub fn enforce(
+        &mut self,
+        a: impl Into<LinearCombination<F>>,
+        b: impl Into<LinearCombination<F>>,
+        c: impl Into<LinearCombination<F>>,
+    ) {
+        let constraint = [a.into(), b.into(), c.into()];
+        let in_range = |variable: &Variable| match *variable {
+            Variable::One => true,
+            Variable::Instance(i) => i < self.num_instance,
+            Variable::Witness(i) => i < self.num_witness,
+        };
+        assert!(
+            constraint
+                .iter()
+                .flat_map(|lc| &lc.terms)
+                .all(|(variable, _)| in_range(variable)),
+            "constraints must use allocated variables"
+        );
+        self.constraints.push(constraint);
+    }
+
+    /// `a = b`, as `a · 1 = b`
+    pub fn enforce_equal(
+        &mut self,
+        a: impl Into<LinearCombination<F>>,
+        b: impl Into<LinearCombination<F>>,
+    ) {
+        self.enforce(a, Variable::One, b);
+    }
+
+    /// A fresh witness `c` with the constraint `a · b = c`
+    pub fn mul(
+        &mut self,
+        a: impl Into<LinearCombination<F>>,
+        b: impl Into<LinearCombination<F>>,
+    ) -> Variable {
+        let c = self.witness();
+        self.enforce(a, b, c);
+        c
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    pub fn build(self) -> R1CS<F> {
+        let num_columns = 1 + self.num_instance + self.num_witness;
+        let column = |variable: Variable| match variable {
+            Variable::One => 0,
+            Variable::Instance(i) => 1 + i,
+            Variable::Witness(i) => 1 + self.num_instance + i,
+        };
+        let matrix = |k: usize| {
+            let rows = self
+                .constraints
+                .iter()
+                .map(|constraint| {
+                    constraint[k]
+                        .terms
+                        .iter()
+                        .map(|(variable, value)| (column(*variable), *value))
+                        .collect()
+                })
+                .collect();
+            SparseMatrix::new(num_columns, rows)
+        };
+        R1CS {
+            num_instance: self.num_instance,
+            num_witness: self.num_witness,
+            a: matrix(0),
+            b: matrix(1),
+            c: matrix(2),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Small constraint systems shared by the tests of the R1CS-based SNARKs,
+//! so that every backend proves the same statements
+
+use finite_fields::Field;
+
+use crate::{ConstraintSystem, LinearCombination, Variable, R1CS};
+
+/// `x^3 + x + 5 = out` with `out` public, so that `z = (1, out, x, x^2,
+/// x^3)`
+pub fn cubic<F: Field>() -> R1CS<F> {
+    let mut cs = ConstraintSystem::new();
+    let out = cs.instance();
+    let x = cs.witness();
+    let x_squared = cs.mul(x, x);
+    let x_cubed = cs.mul(x_squared, x);
+    cs.enforce_equal(
+        LinearCombination::from(x_cubed) + x + LinearCombination::constant(F::from_u64(5)),
+        out,
+    );
+    cs.build()
+}
+
+/// `c = k a^2 b^2` with `c` public, as `(ab)^2` and then `k (ab)^2`, the
+/// statement of halo2's introductory example
+pub fn scaled_square_product<F: Field>(k: F) -> R1CS<F> {
+    let mut cs = ConstraintSystem::new();
+    let c = cs.instance();
+    let a = cs.witness();
+    let b = cs.witness();
+    let ab = cs.mul(a, b);
+    let absq = cs.mul(ab, ab);
+    cs.enforce(LinearCombination::from(absq) * k, Variable::One, c);
+    cs.build()
+}
+
+/// The witness `a`, `b`, `ab`, `(ab)^2` of [`scaled_square_product`]
+pub fn scaled_square_product_witness<F: Field>(a: F, b: F) -> Vec<F> {
+    let ab = a * b;
+    vec![a, b, ab, ab.square()]
+}
@@ -0,0 +1,155 @@
+//! Rank-1 constraint systems: matrices `A`, `B`, `C` with `m` rows and an
+//! assignment `z = (1, x, w)` of a public instance `x` and a private
+//! witness `w`, satisfying
+//!
+//! `(A z) ∘ (B z) = C z`
+//!
+//! row by row. Every row is one constraint `<A_i, z> · <B_i, z> =
+//! <C_i, z>`, so each allows a single multiplication of linear
+//! combinations. This is the target of a frontend compiler and the input
+//! to QAP-based and sumcheck-based SNARKs.
+//!
+//! [`ConstraintSystem`] allocates variables and collects constraints, and
+//! builds the sparse matrices of an [`R1CS`].
+//! [`examples`] has the statements the SNARKs of the workspace test with.
+
+pub mod builder;
+pub mod examples;
+pub mod matrix;
+
+pub use builder::{ConstraintSystem, LinearCombination, Variable};
+pub use matrix::SparseMatrix;
+
+use finite_fields::Field;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum R1csError {
+    /// The instance does not have one value per instance variable
+    WrongInstanceLength { expected: usize, actual: usize },
+    /// The witness does not have one value per witness variable
+    WrongWitnessLength { expected: usize, actual: usize },
+    /// The assignment violates the constraint at this row
+    Unsatisfied(usize),
+}
+
+/// The matrices of a constraint system, with columns indexing
+/// `z = (1, x, w)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct R1CS<F> {
+    pub num_instance: usize,
+    pub num_witness: usize,
+    pub a: SparseMatrix<F>,
+    pub b: SparseMatrix<F>,
+    pub c: SparseMatrix<F>,
+}
+
+impl<F: Field> R1CS<F> {
+    pub fn num_constraints(&self) -> usize {
+        self.a.num_rows()
+    }
+
+    /// Length of `z`, the constant one included
+    pub fn num_variables(&self) -> usize {
+        1 + self.num_instance + self.num_witness
+    }
+
+    /// `z = (1, x, w)`, checking the lengths of `x` and `w`
+    pub fn assignment(&self, instance: &[F], witness: &[F]) -> Result<Vec<F>, R1csError> {
+        if instance.len() != self.num_instance {
+            return Err(R1csError::WrongInstanceLength {
+                expected: self.num_instance,
+                actual: instance.len(),
+            });
+        }
+        if witness.len() != self.num_witness {
+            return Err(R1csError::WrongWitnessLength {
+                expected: self.num_witness,
+                actual: witness.len(),
+            });
+        }
+        Ok(std::iter::once(F::one())
+            .chain(instance.iter().copied())
+            .chain(witness.iter().copied())
+            .collect())
+    }
+
+    /// Checks every constraint, returning the first failing row
+    pub fn is_satisfied(&self, instance: &[F], witness: &[F]) -> Result<(), R1csError> {
+        let z = self.assignment(instance, witness)?;
+        match (0..self.num_constraints())
+            .find(|i| self.a.row_dot(*i, &z) * self.b.row_dot(*i, &z) != self.c.row_dot(*i, &z))
+        {
+            Some(row) => Err(R1csError::Unsatisfied(row)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use finite_fields::PrimeField;
+
+    use super::*;
+    use crate::examples::cubic;
+
+    type Fq = PrimeField<2147483647>;
+
+    #[test]
+    fn matrices_follow_the_layout_of_z() {
+        let r1cs = cubic::<Fq>();
+        assert_eq!(r1cs.num_constraints(), 3);
+        assert_eq!(r1cs.num_variables(), 5);
+        // z = (1, out, x, x^2, x^3)
+        let one = Fq::from_u64(1);
+        assert_eq!(r1cs.a.row(1), [(3, one)]);
+        assert_eq!(r1cs.b.row(1), [(2, one)]);
+        assert_eq!(r1cs.c.row(1), [(4, one)]);
+        assert_eq!(
+            r1cs.a.to_dense()[2],
+            [5, 0, 1, 0, 1].map(Fq::from_u64).to_vec()
+        );
+        assert_eq!(r1cs.c.row(2), [(1, one)]);
+    }
+
+    #[test]
+    fn satisfiability_reports_the_first_failing_row() {
+        let r1cs = cubic::<Fq>();
+        let witness = |values: [u64; 3]| values.map(Fq::from_u64).to_vec();
+        let instance = [Fq::from_u64(35)];
+        assert_eq!(r1cs.is_satisfied(&instance, &witness([3, 9, 27])), Ok(()));
+
+        assert_eq!(
+            r1cs.is_satisfied(&instance, &witness([3, 10, 27])),
+            Err(R1csError::Unsatisfied(0))
+        );
+        assert_eq!(
+            r1cs.is_satisfied(&instance, &witness([3, 9, 28])),
+            Err(R1csError::Unsatisfied(1))
+        );
+        assert_eq!(
+            r1cs.is_satisfied(&[Fq::from_u64(36)], &witness([3, 9, 27])),
+            Err(R1csError::Unsatisfied(2))
+        );
+        assert_eq!(
+            r1cs.is_satisfied(&instance, &witness([3, 9, 27])[..2]),
+            Err(R1csError::WrongWitnessLength {
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn duplicate_terms_are_merged() {
+        let mut cs = ConstraintSystem::<Fq>::new();
+        let x = cs.witness();
+        cs.enforce(
+            LinearCombination::from(x) + x - x,
+            LinearCombination::from(x) - x,
+            Variable::One,
+        );
+        let r1cs = cs.build();
+        assert_eq!(r1cs.a.row(0), [(1, Fq::from_u64(1))]);
+        assert!(r1cs.b.row(0).is_empty());
+    }
+}
@@ -0,0 +1,82 @@
+use finite_fields::Field;
+
+/// A matrix stored row by row, keeping only the non-zero entries of each
+/// row as `(column, value)` by increasing column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseMatrix<F> {
+    num_columns: usize,
+    rows: Vec<Vec<(usize, F)>>,
+}
+
+impl<F: Field> SparseMatrix<F> {
+    /// A matrix with the given rows. Entries of the same column are summed
+    /// and zeros dropped.
+    pub fn new(num_columns: usize, rows: Vec<Vec<(usize, F)>>) -> Self {
+        let rows = rows
+            .into_iter()
+            .map(|mut row| {
+                assert!(
+                    row.iter().all(|(column, _)| *column < num_columns),
+                    "column out of range"
+                );
+                row.sort_by_key(|(column, _)| *column);
+                let mut merged: Vec<(usize, F)> = Vec::with_capacity(row.len());
+                for (column, value) in row {
+                    match merged.last_mut() {
+                        Some((last, sum)) if *last == column => *sum = *sum + value,
+                        _ => merged.push((column, value)),
+                    }
+                }
+                merged.retain(|(_, value)| !value.is_zero());
+                merged
+            })
+            .collect();
+        Self { num_columns, rows }
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.num_columns
+    }
+
+    pub fn row(&self, i: usize) -> &[(usize, F)] {
+        &self.rows[i]
+    }
+
+    /// The non-zero entries as `(row, column, value)`
+    pub fn entries(&self) -> impl Iterator<Item = (usize, usize, F)> + '_ {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| row.iter().map(move |(j, value)| (i, *j, *value)))
+    }
+
+    /// Inner product of row `i` with `z`
+    pub fn row_dot(&self, i: usize, z: &[F]) -> F {
+        self.rows[i]
+            .iter()
+            .fold(F::zero(), |acc, (j, value)| acc + *value * z[*j])
+    }
+
+    /// `M z`
+    pub fn mul_vector(&self, z: &[F]) -> Vec<F> {
+        assert_eq!(z.len(), self.num_columns, "one value per column");
+        (0..self.num_rows()).map(|i| self.row_dot(i, z)).collect()
+    }
+
+    pub fn to_dense(&self) -> Vec<Vec<F>> {
+        self.rows
+            .iter()
+            .map(|row| {
+                let mut dense = vec![F::zero(); self.num_columns];
+                for (j, value) in row {
+                    dense[*j] = *value;
+                }
+                dense
+            })
+            .collect()
+    }
+}
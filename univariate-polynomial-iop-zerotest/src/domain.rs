@@ -0,0 +1,128 @@
+use finite_fields::Field;
+use polynomial::Polynomial;
+
+/// A multiplicative subgroup `H = {1, ω, ω^2, ..., ω^(k-1)}` of a field,
+/// generated by a `k`-th root of unity `ω`
+#[derive(Debug, Clone, Copy)]
+pub struct Domain<F> {
+    generator: F,
+    size: usize,
+}
+
+impl<F: Field> Domain<F> {
+    /// Generate the domain from a primitive `size`-th root of unity
+    pub fn new(generator: F, size: usize) -> Self {
+        assert!(size > 0);
+        assert_eq!(
+            generator.pow([size as u64]),
+            F::one(),
+            "generator is not a root of unity of order {size}"
+        );
+        assert!(
+            (1..size).all(|k| generator.pow([k as u64]) != F::one()),
+            "generator is not a primitive root of unity of order {size}"
+        );
+        Self { generator, size }
+    }
+
+    pub fn generator(&self) -> F {
+        self.generator
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Elements `ω^0, ω^1, ..., ω^(k-1)` in order
+    pub fn elements(&self) -> Vec<F> {
+        std::iter::successors(Some(F::one()), |x| Some(*x * self.generator))
+            .take(self.size)
+            .collect()
+    }
+
+    /// Vanishing polynomial `Z_H(x) = x^k - 1` of the domain, since every
+    /// element of `H` is a `k`-th root of unity
+    pub fn vanishing_polynomial(&self) -> Polynomial<F> {
+        let mut coeffs = vec![F::zero(); self.size + 1];
+        coeffs[0] = -F::one();
+        coeffs[self.size] = F::one();
+        Polynomial::new_from_coeffs(&coeffs)
+    }
+
+    /// Evaluates `Z_H(r) = r^k - 1` without building the polynomial
+    pub fn evaluate_vanishing_polynomial(&self, point: F) -> F {
+        point.pow([self.size as u64]) - F::one()
+    }
+
+    /// The polynomial of degree less than `k` taking value `evals[i]` at
+    /// `ω^i`
+    pub fn interpolate(&self, evals: &[F]) -> Polynomial<F> {
+        assert_eq!(evals.len(), self.size, "one evaluation per domain element");
+        let points: Vec<(F, F)> = self
+            .elements()
+            .into_iter()
+            .zip(evals.iter().copied())
+            .collect();
+        Polynomial::new_from_evals_lagrange(&points)
+    }
+
+    /// `p(ω · x)`: its evaluation at `ω^i` is that of `p` at the next
+    /// element `ω^(i+1)`, wrapping around at the end of the domain
+    pub fn shifted(&self, polynomial: &Polynomial<F>) -> Polynomial<F> {
+        let coeffs: Vec<F> = polynomial
+            .get_raw_coefficients()
+            .into_iter()
+            .zip(std::iter::successors(Some(F::one()), |power| {
+                Some(*power * self.generator)
+            }))
+            .map(|(coeff, power)| coeff * power)
+            .collect();
+        Polynomial::new_from_coeffs(&coeffs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fq;
+
+    #[test]
+    fn subgroup_of_order_8() {
+        let domain = Domain::new(Fq::from(3), 8);
+        let elements = domain.elements();
+        assert_eq!(
+            elements,
+            [1, 3, 9, 27, 40, 38, 32, 14].map(Fq::from).to_vec()
+        );
+
+        let vanishing = domain.vanishing_polynomial();
+        for x in elements {
+            assert_eq!(vanishing.eval(x), Fq::from(0));
+        }
+        assert_eq!(
+            vanishing.eval(Fq::from(2)),
+            domain.evaluate_vanishing_polynomial(Fq::from(2))
+        );
+    }
+
+    #[test]
+    fn interpolate_and_shift() {
+        let domain = Domain::new(Fq::from(3), 8);
+        let evals = [5, 0, 7, 1, 1, 2, 40, 9].map(Fq::from);
+        let polynomial = domain.interpolate(&evals);
+        assert!(polynomial.leading_degree().unwrap() < 8);
+
+        let shifted = domain.shifted(&polynomial);
+        for (i, x) in domain.elements().into_iter().enumerate() {
+            assert_eq!(polynomial.eval(x), evals[i]);
+            assert_eq!(shifted.eval(x), evals[(i + 1) % 8]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_non_primitive_root() {
+        // 9 = 3^2 only has order 4
+        Domain::new(Fq::from(9), 8);
+    }
+}
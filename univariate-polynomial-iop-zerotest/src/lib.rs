@@ -1,5 +1,20 @@
+//! Interactive oracle proofs over univariate polynomials, written for any
+//! [`finite_fields::Field`]. Tests run over `Fq` mod 41, small enough for
+//! every challenge to be enumerated.
+
 use ark_ff::fields::{Fp64, MontBackend, MontConfig};
 
+pub mod domain;
+pub mod logup;
+pub mod lookup;
+pub mod oracle;
+pub mod permutation;
+pub mod plookup;
+pub mod productcheck;
+pub mod snark;
+pub mod sumcheck;
+pub mod zerotest;
+
 /// 41 = (2^3 x 5) + 1
 /// Hence, a 8th root of unity would exist in this, since 2^3 is a divisor
 /// 3 is such root of unity. 3^8 = 6561. And 6561 % 41 = 1
@@ -10,17 +25,6 @@ use ark_ff::fields::{Fp64, MontBackend, MontConfig};
 pub struct FqConfig;
 pub type Fq = Fp64<MontBackend<FqConfig, 1>>;
 
-pub enum PolynomialRepr<T> {
-    Points(Vec<(T, T)>),
-    Roots(Vec<T>),
-    Coeff(Vec<T>),
-}
-
-/// A polynomial
-pub struct Polynomial<T> {
-    repr: PolynomialRepr<T>,
-}
-
 #[cfg(test)]
 mod tests {
     use ark_ff::{BigInt, Field, PrimeField};
@@ -0,0 +1,419 @@
+//! LogUp: the prover convinces the verifier that every value of a witness
+//! `f` on `H` is contained in the values of a table `t` on `H`, by the
+//! logarithmic derivative of `Π_i (X + f_i) = Π_j (X + t_j)^(m_j)`,
+//!
+//! `Σ_i 1 / (β + f_i) = Σ_j m_j / (β + t_j)`,
+//!
+//! where the multiplicity `m_j` counts the occurrences of `t_j` in the
+//! witness. As rational functions in `β`, both sides agree iff every `f_i`
+//! is some `t_j`, as long as the characteristic exceeds `k`.
+//!
+//! 1. The prover sends `m` interpolating the multiplicities on `H`.
+//! 2. The verifier sends `β`, the prover sends the helpers `h_f` and `h_t`
+//!    with `h_f(a) = 1 / (β + f(a))` and `h_t(a) = m(a) / (β + t(a))`.
+//! 3. The verifier sends `λ`. A zero-test shows the helpers are what they
+//!    claim to be,
+//!
+//!    `h_f(x) (β + f(x)) - 1 + λ (h_t(x) (β + t(x)) - m(x)) = 0` on `H`,
+//!
+//!    and a univariate sumcheck that `h_f - h_t` sums to zero over `H`.
+//!
+//! Unlike Plookup and halo2, nothing is sorted and the witness may repeat
+//! table values any number of times at no extra cost.
+
+use finite_fields::Field;
+pub use multivariate_sumcheck::logup::{multiplicities, LogUpError};
+use polynomial::Polynomial;
+use rand::RngCore;
+
+use crate::{
+    domain::Domain,
+    oracle::PolynomialOracle,
+    sumcheck::{SumcheckError, SumcheckProof, SumcheckProver, SumcheckVerifier},
+    zerotest::{ZeroTestError, ZeroTestProver, ZeroTestVerifier},
+};
+
+impl From<ZeroTestError> for LogUpError {
+    fn from(error: ZeroTestError) -> Self {
+        match error {
+            ZeroTestError::NotZeroOnDomain => Self::NotInTable,
+        }
+    }
+}
+
+impl From<SumcheckError> for LogUpError {
+    fn from(error: SumcheckError) -> Self {
+        match error {
+            SumcheckError::WrongSum => Self::NotInTable,
+        }
+    }
+}
+
+/// The prover's second message: `h_f` and `h_t`
+#[derive(Debug, Clone)]
+pub struct LogUpHelpers<F> {
+    pub witness: Polynomial<F>,
+    pub table: Polynomial<F>,
+}
+
+/// The prover's last message: the quotient of the zero-test on the helpers
+/// and the sumcheck of `h_f - h_t`
+#[derive(Debug, Clone)]
+pub struct LogUpProof<F> {
+    pub quotient: Polynomial<F>,
+    pub sumcheck: SumcheckProof<F>,
+}
+
+pub struct LogUpProver<F> {
+    domain: Domain<F>,
+    f: Polynomial<F>,
+    t: Polynomial<F>,
+}
+
+impl<F: Field> LogUpProver<F> {
+    pub fn new(domain: Domain<F>, f: Polynomial<F>, t: Polynomial<F>) -> Self {
+        Self { domain, f, t }
+    }
+
+    fn values(&self, p: &Polynomial<F>) -> Vec<F> {
+        self.domain
+            .elements()
+            .into_iter()
+            .map(|a| p.eval(a))
+            .collect()
+    }
+
+    /// The prover's first message: `m` interpolating the multiplicities of
+    /// the table values in the witness
+    pub fn multiplicities(&self) -> Result<Polynomial<F>, LogUpError> {
+        let counts = multiplicities(&self.values(&self.f), &self.values(&self.t))?;
+        Ok(self.domain.interpolate(&counts))
+    }
+
+    /// `h_f` and `h_t` once the verifier has sent `β`
+    pub fn helpers(&self, beta: F) -> Result<LogUpHelpers<F>, LogUpError> {
+        let counts = multiplicities(&self.values(&self.f), &self.values(&self.t))?;
+        let inverses = |p: &Polynomial<F>| -> Result<Vec<F>, LogUpError> {
+            self.values(p)
+                .into_iter()
+                .map(|value| {
+                    (beta + value)
+                        .inverse()
+                        .ok_or(LogUpError::DegenerateChallenge)
+                })
+                .collect()
+        };
+        let table: Vec<F> = inverses(&self.t)?
+            .into_iter()
+            .zip(counts)
+            .map(|(inverse, count)| inverse * count)
+            .collect();
+        Ok(LogUpHelpers {
+            witness: self.domain.interpolate(&inverses(&self.f)?),
+            table: self.domain.interpolate(&table),
+        })
+    }
+
+    /// Runs the zero-test and the sumcheck once the verifier has sent `β`
+    /// and `λ`
+    pub fn prove(&self, beta: F, lambda: F) -> Result<LogUpProof<F>, LogUpError> {
+        let multiplicities = self.multiplicities()?;
+        let helpers = self.helpers(beta)?;
+        self.prove_with_helpers(&multiplicities, &helpers, beta, lambda)
+    }
+
+    /// `h_f(x) (β + f(x)) - 1 + λ (h_t(x) (β + t(x)) - m(x))`
+    fn constraint(
+        &self,
+        multiplicities: &Polynomial<F>,
+        helpers: &LogUpHelpers<F>,
+        beta: F,
+        lambda: F,
+    ) -> Polynomial<F> {
+        let constant = |value: F| Polynomial::new_from_coeffs(&[value]);
+        let witness = &(&helpers.witness * &(&self.f + &constant(beta))) - &constant(F::one());
+        let table = &(&helpers.table * &(&self.t + &constant(beta))) - multiplicities;
+        &witness + &(&table * &constant(lambda))
+    }
+
+    fn prove_with_helpers(
+        &self,
+        multiplicities: &Polynomial<F>,
+        helpers: &LogUpHelpers<F>,
+        beta: F,
+        lambda: F,
+    ) -> Result<LogUpProof<F>, LogUpError> {
+        let constraint = self.constraint(multiplicities, helpers, beta, lambda);
+        let quotient = ZeroTestProver::new(self.domain, constraint).quotient()?;
+        let sumcheck =
+            SumcheckProver::new(self.domain, &helpers.witness - &helpers.table).prove(F::zero())?;
+        Ok(LogUpProof { quotient, sumcheck })
+    }
+}
+
+/// Oracle for `h_f(x) (β + f(x)) - 1 + λ (h_t(x) (β + t(x)) - m(x))`
+struct ConstraintOracle<'a, F, Of, Ot, Om, Ohf, Oht> {
+    beta: F,
+    lambda: F,
+    f: &'a Of,
+    t: &'a Ot,
+    multiplicities: &'a Om,
+    witness_helper: &'a Ohf,
+    table_helper: &'a Oht,
+}
+
+impl<F, Of, Ot, Om, Ohf, Oht> PolynomialOracle<F> for ConstraintOracle<'_, F, Of, Ot, Om, Ohf, Oht>
+where
+    F: Field,
+    Of: PolynomialOracle<F>,
+    Ot: PolynomialOracle<F>,
+    Om: PolynomialOracle<F>,
+    Ohf: PolynomialOracle<F>,
+    Oht: PolynomialOracle<F>,
+{
+    fn query(&self, point: F) -> F {
+        let witness =
+            self.witness_helper.query(point) * (self.beta + self.f.query(point)) - F::one();
+        let table = self.table_helper.query(point) * (self.beta + self.t.query(point))
+            - self.multiplicities.query(point);
+        witness + self.lambda * table
+    }
+
+    fn degree_bound(&self) -> usize {
+        (self.witness_helper.degree_bound() + self.f.degree_bound())
+            .max(self.table_helper.degree_bound() + self.t.degree_bound())
+            .max(self.multiplicities.degree_bound())
+    }
+}
+
+/// Oracle for `h_f - h_t`
+struct DifferenceOracle<'a, Ohf, Oht> {
+    witness_helper: &'a Ohf,
+    table_helper: &'a Oht,
+}
+
+impl<F, Ohf, Oht> PolynomialOracle<F> for DifferenceOracle<'_, Ohf, Oht>
+where
+    F: Field,
+    Ohf: PolynomialOracle<F>,
+    Oht: PolynomialOracle<F>,
+{
+    fn query(&self, point: F) -> F {
+        self.witness_helper.query(point) - self.table_helper.query(point)
+    }
+
+    fn degree_bound(&self) -> usize {
+        self.witness_helper
+            .degree_bound()
+            .max(self.table_helper.degree_bound())
+    }
+}
+
+pub struct LogUpVerifier<F> {
+    domain: Domain<F>,
+}
+
+impl<F: Field> LogUpVerifier<F> {
+    pub fn new(domain: Domain<F>) -> Self {
+        Self { domain }
+    }
+
+    /// Samples `β` and `λ`, and later the evaluation point `r` shared by
+    /// the zero-test and the sumcheck
+    pub fn sample_challenge<R: RngCore>(&self, rng: &mut R) -> F {
+        F::from_u64(rng.next_u64())
+    }
+
+    /// Checks the zero-test on the helpers and that `h_f - h_t` sums to zero
+    /// given oracles to the witness, the table, the multiplicities and the
+    /// helpers
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify<Of, Ot, Om, Ohf, Oht>(
+        &self,
+        f: &Of,
+        t: &Ot,
+        multiplicities: &Om,
+        witness_helper: &Ohf,
+        table_helper: &Oht,
+        proof: &LogUpProof<F>,
+        beta: F,
+        lambda: F,
+        challenge: F,
+    ) -> bool
+    where
+        Of: PolynomialOracle<F>,
+        Ot: PolynomialOracle<F>,
+        Om: PolynomialOracle<F>,
+        Ohf: PolynomialOracle<F>,
+        Oht: PolynomialOracle<F>,
+    {
+        let constraint = ConstraintOracle {
+            beta,
+            lambda,
+            f,
+            t,
+            multiplicities,
+            witness_helper,
+            table_helper,
+        };
+        let difference = DifferenceOracle {
+            witness_helper,
+            table_helper,
+        };
+        ZeroTestVerifier::new(self.domain).verify(&constraint, &proof.quotient, challenge)
+            && SumcheckVerifier::new(self.domain).verify(
+                &difference,
+                &proof.sumcheck.quotient,
+                &proof.sumcheck.g,
+                F::zero(),
+                challenge,
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::Fq;
+
+    fn domain() -> Domain<Fq> {
+        Domain::new(Fq::from(3), 8)
+    }
+
+    const TABLE: [u64; 8] = [0, 1, 2, 3, 5, 8, 13, 21];
+
+    fn column(values: [u64; 8]) -> Polynomial<Fq> {
+        domain().interpolate(&values.map(Fq::from))
+    }
+
+    #[test]
+    fn multiplicities_count_occurrences() {
+        let counts = multiplicities(&[5, 1, 5, 5].map(Fq::from), &[1, 3, 5, 1].map(Fq::from));
+        assert_eq!(counts, Ok([1, 0, 3, 0].map(Fq::from).to_vec()));
+        assert_eq!(
+            multiplicities(&[4].map(Fq::from), &[1, 3].map(Fq::from)),
+            Err(LogUpError::NotInTable)
+        );
+    }
+
+    #[test]
+    fn contained_witness_is_accepted() {
+        let domain = domain();
+        let f = column([8, 1, 21, 8, 8, 2, 13, 8]);
+        let t = column(TABLE);
+        let prover = LogUpProver::new(domain, f.clone(), t.clone());
+        let m = prover.multiplicities().unwrap();
+        let verifier = LogUpVerifier::new(domain);
+
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        for _ in 0..10 {
+            let (beta, lambda) = (
+                verifier.sample_challenge(&mut rng),
+                verifier.sample_challenge(&mut rng),
+            );
+            let (helpers, proof) = match prover.helpers(beta) {
+                Ok(helpers) => (helpers, prover.prove(beta, lambda).unwrap()),
+                Err(error) => {
+                    assert_eq!(error, LogUpError::DegenerateChallenge);
+                    continue;
+                }
+            };
+            let challenge = verifier.sample_challenge(&mut rng);
+            assert!(verifier.verify(
+                &f,
+                &t,
+                &m,
+                &helpers.witness,
+                &helpers.table,
+                &proof,
+                beta,
+                lambda,
+                challenge
+            ));
+        }
+    }
+
+    #[test]
+    fn missing_value_is_rejected() {
+        let domain = domain();
+        let f = column([8, 1, 21, 8, 4, 2, 13, 8]);
+        let t = column(TABLE);
+        let prover = LogUpProver::new(domain, f.clone(), t.clone());
+        assert_eq!(prover.multiplicities().unwrap_err(), LogUpError::NotInTable);
+
+        // The cheater counts the 4 as a 5. Every helper is then honest and
+        // the zero-test passes, but the sums differ by `1 / (β + 4) -
+        // 1 / (β + 5)`, which is never zero
+        let mut counts = [0u64, 1, 1, 0, 1, 3, 1, 1].map(Fq::from);
+        let m = domain.interpolate(&counts);
+        let beta = Fq::from(7);
+        let inverses: Vec<Fq> = domain
+            .elements()
+            .into_iter()
+            .map(|a| (beta + f.eval(a)).inverse().unwrap())
+            .collect();
+        for (count, value) in counts.iter_mut().zip(TABLE) {
+            *count *= (beta + Fq::from(value)).inverse().unwrap();
+        }
+        let helpers = LogUpHelpers {
+            witness: domain.interpolate(&inverses),
+            table: domain.interpolate(&counts),
+        };
+        assert_eq!(
+            prover
+                .prove_with_helpers(&m, &helpers, beta, Fq::from(2))
+                .unwrap_err(),
+            LogUpError::NotInTable
+        );
+
+        // Sending the honest zero-test quotient alongside a sumcheck for
+        // the actual sum of `h_f - h_t` fails at every point
+        let lambda = Fq::from(2);
+        let constraint = prover.constraint(&m, &helpers, beta, lambda);
+        let quotient = ZeroTestProver::new(domain, constraint).quotient().unwrap();
+        let difference = SumcheckProver::new(domain, &helpers.witness - &helpers.table);
+        let sumcheck = difference.prove(difference.sum()).unwrap();
+        assert_ne!(difference.sum(), Fq::from(0));
+        let proof = LogUpProof { quotient, sumcheck };
+        let verifier = LogUpVerifier::new(domain);
+        assert!((0..41u64).all(|r| !verifier.verify(
+            &f,
+            &t,
+            &m,
+            &helpers.witness,
+            &helpers.table,
+            &proof,
+            beta,
+            lambda,
+            Fq::from(r)
+        )));
+    }
+
+    #[test]
+    fn wrong_multiplicities_are_rejected() {
+        // With one occurrence of 8 moved onto 13 the helpers still satisfy
+        // the zero-test, but no longer sum to zero
+        let domain = domain();
+        let f = column([8, 1, 21, 8, 8, 2, 13, 8]);
+        let t = column(TABLE);
+        let prover = LogUpProver::new(domain, f, t);
+        let m = domain.interpolate(&[0u64, 1, 1, 0, 0, 3, 2, 1].map(Fq::from));
+        let beta = Fq::from(7);
+        let mut helpers = prover.helpers(beta).unwrap();
+        let table: Vec<Fq> = TABLE
+            .iter()
+            .zip([0u64, 1, 1, 0, 0, 3, 2, 1])
+            .map(|(value, count)| Fq::from(count) * (beta + Fq::from(*value)).inverse().unwrap())
+            .collect();
+        helpers.table = domain.interpolate(&table);
+        assert_eq!(
+            prover
+                .prove_with_helpers(&m, &helpers, beta, Fq::from(2))
+                .unwrap_err(),
+            LogUpError::NotInTable
+        );
+    }
+}
@@ -0,0 +1,406 @@
+//! The lookup argument of halo2: the prover convinces the verifier that
+//! every value of an input `A` on `H` is contained in the values of a table
+//! `S` on `H`.
+//!
+//! The prover permutes both columns. The permuted input `A'` has equal
+//! values next to each other, and the permuted table `S'` starts every run
+//! of equal values of `A'` with that same value. Then every `A'_i` either
+//! equals `S'_i` or the previous entry `A'_(i-1)`, which leads back to the
+//! start of its run and thus to a value of the table. The verifier checks
+//!
+//! - `A'` is a permutation of `A` and `S'` one of `S`, two grand products
+//!   `Π (β - A(x)) / (β - A'(x))` and `Π (γ - S(x)) / (γ - S'(x))` over `H`,
+//! - `(A'(x) - S'(x)) (A'(x) - A'(ω^-1 x)) = 0` on `H`, with a zero-test,
+//! - `A'(1) = S'(1)`, since the first entry has no predecessor.
+//!
+//! halo2 itself merges the two grand products into one accumulator and
+//! blinds the last rows of every column, which is left out here.
+
+use std::collections::HashMap;
+
+use finite_fields::Field;
+use polynomial::Polynomial;
+use rand::RngCore;
+
+use crate::{
+    domain::Domain,
+    oracle::PolynomialOracle,
+    permutation::{PermutationCheckError, PermutationCheckProver, PermutationCheckVerifier},
+    productcheck::ProductCheckProof,
+    zerotest::{ZeroTestError, ZeroTestProver, ZeroTestVerifier},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LookupError {
+    /// Some input value does not appear in the table
+    NotInTable,
+    /// `β` or `γ` equals a value of a permuted column; the verifier must
+    /// resample
+    DegenerateChallenge,
+}
+
+impl From<PermutationCheckError> for LookupError {
+    fn from(error: PermutationCheckError) -> Self {
+        match error {
+            PermutationCheckError::NotAPermutation => Self::NotInTable,
+            PermutationCheckError::DegenerateChallenge => Self::DegenerateChallenge,
+        }
+    }
+}
+
+impl From<ZeroTestError> for LookupError {
+    fn from(error: ZeroTestError) -> Self {
+        match error {
+            ZeroTestError::NotZeroOnDomain => Self::NotInTable,
+        }
+    }
+}
+
+/// `p(ω^-1 · x)`: its evaluation at `ω^i` is that of `p` at the previous
+/// element `ω^(i-1)`, wrapping around at the start of the domain
+fn previous<F: Field>(domain: &Domain<F>, polynomial: &Polynomial<F>) -> Polynomial<F> {
+    let inverse = domain.generator().inverse().expect("non-zero");
+    let coeffs: Vec<F> = polynomial
+        .get_raw_coefficients()
+        .into_iter()
+        .zip(std::iter::successors(Some(F::one()), |power| {
+            Some(*power * inverse)
+        }))
+        .map(|(coeff, power)| coeff * power)
+        .collect();
+    Polynomial::new_from_coeffs(&coeffs)
+}
+
+/// The permuted input, with equal values grouped, and the permuted table,
+/// which starts every group with its value
+fn permute_columns<F: Field>(input: &[F], table: &[F]) -> Result<(Vec<F>, Vec<F>), LookupError> {
+    let mut permuted_input = input.to_vec();
+    permuted_input.sort_by_key(|value| value.to_limbs());
+
+    let mut counts: HashMap<Vec<u64>, usize> = HashMap::new();
+    for value in table {
+        *counts.entry(value.to_limbs()).or_default() += 1;
+    }
+    let mut permuted_table: Vec<Option<F>> = vec![None; table.len()];
+    for (i, value) in permuted_input.iter().enumerate() {
+        if i > 0 && permuted_input[i - 1] == *value {
+            continue;
+        }
+        match counts.get_mut(&value.to_limbs()) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => return Err(LookupError::NotInTable),
+        }
+        permuted_table[i] = Some(*value);
+    }
+
+    // The unused table values fill the remaining rows in any order
+    let mut unused = table.iter().filter(|value| {
+        let count = counts.get_mut(&value.to_limbs()).expect("counted");
+        let remaining = *count > 0;
+        *count = count.saturating_sub(1);
+        remaining
+    });
+    let permuted_table = permuted_table
+        .into_iter()
+        .map(|value| value.unwrap_or_else(|| *unused.next().expect("as many as the rows left")))
+        .collect();
+    Ok((permuted_input, permuted_table))
+}
+
+/// The prover's message after the permuted columns: the two grand products
+/// and the quotient of the zero-test on the permuted columns
+#[derive(Debug, Clone)]
+pub struct LookupProof<F> {
+    pub input: ProductCheckProof<F>,
+    pub table: ProductCheckProof<F>,
+    pub quotient: Polynomial<F>,
+}
+
+pub struct LookupProver<F> {
+    domain: Domain<F>,
+    input: Polynomial<F>,
+    table: Polynomial<F>,
+}
+
+impl<F: Field> LookupProver<F> {
+    pub fn new(domain: Domain<F>, input: Polynomial<F>, table: Polynomial<F>) -> Self {
+        Self {
+            domain,
+            input,
+            table,
+        }
+    }
+
+    /// The prover's first message: `A'` and `S'` interpolating the permuted
+    /// input and the permuted table
+    pub fn permuted(&self) -> Result<(Polynomial<F>, Polynomial<F>), LookupError> {
+        let elements = self.domain.elements();
+        let input: Vec<F> = elements.iter().map(|a| self.input.eval(*a)).collect();
+        let table: Vec<F> = elements.iter().map(|a| self.table.eval(*a)).collect();
+        let (permuted_input, permuted_table) = permute_columns(&input, &table)?;
+        Ok((
+            self.domain.interpolate(&permuted_input),
+            self.domain.interpolate(&permuted_table),
+        ))
+    }
+
+    /// Runs both grand products and the zero-test once the verifier has
+    /// sent `β` and `γ`
+    pub fn prove(&self, beta: F, gamma: F) -> Result<LookupProof<F>, LookupError> {
+        let (permuted_input, permuted_table) = self.permuted()?;
+        self.prove_with_permuted(&permuted_input, &permuted_table, beta, gamma)
+    }
+
+    fn prove_with_permuted(
+        &self,
+        permuted_input: &Polynomial<F>,
+        permuted_table: &Polynomial<F>,
+        beta: F,
+        gamma: F,
+    ) -> Result<LookupProof<F>, LookupError> {
+        let constraint = &(permuted_input - permuted_table)
+            * &(permuted_input - &previous(&self.domain, permuted_input));
+        let quotient = ZeroTestProver::new(self.domain, constraint).quotient()?;
+        let input =
+            PermutationCheckProver::new(self.domain, self.input.clone(), permuted_input.clone())
+                .prove(beta)?;
+        let table =
+            PermutationCheckProver::new(self.domain, self.table.clone(), permuted_table.clone())
+                .prove(gamma)?;
+        Ok(LookupProof {
+            input,
+            table,
+            quotient,
+        })
+    }
+}
+
+/// Oracle for `(A'(x) - S'(x)) (A'(x) - A'(ω^-1 x))`
+struct ConstraintOracle<'a, F, Oa, Os> {
+    domain: &'a Domain<F>,
+    permuted_input: &'a Oa,
+    permuted_table: &'a Os,
+}
+
+impl<F, Oa, Os> PolynomialOracle<F> for ConstraintOracle<'_, F, Oa, Os>
+where
+    F: Field,
+    Oa: PolynomialOracle<F>,
+    Os: PolynomialOracle<F>,
+{
+    fn query(&self, point: F) -> F {
+        let previous = point * self.domain.generator().inverse().expect("non-zero");
+        let value = self.permuted_input.query(point);
+        (value - self.permuted_table.query(point)) * (value - self.permuted_input.query(previous))
+    }
+
+    fn degree_bound(&self) -> usize {
+        let degree = self.permuted_input.degree_bound();
+        degree + degree.max(self.permuted_table.degree_bound())
+    }
+}
+
+pub struct LookupVerifier<F> {
+    domain: Domain<F>,
+}
+
+impl<F: Field> LookupVerifier<F> {
+    pub fn new(domain: Domain<F>) -> Self {
+        Self { domain }
+    }
+
+    /// Samples `β` and `γ`, and later the evaluation point `r` of the
+    /// zero-tests
+    pub fn sample_challenge<R: RngCore>(&self, rng: &mut R) -> F {
+        F::from_u64(rng.next_u64())
+    }
+
+    /// Checks the first row, both grand products and the zero-test on the
+    /// permuted columns given oracles to the input, the table and their
+    /// permutations
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify<Oa, Os, Opa, Ops>(
+        &self,
+        input: &Oa,
+        table: &Os,
+        permuted_input: &Opa,
+        permuted_table: &Ops,
+        proof: &LookupProof<F>,
+        beta: F,
+        gamma: F,
+        challenge: F,
+    ) -> bool
+    where
+        Oa: PolynomialOracle<F>,
+        Os: PolynomialOracle<F>,
+        Opa: PolynomialOracle<F>,
+        Ops: PolynomialOracle<F>,
+    {
+        let permutation = PermutationCheckVerifier::new(self.domain);
+        let constraint = ConstraintOracle {
+            domain: &self.domain,
+            permuted_input,
+            permuted_table,
+        };
+        permuted_input.query(F::one()) == permuted_table.query(F::one())
+            && permutation.verify(
+                input,
+                permuted_input,
+                &proof.input.accumulator,
+                &proof.input.quotient,
+                beta,
+                challenge,
+            )
+            && permutation.verify(
+                table,
+                permuted_table,
+                &proof.table.accumulator,
+                &proof.table.quotient,
+                gamma,
+                challenge,
+            )
+            && ZeroTestVerifier::new(self.domain).verify(&constraint, &proof.quotient, challenge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::Fq;
+
+    fn domain() -> Domain<Fq> {
+        Domain::new(Fq::from(3), 8)
+    }
+
+    const TABLE: [u64; 8] = [0, 1, 2, 3, 5, 8, 13, 21];
+
+    fn column(values: [u64; 8]) -> Polynomial<Fq> {
+        domain().interpolate(&values.map(Fq::from))
+    }
+
+    #[test]
+    fn permuted_columns_start_every_run() {
+        let (permuted_input, permuted_table) =
+            permute_columns(&[5, 1, 5, 0].map(Fq::from), &[0, 1, 3, 5].map(Fq::from)).unwrap();
+        assert_eq!(permuted_input, [0, 1, 5, 5].map(Fq::from));
+        assert_eq!(permuted_table, [0, 1, 5, 3].map(Fq::from));
+        assert_eq!(
+            permute_columns(&[4].map(Fq::from), &[1].map(Fq::from)),
+            Err(LookupError::NotInTable)
+        );
+    }
+
+    #[test]
+    fn contained_input_is_accepted() {
+        let domain = domain();
+        let input = column([8, 1, 21, 8, 8, 2, 13, 0]);
+        let table = column(TABLE);
+        let prover = LookupProver::new(domain, input.clone(), table.clone());
+        let (permuted_input, permuted_table) = prover.permuted().unwrap();
+        let verifier = LookupVerifier::new(domain);
+
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        for _ in 0..10 {
+            let (beta, gamma) = (
+                verifier.sample_challenge(&mut rng),
+                verifier.sample_challenge(&mut rng),
+            );
+            let proof = match prover.prove(beta, gamma) {
+                Ok(proof) => proof,
+                Err(error) => {
+                    assert_eq!(error, LookupError::DegenerateChallenge);
+                    continue;
+                }
+            };
+            let challenge = verifier.sample_challenge(&mut rng);
+            assert!(verifier.verify(
+                &input,
+                &table,
+                &permuted_input,
+                &permuted_table,
+                &proof,
+                beta,
+                gamma,
+                challenge
+            ));
+        }
+    }
+
+    #[test]
+    fn missing_value_is_rejected() {
+        let domain = domain();
+        // 4 is not in the table
+        let input = column([8, 1, 21, 4, 8, 2, 13, 0]);
+        let table = column(TABLE);
+        let prover = LookupProver::new(domain, input, table);
+        assert_eq!(prover.permuted().unwrap_err(), LookupError::NotInTable);
+
+        // Permuting the table honestly leaves 4 with neither its table row
+        // nor its predecessor to match, so no quotient exists
+        let permuted_input = column([0, 1, 2, 4, 8, 8, 13, 21]);
+        let permuted_table = column([0, 1, 2, 3, 8, 5, 13, 21]);
+        for gamma in 0..41u64 {
+            assert_eq!(
+                prover
+                    .prove_with_permuted(
+                        &permuted_input,
+                        &permuted_table,
+                        Fq::from(6),
+                        Fq::from(gamma)
+                    )
+                    .unwrap_err(),
+                LookupError::NotInTable
+            );
+        }
+
+        // Writing 4 into the permuted table satisfies the zero-test, but
+        // then it is not a permutation of the table. Two distinct multisets
+        // of size 8 agree for at most 8 values of `γ`.
+        let permuted_table = column([0, 1, 2, 4, 8, 5, 13, 21]);
+        let accepted = (0..41u64)
+            .filter(|gamma| {
+                prover
+                    .prove_with_permuted(
+                        &permuted_input,
+                        &permuted_table,
+                        Fq::from(6),
+                        Fq::from(*gamma),
+                    )
+                    .is_ok()
+            })
+            .count();
+        assert!(accepted <= 8, "{accepted} challenges accepted");
+    }
+
+    #[test]
+    fn first_row_is_checked() {
+        let domain = domain();
+        let input = column([1, 1, 1, 1, 1, 1, 1, 1]);
+        let table = column(TABLE);
+        let prover = LookupProver::new(domain, input.clone(), table.clone());
+        let (permuted_input, permuted_table) = prover.permuted().unwrap();
+        let (beta, gamma) = (Fq::from(6), Fq::from(9));
+
+        // The permuted input is constant, so any permutation of the table
+        // passes the zero-test. Rotating the permuted table by one row
+        // leaves only the first row to catch the mismatch.
+        let rotated = domain.shifted(&permuted_table);
+        let proof = prover
+            .prove_with_permuted(&permuted_input, &rotated, beta, gamma)
+            .unwrap();
+        let verifier = LookupVerifier::new(domain);
+        assert!(!verifier.verify(
+            &input,
+            &table,
+            &permuted_input,
+            &rotated,
+            &proof,
+            beta,
+            gamma,
+            Fq::from(5)
+        ));
+    }
+}
@@ -0,0 +1,59 @@
+use finite_fields::Field;
+use polynomial::Polynomial;
+
+/// Oracle access to a polynomial: in an IOP the verifier never reads a
+/// prover's polynomial in full, it may only ask for evaluations at points
+/// of its choosing. Compiling the IOP into a SNARK replaces the oracle by
+/// a polynomial commitment with evaluation proofs.
+pub trait PolynomialOracle<F> {
+    /// Evaluation of the underlying polynomial at `point`
+    fn query(&self, point: F) -> F;
+
+    /// Bound on the degree of the underlying polynomial. Oracles are
+    /// degree-bounded by definition in the IOP model; once compiled, the
+    /// bound is enforced by the size of the commitment key.
+    fn degree_bound(&self) -> usize;
+}
+
+/// An honest oracle answering every query by evaluating the polynomial
+impl<F: Field> PolynomialOracle<F> for Polynomial<F> {
+    fn query(&self, point: F) -> F {
+        self.eval(point)
+    }
+
+    fn degree_bound(&self) -> usize {
+        self.leading_degree().unwrap_or(0)
+    }
+}
+
+/// An oracle that counts the queries made to it, handy for checking that a
+/// verifier stays within its query budget
+pub struct CountingOracle<O> {
+    oracle: O,
+    queries: std::cell::Cell<usize>,
+}
+
+impl<O> CountingOracle<O> {
+    pub fn new(oracle: O) -> Self {
+        Self {
+            oracle,
+            queries: std::cell::Cell::new(0),
+        }
+    }
+
+    /// Number of queries answered so far
+    pub fn queries(&self) -> usize {
+        self.queries.get()
+    }
+}
+
+impl<F, O: PolynomialOracle<F>> PolynomialOracle<F> for CountingOracle<O> {
+    fn query(&self, point: F) -> F {
+        self.queries.set(self.queries.get() + 1);
+        self.oracle.query(point)
+    }
+
+    fn degree_bound(&self) -> usize {
+        self.oracle.degree_bound()
+    }
+}
@@ -0,0 +1,469 @@
+//! Permutation check IOP: the prover convinces the verifier that the values
+//! of `f` on `H` are a permutation of the values of `g` on `H`.
+//!
+//! The multisets `{f(a)}` and `{g(a)}` are equal iff the polynomials
+//! `Π_{a ∈ H} (X - f(a))` and `Π_{a ∈ H} (X - g(a))` are. The verifier
+//! compares them at a random point `γ`, which is a rational product check
+//! of `(γ - f) / (γ - g)`. Two distinct multisets of size `k` pass for at
+//! most `k` values of `γ`.
+//!
+//! The prescribed permutation check proves `g(a) = f(W(a))` for a public
+//! permutation `W` of `H`, which is how PLONK enforces copy constraints.
+//! Since `W` is a bijection, this holds iff the multisets of pairs
+//! `{(a, f(a))}` and `{(W(a), g(a))}` are equal: the first coordinates of
+//! the former are distinct, so each `(W(a), g(a))` has to be
+//! `(W(a), f(W(a)))`. Pairs are folded into field elements with a random
+//! `β`, and the multisets compared at a random `γ`, giving the grand product
+//!
+//! `Π_{a ∈ H} (f(a) + β · a + γ) / (g(a) + β · W(a) + γ) = 1`
+
+use finite_fields::Field;
+use polynomial::Polynomial;
+use rand::RngCore;
+
+use crate::{
+    domain::Domain,
+    oracle::PolynomialOracle,
+    productcheck::{
+        ProductCheckError, ProductCheckProof, ProductCheckProver, ProductCheckVerifier,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermutationCheckError {
+    /// The values of `f` on the domain are not a permutation of those of `g`
+    NotAPermutation,
+    /// `γ` equals a value of `g` on the domain; the verifier must resample
+    DegenerateChallenge,
+}
+
+impl From<ProductCheckError> for PermutationCheckError {
+    fn from(error: ProductCheckError) -> Self {
+        match error {
+            ProductCheckError::WrongProduct => Self::NotAPermutation,
+            ProductCheckError::ZeroDenominator => Self::DegenerateChallenge,
+        }
+    }
+}
+
+/// `γ - p(x)` for a polynomial `p`
+fn offset<F: Field>(gamma: F, polynomial: &Polynomial<F>) -> Polynomial<F> {
+    &Polynomial::new_from_coeffs(&[gamma]) - polynomial
+}
+
+/// Oracle for `γ - p(x)`, answered from the oracle to `p`
+struct OffsetOracle<'a, F, O> {
+    gamma: F,
+    oracle: &'a O,
+}
+
+impl<F: Field, O: PolynomialOracle<F>> PolynomialOracle<F> for OffsetOracle<'_, F, O> {
+    fn query(&self, point: F) -> F {
+        self.gamma - self.oracle.query(point)
+    }
+
+    fn degree_bound(&self) -> usize {
+        self.oracle.degree_bound()
+    }
+}
+
+/// Oracle for `v(x) + β · i(x) + γ`, answered from the oracles to a value
+/// polynomial `v` and an index polynomial `i`
+struct FoldedPairOracle<'a, F, Ov, Oi> {
+    beta: F,
+    gamma: F,
+    value: &'a Ov,
+    index: &'a Oi,
+}
+
+impl<F, Ov, Oi> PolynomialOracle<F> for FoldedPairOracle<'_, F, Ov, Oi>
+where
+    F: Field,
+    Ov: PolynomialOracle<F>,
+    Oi: PolynomialOracle<F>,
+{
+    fn query(&self, point: F) -> F {
+        self.value.query(point) + self.beta * self.index.query(point) + self.gamma
+    }
+
+    fn degree_bound(&self) -> usize {
+        self.value.degree_bound().max(self.index.degree_bound())
+    }
+}
+
+pub struct PermutationCheckProver<F> {
+    domain: Domain<F>,
+    f: Polynomial<F>,
+    g: Polynomial<F>,
+}
+
+impl<F: Field> PermutationCheckProver<F> {
+    pub fn new(domain: Domain<F>, f: Polynomial<F>, g: Polynomial<F>) -> Self {
+        Self { domain, f, g }
+    }
+
+    /// Runs the rational product check of `(γ - f) / (γ - g)` once the
+    /// verifier has sent `γ`
+    pub fn prove(&self, gamma: F) -> Result<ProductCheckProof<F>, PermutationCheckError> {
+        Ok(ProductCheckProver::new_rational(
+            self.domain,
+            offset(gamma, &self.f),
+            offset(gamma, &self.g),
+        )
+        .prove()?)
+    }
+}
+
+pub struct PermutationCheckVerifier<F> {
+    domain: Domain<F>,
+}
+
+impl<F: Field> PermutationCheckVerifier<F> {
+    pub fn new(domain: Domain<F>) -> Self {
+        Self { domain }
+    }
+
+    /// Samples `γ`, and later the evaluation point `r` of the zero-test
+    pub fn sample_challenge<R: RngCore>(&self, rng: &mut R) -> F {
+        F::from_u64(rng.next_u64())
+    }
+
+    pub fn verify<Of, Og, Ot, Oq>(
+        &self,
+        f: &Of,
+        g: &Og,
+        accumulator: &Ot,
+        quotient: &Oq,
+        gamma: F,
+        challenge: F,
+    ) -> bool
+    where
+        Of: PolynomialOracle<F>,
+        Og: PolynomialOracle<F>,
+        Ot: PolynomialOracle<F>,
+        Oq: PolynomialOracle<F>,
+    {
+        ProductCheckVerifier::new(self.domain).verify_rational(
+            &OffsetOracle { gamma, oracle: f },
+            &OffsetOracle { gamma, oracle: g },
+            accumulator,
+            quotient,
+            challenge,
+        )
+    }
+}
+
+/// A public permutation `W` of the domain with `W(ω^i) = ω^σ(i)`, along
+/// with the polynomial interpolating it, which PLONK preprocesses and
+/// commits to once per circuit
+#[derive(Debug, Clone)]
+pub struct PrescribedPermutation<F> {
+    domain: Domain<F>,
+    sigma: Vec<usize>,
+    polynomial: Polynomial<F>,
+}
+
+impl<F: Field> PrescribedPermutation<F> {
+    pub fn new(domain: Domain<F>, sigma: Vec<usize>) -> Self {
+        assert_eq!(sigma.len(), domain.size(), "one image per domain element");
+        let mut seen = vec![false; sigma.len()];
+        for &image in &sigma {
+            assert!(
+                image < sigma.len() && !seen[image],
+                "σ is not a permutation"
+            );
+            seen[image] = true;
+        }
+        let elements = domain.elements();
+        let images: Vec<F> = sigma.iter().map(|&image| elements[image]).collect();
+        let polynomial = domain.interpolate(&images);
+        Self {
+            domain,
+            sigma,
+            polynomial,
+        }
+    }
+
+    pub fn sigma(&self) -> &[usize] {
+        &self.sigma
+    }
+
+    /// The polynomial `W` of degree less than `k`
+    pub fn polynomial(&self) -> &Polynomial<F> {
+        &self.polynomial
+    }
+}
+
+/// `v(x) + β · i(x) + γ` for polynomials `v` and `i`
+fn fold_pair<F: Field>(
+    beta: F,
+    gamma: F,
+    value: &Polynomial<F>,
+    index: &Polynomial<F>,
+) -> Polynomial<F> {
+    &(value + &(index * &Polynomial::new_from_coeffs(&[beta])))
+        + &Polynomial::new_from_coeffs(&[gamma])
+}
+
+/// The identity map `x`, which indexes the numerator of the grand product
+fn identity<F: Field>() -> Polynomial<F> {
+    Polynomial::new_from_coeffs(&[F::zero(), F::one()])
+}
+
+pub struct PrescribedPermutationProver<F> {
+    permutation: PrescribedPermutation<F>,
+    f: Polynomial<F>,
+    g: Polynomial<F>,
+}
+
+impl<F: Field> PrescribedPermutationProver<F> {
+    pub fn new(permutation: PrescribedPermutation<F>, f: Polynomial<F>, g: Polynomial<F>) -> Self {
+        Self { permutation, f, g }
+    }
+
+    /// Runs the rational product check of
+    /// `(f + β x + γ) / (g + β W + γ)` once the verifier has sent `β` and
+    /// `γ`. `NotAPermutation` means `g ≠ f ∘ W` on the domain.
+    pub fn prove(&self, beta: F, gamma: F) -> Result<ProductCheckProof<F>, PermutationCheckError> {
+        Ok(ProductCheckProver::new_rational(
+            self.permutation.domain,
+            fold_pair(beta, gamma, &self.f, &identity()),
+            fold_pair(beta, gamma, &self.g, self.permutation.polynomial()),
+        )
+        .prove()?)
+    }
+}
+
+pub struct PrescribedPermutationVerifier<F> {
+    permutation: PrescribedPermutation<F>,
+}
+
+impl<F: Field> PrescribedPermutationVerifier<F> {
+    pub fn new(permutation: PrescribedPermutation<F>) -> Self {
+        Self { permutation }
+    }
+
+    /// Samples `β` and `γ`, and later the evaluation point `r` of the
+    /// zero-test
+    pub fn sample_challenge<R: RngCore>(&self, rng: &mut R) -> F {
+        F::from_u64(rng.next_u64())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify<Of, Og, Ot, Oq>(
+        &self,
+        f: &Of,
+        g: &Og,
+        accumulator: &Ot,
+        quotient: &Oq,
+        beta: F,
+        gamma: F,
+        challenge: F,
+    ) -> bool
+    where
+        Of: PolynomialOracle<F>,
+        Og: PolynomialOracle<F>,
+        Ot: PolynomialOracle<F>,
+        Oq: PolynomialOracle<F>,
+    {
+        let identity = identity();
+        ProductCheckVerifier::new(self.permutation.domain).verify_rational(
+            &FoldedPairOracle {
+                beta,
+                gamma,
+                value: f,
+                index: &identity,
+            },
+            &FoldedPairOracle {
+                beta,
+                gamma,
+                value: g,
+                index: self.permutation.polynomial(),
+            },
+            accumulator,
+            quotient,
+            challenge,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::Fq;
+
+    fn domain() -> Domain<Fq> {
+        Domain::new(Fq::from(3), 8)
+    }
+
+    const VALUES: [u64; 8] = [4, 8, 15, 16, 23, 4, 29, 1];
+
+    #[test]
+    fn permuted_values_are_accepted() {
+        let domain = domain();
+        let f = domain.interpolate(&[16, 4, 1, 23, 8, 29, 15, 4].map(Fq::from));
+        let g = domain.interpolate(&VALUES.map(Fq::from));
+        let prover = PermutationCheckProver::new(domain, f.clone(), g.clone());
+        let verifier = PermutationCheckVerifier::new(domain);
+
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        for _ in 0..10 {
+            let gamma = verifier.sample_challenge(&mut rng);
+            let proof = match prover.prove(gamma) {
+                Ok(proof) => proof,
+                Err(error) => {
+                    assert_eq!(error, PermutationCheckError::DegenerateChallenge);
+                    continue;
+                }
+            };
+            let challenge = verifier.sample_challenge(&mut rng);
+            assert!(verifier.verify(
+                &f,
+                &g,
+                &proof.accumulator,
+                &proof.quotient,
+                gamma,
+                challenge
+            ));
+        }
+    }
+
+    #[test]
+    fn different_multisets_are_rejected() {
+        // Same set of values, but 4 appears once in `f` and twice in `g`
+        let domain = domain();
+        let f = domain.interpolate(&[16, 4, 1, 23, 8, 29, 15, 1].map(Fq::from));
+        let g = domain.interpolate(&VALUES.map(Fq::from));
+        let prover = PermutationCheckProver::new(domain, f, g);
+
+        // The two products agree as polynomials in `γ` only at the roots of
+        // their difference, of degree at most 7
+        let accepted = (0..41u64)
+            .filter(|gamma| prover.prove(Fq::from(*gamma)).is_ok())
+            .count();
+        assert!(accepted <= 7);
+        assert_eq!(
+            prover.prove(Fq::from(2)).unwrap_err(),
+            PermutationCheckError::NotAPermutation
+        );
+        assert_eq!(
+            prover.prove(Fq::from(23)).unwrap_err(),
+            PermutationCheckError::DegenerateChallenge
+        );
+    }
+
+    /// `W` swaps the cells in three 2-cycles and fixes the rest, as copy
+    /// constraints between pairs of wires would
+    fn copy_constraints() -> PrescribedPermutation<Fq> {
+        PrescribedPermutation::new(domain(), vec![5, 2, 1, 3, 7, 0, 6, 4])
+    }
+
+    /// `f` and `g = f ∘ W` on the domain
+    fn wires(permutation: &PrescribedPermutation<Fq>) -> (Vec<Fq>, Vec<Fq>) {
+        let f = [4, 8, 8, 16, 23, 4, 29, 23].map(Fq::from).to_vec();
+        let g = permutation.sigma().iter().map(|&image| f[image]).collect();
+        (f, g)
+    }
+
+    #[test]
+    fn permutation_polynomial_maps_domain() {
+        let permutation = copy_constraints();
+        let elements = domain().elements();
+        for (i, &image) in permutation.sigma().iter().enumerate() {
+            assert_eq!(permutation.polynomial().eval(elements[i]), elements[image]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "σ is not a permutation")]
+    fn rejects_non_permutation() {
+        PrescribedPermutation::new(domain(), vec![5, 2, 1, 3, 7, 0, 6, 5]);
+    }
+
+    #[test]
+    fn copy_constraints_are_accepted() {
+        let domain = domain();
+        let permutation = copy_constraints();
+        let (f, g) = wires(&permutation);
+        assert_eq!(f, g, "f is constant on every cycle of W");
+        let (f, g) = (domain.interpolate(&f), domain.interpolate(&g));
+
+        let prover = PrescribedPermutationProver::new(permutation.clone(), f.clone(), g.clone());
+        let verifier = PrescribedPermutationVerifier::new(permutation);
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        for _ in 0..10 {
+            let (beta, gamma) = (
+                verifier.sample_challenge(&mut rng),
+                verifier.sample_challenge(&mut rng),
+            );
+            let proof = match prover.prove(beta, gamma) {
+                Ok(proof) => proof,
+                Err(error) => {
+                    assert_eq!(error, PermutationCheckError::DegenerateChallenge);
+                    continue;
+                }
+            };
+            let challenge = verifier.sample_challenge(&mut rng);
+            assert!(verifier.verify(
+                &f,
+                &g,
+                &proof.accumulator,
+                &proof.quotient,
+                beta,
+                gamma,
+                challenge
+            ));
+        }
+    }
+
+    #[test]
+    fn swapped_cell_is_detected() {
+        let domain = domain();
+        let permutation = copy_constraints();
+        let (f, mut g) = wires(&permutation);
+        let honest_g = domain.interpolate(&g);
+        // Swap two cells of `g`: it is still a permutation of `f`, but no
+        // longer the prescribed one
+        g.swap(0, 1);
+        let (f, g) = (domain.interpolate(&f), domain.interpolate(&g));
+
+        // Replaying a proof made for the honest `g` fails the zero-test
+        let (beta, gamma, challenge) = (Fq::from(11), Fq::from(4), Fq::from(7));
+        let proof = PrescribedPermutationProver::new(permutation.clone(), f.clone(), honest_g)
+            .prove(beta, gamma)
+            .unwrap();
+        let verifier = PrescribedPermutationVerifier::new(permutation.clone());
+        assert!(!verifier.verify(
+            &f,
+            &g,
+            &proof.accumulator,
+            &proof.quotient,
+            beta,
+            gamma,
+            challenge
+        ));
+
+        assert!(PermutationCheckProver::new(domain, f.clone(), g.clone())
+            .prove(Fq::from(2))
+            .is_ok());
+
+        // Only the factors of the swapped cells differ between the two sides
+        // of the grand product. With `A = β W(1)` and `B = β W(ω)`, their
+        // ratio is `(4 + A + γ)(8 + B + γ) / ((8 + A + γ)(4 + B + γ))`, which
+        // is one iff `4 (A - B) = 0`. As `W(1) ≠ W(ω)`, every `β ≠ 0` and
+        // every `γ` catches the swap.
+        let prover = PrescribedPermutationProver::new(permutation, f, g);
+        for beta in 1..41u64 {
+            for gamma in 0..41u64 {
+                assert!(prover.prove(Fq::from(beta), Fq::from(gamma)).is_err());
+            }
+        }
+        assert_eq!(
+            prover.prove(Fq::from(3), Fq::from(5)).unwrap_err(),
+            PermutationCheckError::NotAPermutation
+        );
+    }
+}
@@ -0,0 +1,430 @@
+//! Plookup: the prover convinces the verifier that every value of a witness
+//! `f` on `ω^0, ..., ω^(k-2)` is contained in the values of a table `t` on
+//! the whole domain `H` of order `k`.
+//!
+//! Let `s` be the sorted merge of witness and table: the table values in
+//! order, with every witness value inserted next to its copy in the table.
+//! Writing `c = γ (1 + β)`, the witness is contained in the table and `s`
+//! is sorted by it iff the bivariate identity
+//!
+//! `(1 + β)^(k-1) Π_i (γ + f_i) Π_i (c + t_i + β t_(i+1)) = Π_i (c + s_i + β s_(i+1))`
+//!
+//! holds, since the pairs `(s_i, s_(i+1))` then consist of the pairs of
+//! consecutive table values and of pairs of equal values. `s` has `2k - 1`
+//! entries, so the prover splits it into `h1 = s[..k]` and
+//! `h2 = s[k-1..]`, which overlap in one entry. The identity then becomes
+//! a rational product check over `H` of
+//!
+//! `(1 + β)(γ + f(x))(c + t(x) + β t(ω x))` over
+//! `(c + h1(x) + β h1(ω x))(c + h2(x) + β h2(ω x))`,
+//!
+//! both masked to one at `x = ω^(k-1)`, where there is no next entry. On
+//! top, the verifier checks the overlap `h1(ω^(k-1)) = h2(1)`.
+
+use std::collections::HashMap;
+
+use finite_fields::Field;
+use polynomial::Polynomial;
+use rand::RngCore;
+
+use crate::{
+    domain::Domain,
+    oracle::PolynomialOracle,
+    productcheck::{
+        ProductCheckError, ProductCheckProof, ProductCheckProver, ProductCheckVerifier,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlookupError {
+    /// Some witness value does not appear in the table
+    NotInTable,
+    /// A factor of the denominator vanishes for these `β`, `γ`; the
+    /// verifier must resample
+    DegenerateChallenge,
+}
+
+impl From<ProductCheckError> for PlookupError {
+    fn from(error: ProductCheckError) -> Self {
+        match error {
+            ProductCheckError::WrongProduct => Self::NotInTable,
+            ProductCheckError::ZeroDenominator => Self::DegenerateChallenge,
+        }
+    }
+}
+
+fn constant<F: Field>(value: F) -> Polynomial<F> {
+    Polynomial::new_from_coeffs(&[value])
+}
+
+/// `c + p(x) + β p(ω x)`
+fn step<F: Field>(domain: &Domain<F>, beta: F, c: F, p: &Polynomial<F>) -> Polynomial<F> {
+    &(&constant(c) + p) + &(&domain.shifted(p) * &constant(beta))
+}
+
+/// `(1 - L(x)) p(x) + L(x)` for the Lagrange polynomial `L` of
+/// `ω^(k-1)`, which agrees with `p` on the domain except for one there
+fn mask<F: Field>(domain: &Domain<F>, p: &Polynomial<F>) -> Polynomial<F> {
+    let mut indicator = vec![F::zero(); domain.size()];
+    indicator[domain.size() - 1] = F::one();
+    let lagrange = domain.interpolate(&indicator);
+    &(&(&constant(F::one()) - &lagrange) * p) + &lagrange
+}
+
+/// The sorted merge of `witness` and `table`
+fn sorted_merge<F: Field>(witness: &[F], table: &[F]) -> Result<Vec<F>, PlookupError> {
+    let mut counts: HashMap<Vec<u64>, usize> = HashMap::new();
+    for value in witness {
+        *counts.entry(value.to_limbs()).or_default() += 1;
+    }
+    let mut sorted = Vec::with_capacity(witness.len() + table.len());
+    for value in table {
+        sorted.push(*value);
+        if let Some(count) = counts.remove(&value.to_limbs()) {
+            sorted.extend(std::iter::repeat(*value).take(count));
+        }
+    }
+    match counts.is_empty() {
+        true => Ok(sorted),
+        false => Err(PlookupError::NotInTable),
+    }
+}
+
+pub struct PlookupProver<F> {
+    domain: Domain<F>,
+    f: Polynomial<F>,
+    t: Polynomial<F>,
+}
+
+impl<F: Field> PlookupProver<F> {
+    pub fn new(domain: Domain<F>, f: Polynomial<F>, t: Polynomial<F>) -> Self {
+        Self { domain, f, t }
+    }
+
+    /// The prover's first message: `h1` and `h2` interpolating the two
+    /// halves of the sorted merge of witness and table
+    pub fn sorted(&self) -> Result<(Polynomial<F>, Polynomial<F>), PlookupError> {
+        let elements = self.domain.elements();
+        let k = self.domain.size();
+        let witness: Vec<F> = elements[..k - 1].iter().map(|a| self.f.eval(*a)).collect();
+        let table: Vec<F> = elements.iter().map(|a| self.t.eval(*a)).collect();
+        let sorted = sorted_merge(&witness, &table)?;
+        Ok((
+            self.domain.interpolate(&sorted[..k]),
+            self.domain.interpolate(&sorted[k - 1..]),
+        ))
+    }
+
+    /// Runs the rational product check once the verifier has sent `β` and
+    /// `γ`
+    pub fn prove(&self, beta: F, gamma: F) -> Result<ProductCheckProof<F>, PlookupError> {
+        let (h1, h2) = self.sorted()?;
+        self.prove_with_sorted(&h1, &h2, beta, gamma)
+    }
+
+    fn prove_with_sorted(
+        &self,
+        h1: &Polynomial<F>,
+        h2: &Polynomial<F>,
+        beta: F,
+        gamma: F,
+    ) -> Result<ProductCheckProof<F>, PlookupError> {
+        let one_plus_beta = F::one() + beta;
+        let c = gamma * one_plus_beta;
+        let numerator = &(&constant(one_plus_beta) * &(&self.f + &constant(gamma)))
+            * &step(&self.domain, beta, c, &self.t);
+        let denominator = &step(&self.domain, beta, c, h1) * &step(&self.domain, beta, c, h2);
+        Ok(ProductCheckProver::new_rational(
+            self.domain,
+            mask(&self.domain, &numerator),
+            mask(&self.domain, &denominator),
+        )
+        .prove()?)
+    }
+}
+
+/// `(1 - L(x)) v + L(x)` at `point`, with
+/// `L(x) = ω^(k-1) Z_H(x) / (k (x - ω^(k-1)))`
+fn masked<F: Field>(domain: &Domain<F>, point: F, value: F) -> F {
+    let last = domain.generator().pow([domain.size() as u64 - 1]);
+    let Some(inverse) = (F::from_u64(domain.size() as u64) * (point - last)).inverse() else {
+        return F::one();
+    };
+    let lagrange = last * domain.evaluate_vanishing_polynomial(point) * inverse;
+    (F::one() - lagrange) * value + lagrange
+}
+
+/// Oracle for the masked `(1 + β)(γ + f(x))(c + t(x) + β t(ω x))`
+struct NumeratorOracle<'a, F, Of, Ot> {
+    domain: &'a Domain<F>,
+    beta: F,
+    gamma: F,
+    f: &'a Of,
+    t: &'a Ot,
+}
+
+impl<F, Of, Ot> PolynomialOracle<F> for NumeratorOracle<'_, F, Of, Ot>
+where
+    F: Field,
+    Of: PolynomialOracle<F>,
+    Ot: PolynomialOracle<F>,
+{
+    fn query(&self, point: F) -> F {
+        let one_plus_beta = F::one() + self.beta;
+        let next = point * self.domain.generator();
+        let value = one_plus_beta
+            * (self.gamma + self.f.query(point))
+            * (self.gamma * one_plus_beta + self.t.query(point) + self.beta * self.t.query(next));
+        masked(self.domain, point, value)
+    }
+
+    fn degree_bound(&self) -> usize {
+        self.domain.size() - 1 + self.f.degree_bound() + self.t.degree_bound()
+    }
+}
+
+/// Oracle for the masked `(c + h1(x) + β h1(ω x))(c + h2(x) + β h2(ω x))`
+struct DenominatorOracle<'a, F, Oh1, Oh2> {
+    domain: &'a Domain<F>,
+    beta: F,
+    gamma: F,
+    h1: &'a Oh1,
+    h2: &'a Oh2,
+}
+
+impl<F, Oh1, Oh2> PolynomialOracle<F> for DenominatorOracle<'_, F, Oh1, Oh2>
+where
+    F: Field,
+    Oh1: PolynomialOracle<F>,
+    Oh2: PolynomialOracle<F>,
+{
+    fn query(&self, point: F) -> F {
+        let c = self.gamma * (F::one() + self.beta);
+        let next = point * self.domain.generator();
+        let value = (c + self.h1.query(point) + self.beta * self.h1.query(next))
+            * (c + self.h2.query(point) + self.beta * self.h2.query(next));
+        masked(self.domain, point, value)
+    }
+
+    fn degree_bound(&self) -> usize {
+        self.domain.size() - 1 + self.h1.degree_bound() + self.h2.degree_bound()
+    }
+}
+
+pub struct PlookupVerifier<F> {
+    domain: Domain<F>,
+}
+
+impl<F: Field> PlookupVerifier<F> {
+    pub fn new(domain: Domain<F>) -> Self {
+        Self { domain }
+    }
+
+    /// Samples `β` and `γ`, and later the evaluation point `r` of the
+    /// zero-test
+    pub fn sample_challenge<R: RngCore>(&self, rng: &mut R) -> F {
+        F::from_u64(rng.next_u64())
+    }
+
+    /// Checks the overlap of `h1` and `h2` and the rational product check
+    /// given oracles to the witness, the table, the sorted halves, the
+    /// accumulator and the zero-test quotient
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify<Of, Ot, Oh1, Oh2, Oz, Oq>(
+        &self,
+        f: &Of,
+        t: &Ot,
+        h1: &Oh1,
+        h2: &Oh2,
+        accumulator: &Oz,
+        quotient: &Oq,
+        beta: F,
+        gamma: F,
+        challenge: F,
+    ) -> bool
+    where
+        Of: PolynomialOracle<F>,
+        Ot: PolynomialOracle<F>,
+        Oh1: PolynomialOracle<F>,
+        Oh2: PolynomialOracle<F>,
+        Oz: PolynomialOracle<F>,
+        Oq: PolynomialOracle<F>,
+    {
+        let last = self.domain.generator().pow([self.domain.size() as u64 - 1]);
+        let numerator = NumeratorOracle {
+            domain: &self.domain,
+            beta,
+            gamma,
+            f,
+            t,
+        };
+        let denominator = DenominatorOracle {
+            domain: &self.domain,
+            beta,
+            gamma,
+            h1,
+            h2,
+        };
+        h1.query(last) == h2.query(F::one())
+            && ProductCheckVerifier::new(self.domain).verify_rational(
+                &numerator,
+                &denominator,
+                accumulator,
+                quotient,
+                challenge,
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::Fq;
+
+    fn domain() -> Domain<Fq> {
+        Domain::new(Fq::from(3), 8)
+    }
+
+    const TABLE: [u64; 8] = [1, 2, 3, 5, 8, 13, 21, 34];
+
+    /// Witness values on the first seven elements of the domain, the last
+    /// value is ignored
+    fn witness(values: [u64; 7]) -> Polynomial<Fq> {
+        let mut evals = values.map(Fq::from).to_vec();
+        evals.push(Fq::from(40));
+        domain().interpolate(&evals)
+    }
+
+    #[test]
+    fn sorted_merge_follows_the_table() {
+        let merged = sorted_merge(&[5, 1, 5, 34].map(Fq::from), &[1, 3, 5, 34].map(Fq::from));
+        assert_eq!(
+            merged,
+            Ok([1, 1, 3, 5, 5, 5, 34, 34].map(Fq::from).to_vec())
+        );
+        assert_eq!(
+            sorted_merge(&[4].map(Fq::from), &[1, 3, 5].map(Fq::from)),
+            Err(PlookupError::NotInTable)
+        );
+    }
+
+    #[test]
+    fn contained_witness_is_accepted() {
+        let domain = domain();
+        let f = witness([8, 1, 34, 8, 8, 2, 21]);
+        let t = domain.interpolate(&TABLE.map(Fq::from));
+        let prover = PlookupProver::new(domain, f.clone(), t.clone());
+        let (h1, h2) = prover.sorted().unwrap();
+        let verifier = PlookupVerifier::new(domain);
+
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        for _ in 0..10 {
+            let (beta, gamma) = (
+                verifier.sample_challenge(&mut rng),
+                verifier.sample_challenge(&mut rng),
+            );
+            let proof = match prover.prove(beta, gamma) {
+                Ok(proof) => proof,
+                Err(error) => {
+                    assert_eq!(error, PlookupError::DegenerateChallenge);
+                    continue;
+                }
+            };
+            let challenge = verifier.sample_challenge(&mut rng);
+            assert!(verifier.verify(
+                &f,
+                &t,
+                &h1,
+                &h2,
+                &proof.accumulator,
+                &proof.quotient,
+                beta,
+                gamma,
+                challenge
+            ));
+        }
+    }
+
+    #[test]
+    fn missing_value_is_rejected() {
+        let domain = domain();
+        // 4 is not in the table
+        let f = witness([8, 1, 34, 4, 8, 2, 21]);
+        let t = domain.interpolate(&TABLE.map(Fq::from));
+        let prover = PlookupProver::new(domain, f.clone(), t.clone());
+        assert_eq!(prover.sorted().unwrap_err(), PlookupError::NotInTable);
+
+        // A cheater sorts 4 in between 3 and 5 anyway. The grand product
+        // then differs as a polynomial in `β` and `γ`, and is one for few
+        // challenges only.
+        let sorted = [1, 1, 2, 2, 3, 4, 5, 8, 8, 8, 13, 21, 21, 34, 34].map(Fq::from);
+        let (h1, h2) = (
+            domain.interpolate(&sorted[..8]),
+            domain.interpolate(&sorted[7..]),
+        );
+        let mut accepted = 0;
+        for beta in 0..41u64 {
+            for gamma in 0..41u64 {
+                let (beta, gamma) = (Fq::from(beta), Fq::from(gamma));
+                if prover.prove_with_sorted(&h1, &h2, beta, gamma).is_ok() {
+                    accepted += 1;
+                }
+            }
+        }
+        assert!(accepted < 41 * 41 / 10, "{accepted} challenges accepted");
+
+        // Replaying a proof for a contained witness fails the zero-test. Its
+        // constraint has degree up to 28, so over F_41 a fair share of the
+        // evaluation points are roots, but most still catch the cheater
+        let honest = witness([8, 1, 34, 3, 8, 2, 21]);
+        let honest_prover = PlookupProver::new(domain, honest, t.clone());
+        let (h1, h2) = honest_prover.sorted().unwrap();
+        let (beta, gamma) = (Fq::from(6), Fq::from(9));
+        let proof = honest_prover.prove(beta, gamma).unwrap();
+        let verifier = PlookupVerifier::new(domain);
+        let fooled = (0..41u64)
+            .filter(|r| {
+                verifier.verify(
+                    &f,
+                    &t,
+                    &h1,
+                    &h2,
+                    &proof.accumulator,
+                    &proof.quotient,
+                    beta,
+                    gamma,
+                    Fq::from(*r),
+                )
+            })
+            .count();
+        assert!(fooled < 41 / 2, "{fooled} challenges fooled the verifier");
+    }
+
+    #[test]
+    fn overlap_is_checked() {
+        let domain = domain();
+        let f = witness([1, 1, 1, 1, 1, 1, 1]);
+        let t = domain.interpolate(&TABLE.map(Fq::from));
+        let prover = PlookupProver::new(domain, f.clone(), t.clone());
+        let (h1, h2) = prover.sorted().unwrap();
+        let (beta, gamma) = (Fq::from(2), Fq::from(3));
+        let proof = prover.prove(beta, gamma).unwrap();
+
+        let shifted_h2 = &h2 + &constant(Fq::from(1));
+        let verifier = PlookupVerifier::new(domain);
+        assert!(!verifier.verify(
+            &f,
+            &t,
+            &h1,
+            &shifted_h2,
+            &proof.accumulator,
+            &proof.quotient,
+            beta,
+            gamma,
+            Fq::from(5)
+        ));
+    }
+}
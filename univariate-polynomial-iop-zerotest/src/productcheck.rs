@@ -0,0 +1,292 @@
+//! Product check IOP: the prover convinces the verifier that
+//! `Π_{a ∈ H} f(a) = 1` over a multiplicative subgroup `H` of order `k`,
+//! and its rational variant `Π_{a ∈ H} f(a) / g(a) = 1`.
+//!
+//! The prover interpolates the accumulator `t` of degree less than `k`
+//! holding the running products `t(ω^i) = Π_{j <= i} f(ω^j) / g(ω^j)`. The
+//! product is one iff `t(ω^(k-1)) = 1` and, for every `x` in `H`,
+//!
+//! `t(ω · x) · g(ω · x) = t(x) · f(ω · x)`
+//!
+//! which wraps around from `ω^(k-1)` to `1` since `t(1) = f(1) / g(1)`. The
+//! latter is a zero-test on `t(ω x) g(ω x) - t(x) f(ω x)`, so the verifier
+//! queries `t` at `ω^(k-1)`, `r` and `ω r`, `f` and `g` at `ω r`, and the
+//! zero-test quotient at `r`.
+
+use finite_fields::Field;
+use polynomial::Polynomial;
+use rand::RngCore;
+
+use crate::{
+    domain::Domain,
+    oracle::PolynomialOracle,
+    zerotest::{ZeroTestProver, ZeroTestVerifier},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProductCheckError {
+    /// The product over the domain is not one
+    WrongProduct,
+    /// The denominator vanishes somewhere on the domain
+    ZeroDenominator,
+}
+
+/// The prover's message: the accumulator `t` and the quotient of the
+/// zero-test on the accumulator constraint
+#[derive(Debug, Clone)]
+pub struct ProductCheckProof<F> {
+    pub accumulator: Polynomial<F>,
+    pub quotient: Polynomial<F>,
+}
+
+pub struct ProductCheckProver<F> {
+    domain: Domain<F>,
+    f: Polynomial<F>,
+    g: Polynomial<F>,
+}
+
+impl<F: Field> ProductCheckProver<F> {
+    /// Prover for `Π_{a ∈ H} f(a) = 1`
+    pub fn new(domain: Domain<F>, f: Polynomial<F>) -> Self {
+        Self::new_rational(domain, f, Polynomial::new_from_coeffs(&[F::one()]))
+    }
+
+    /// Prover for `Π_{a ∈ H} f(a) / g(a) = 1`
+    pub fn new_rational(domain: Domain<F>, f: Polynomial<F>, g: Polynomial<F>) -> Self {
+        Self { domain, f, g }
+    }
+
+    /// Running products `Π_{j <= i} f(ω^j) / g(ω^j)` for every `i`
+    fn running_products(&self) -> Result<Vec<F>, ProductCheckError> {
+        let mut product = F::one();
+        self.domain
+            .elements()
+            .into_iter()
+            .map(|a| {
+                let denominator = self
+                    .g
+                    .eval(a)
+                    .inverse()
+                    .ok_or(ProductCheckError::ZeroDenominator)?;
+                product = product * self.f.eval(a) * denominator;
+                Ok(product)
+            })
+            .collect()
+    }
+
+    pub fn prove(&self) -> Result<ProductCheckProof<F>, ProductCheckError> {
+        let products = self.running_products()?;
+        if products.last() != Some(&F::one()) {
+            return Err(ProductCheckError::WrongProduct);
+        }
+
+        let accumulator = self.domain.interpolate(&products);
+        let constraint = &(&self.domain.shifted(&accumulator) * &self.domain.shifted(&self.g))
+            - &(&accumulator * &self.domain.shifted(&self.f));
+        let quotient = ZeroTestProver::new(self.domain, constraint)
+            .quotient()
+            .expect("accumulator satisfies its constraint on the domain");
+        Ok(ProductCheckProof {
+            accumulator,
+            quotient,
+        })
+    }
+}
+
+/// Oracle for `t(ω x) g(ω x) - t(x) f(ω x)`, answered from the oracles to
+/// `f`, `g` and `t`
+struct AccumulatorConstraint<'a, F, Of, Og, Ot> {
+    domain: &'a Domain<F>,
+    f: &'a Of,
+    g: &'a Og,
+    accumulator: &'a Ot,
+}
+
+impl<F, Of, Og, Ot> PolynomialOracle<F> for AccumulatorConstraint<'_, F, Of, Og, Ot>
+where
+    F: Field,
+    Of: PolynomialOracle<F>,
+    Og: PolynomialOracle<F>,
+    Ot: PolynomialOracle<F>,
+{
+    fn query(&self, point: F) -> F {
+        let next = point * self.domain.generator();
+        self.accumulator.query(next) * self.g.query(next)
+            - self.accumulator.query(point) * self.f.query(next)
+    }
+
+    fn degree_bound(&self) -> usize {
+        self.accumulator.degree_bound() + self.f.degree_bound().max(self.g.degree_bound())
+    }
+}
+
+pub struct ProductCheckVerifier<F> {
+    domain: Domain<F>,
+}
+
+impl<F: Field> ProductCheckVerifier<F> {
+    pub fn new(domain: Domain<F>) -> Self {
+        Self { domain }
+    }
+
+    /// Samples the random evaluation point `r` of the zero-test
+    pub fn sample_challenge<R: RngCore>(&self, rng: &mut R) -> F {
+        F::from_u64(rng.next_u64())
+    }
+
+    /// Checks `Π_{a ∈ H} f(a) = 1` given oracles to `f`, the accumulator
+    /// and the zero-test quotient
+    pub fn verify<Of, Ot, Oq>(&self, f: &Of, accumulator: &Ot, quotient: &Oq, challenge: F) -> bool
+    where
+        Of: PolynomialOracle<F>,
+        Ot: PolynomialOracle<F>,
+        Oq: PolynomialOracle<F>,
+    {
+        let one = Polynomial::new_from_coeffs(&[F::one()]);
+        self.verify_rational(f, &one, accumulator, quotient, challenge)
+    }
+
+    /// Checks `Π_{a ∈ H} f(a) / g(a) = 1` given oracles to `f`, `g`, the
+    /// accumulator and the zero-test quotient
+    pub fn verify_rational<Of, Og, Ot, Oq>(
+        &self,
+        f: &Of,
+        g: &Og,
+        accumulator: &Ot,
+        quotient: &Oq,
+        challenge: F,
+    ) -> bool
+    where
+        Of: PolynomialOracle<F>,
+        Og: PolynomialOracle<F>,
+        Ot: PolynomialOracle<F>,
+        Oq: PolynomialOracle<F>,
+    {
+        let last = self.domain.generator().pow([self.domain.size() as u64 - 1]);
+        let constraint = AccumulatorConstraint {
+            domain: &self.domain,
+            f,
+            g,
+            accumulator,
+        };
+        accumulator.degree_bound() < self.domain.size()
+            && accumulator.query(last) == F::one()
+            && ZeroTestVerifier::new(self.domain).verify(&constraint, quotient, challenge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::{oracle::CountingOracle, Fq};
+
+    fn domain() -> Domain<Fq> {
+        Domain::new(Fq::from(3), 8)
+    }
+
+    /// Values on `H` multiplying to one: 2 · 21 = 5 · 33 = 7 · 6 = 1 mod 41
+    fn unit_product_values() -> [Fq; 8] {
+        [2, 21, 5, 33, 1, 7, 6, 1].map(Fq::from)
+    }
+
+    #[test]
+    fn honest_product_is_accepted() {
+        let domain = domain();
+        // Lift above degree k so that the zero-test quotient is non-trivial
+        let f = &domain.interpolate(&unit_product_values())
+            + &(&domain.vanishing_polynomial()
+                * &Polynomial::new_from_coeffs(&[3, 1].map(Fq::from)));
+        let proof = ProductCheckProver::new(domain, f.clone()).prove().unwrap();
+        let verifier = ProductCheckVerifier::new(domain);
+
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        for _ in 0..10 {
+            let f_oracle = CountingOracle::new(f.clone());
+            let t_oracle = CountingOracle::new(proof.accumulator.clone());
+            let q_oracle = CountingOracle::new(proof.quotient.clone());
+            let challenge = verifier.sample_challenge(&mut rng);
+            assert!(verifier.verify(&f_oracle, &t_oracle, &q_oracle, challenge));
+            assert_eq!(
+                (f_oracle.queries(), t_oracle.queries(), q_oracle.queries()),
+                (1, 3, 1)
+            );
+        }
+    }
+
+    #[test]
+    fn wrong_product_is_rejected() {
+        let domain = domain();
+        let mut values = unit_product_values();
+        values[4] = Fq::from(2);
+        let f = domain.interpolate(&values);
+        assert_eq!(
+            ProductCheckProver::new(domain, f.clone())
+                .prove()
+                .unwrap_err(),
+            ProductCheckError::WrongProduct
+        );
+
+        // The cheater ends the accumulator with a one. The constraint then
+        // fails at `x = ω^6`, and the zero-test catches it for all but the
+        // few challenges where the cheating quotient happens to agree.
+        let mut products = vec![];
+        let mut product = Fq::from(1);
+        for value in values {
+            product *= value;
+            products.push(product);
+        }
+        products[7] = Fq::from(1);
+        let accumulator = domain.interpolate(&products);
+        let constraint = &domain.shifted(&accumulator) - &(&accumulator * &domain.shifted(&f));
+        let quotient = constraint.div_rem(&domain.vanishing_polynomial()).0;
+
+        let verifier = ProductCheckVerifier::new(domain);
+        let fooled = (0..41u64)
+            .filter(|r| verifier.verify(&f, &accumulator, &quotient, Fq::from(*r)))
+            .count();
+        assert!(fooled <= 7);
+    }
+
+    #[test]
+    fn rational_product_check() {
+        let domain = domain();
+        let denominators = [3, 4, 9, 10, 11, 12, 17, 40].map(Fq::from);
+        let numerators: Vec<Fq> = denominators
+            .iter()
+            .zip(unit_product_values())
+            .map(|(d, u)| *d * u)
+            .collect();
+        let f = domain.interpolate(&numerators);
+        let g = domain.interpolate(&denominators);
+
+        let proof = ProductCheckProver::new_rational(domain, f.clone(), g.clone())
+            .prove()
+            .unwrap();
+        let verifier = ProductCheckVerifier::new(domain);
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let challenge = verifier.sample_challenge(&mut rng);
+        assert!(verifier.verify_rational(&f, &g, &proof.accumulator, &proof.quotient, challenge));
+
+        // The proof does not transfer to `2f / g`
+        let doubled = &f * &Polynomial::new_from_coeffs(&[Fq::from(2)]);
+        assert!(!verifier.verify_rational(
+            &doubled,
+            &g,
+            &proof.accumulator,
+            &proof.quotient,
+            challenge
+        ));
+
+        let mut with_zero = denominators;
+        with_zero[2] = Fq::from(0);
+        assert_eq!(
+            ProductCheckProver::new_rational(domain, f, domain.interpolate(&with_zero))
+                .prove()
+                .unwrap_err(),
+            ProductCheckError::ZeroDenominator
+        );
+    }
+}
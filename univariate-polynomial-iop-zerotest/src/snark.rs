@@ -0,0 +1,296 @@
+//! The zero-test compiled into a non-interactive argument.
+//!
+//! The two compilation steps are:
+//!
+//! - every polynomial oracle becomes a commitment of a
+//!   [`PolynomialCommitmentScheme`] such as KZG, and every oracle query an
+//!   evaluation proof, and
+//! - the verifier's random point `r` becomes a hash of the statement and
+//!   the prover's commitments (Fiat–Shamir).
+//!
+//! The statement is a commitment to `f`, and the proof consists of the
+//! commitment to the quotient `q` with openings of `f` and `q` at `r`. The
+//! degree bound on `q` of the IOP is only enforced up to the maximum degree
+//! the commitment key supports.
+
+use elliptic_curves::{
+    encoding::{field_from_bytes, field_to_bytes, write_prefixed, PrefixedReader},
+    Encoding,
+};
+use fiat_shamir::Transcript;
+use finite_fields::Field;
+use polynomial::Polynomial;
+use polynomial_commitments::PolynomialCommitmentScheme;
+
+use crate::{
+    domain::Domain,
+    zerotest::{ZeroTestError, ZeroTestProver},
+};
+
+/// Non-interactive proof that the polynomial behind a commitment vanishes
+/// on a domain
+pub struct ZeroTestProof<F: Field, P: PolynomialCommitmentScheme<F>> {
+    pub quotient_commitment: P::Commitment,
+    pub f_value: F,
+    pub quotient_value: F,
+    pub f_opening: P::Proof,
+    pub quotient_opening: P::Proof,
+}
+
+impl<F: Field, P: PolynomialCommitmentScheme<F>> Clone for ZeroTestProof<F, P> {
+    fn clone(&self) -> Self {
+        Self {
+            quotient_commitment: self.quotient_commitment.clone(),
+            f_value: self.f_value,
+            quotient_value: self.quotient_value,
+            f_opening: self.f_opening.clone(),
+            quotient_opening: self.quotient_opening.clone(),
+        }
+    }
+}
+
+impl<F, P> ZeroTestProof<F, P>
+where
+    F: Field,
+    P: PolynomialCommitmentScheme<F>,
+    P::Commitment: Encoding,
+    P::Proof: Encoding,
+{
+    /// Every component, each prefixed with its length as a little-endian
+    /// `u32`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let parts = [
+            self.quotient_commitment.to_bytes(),
+            field_to_bytes(&self.f_value),
+            field_to_bytes(&self.quotient_value),
+            self.f_opening.to_bytes(),
+            self.quotient_opening.to_bytes(),
+        ];
+        let mut bytes = vec![];
+        for part in parts {
+            write_prefixed(&mut bytes, &part);
+        }
+        bytes
+    }
+
+    /// Inverse of `to_bytes`, `None` for malformed input
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = PrefixedReader::new(bytes);
+        let proof = Self {
+            quotient_commitment: P::Commitment::from_bytes(reader.next_part()?)?,
+            f_value: field_from_bytes(reader.next_part()?)?,
+            quotient_value: field_from_bytes(reader.next_part()?)?,
+            f_opening: P::Proof::from_bytes(reader.next_part()?)?,
+            quotient_opening: P::Proof::from_bytes(reader.next_part()?)?,
+        };
+        reader.is_empty().then_some(proof)
+    }
+}
+
+/// The point `r`, bound to the domain, the statement and the commitment to
+/// the quotient
+fn challenge<F: Field, C: Encoding>(
+    domain: &Domain<F>,
+    f_commitment: &C,
+    quotient_commitment: &C,
+) -> F {
+    let mut transcript = Transcript::new(b"zero-test");
+    transcript.append_field(b"generator", &domain.generator());
+    transcript.append_message(b"size", &(domain.size() as u64).to_le_bytes());
+    transcript.append_message(b"f", &f_commitment.to_bytes());
+    transcript.append_message(b"quotient", &quotient_commitment.to_bytes());
+    transcript.challenge(b"r")
+}
+
+/// Proves that `f`, committed to as `P::commit(ck, f)`, vanishes on the
+/// domain
+pub fn prove<F, P>(
+    ck: &P::CommitterKey,
+    domain: &Domain<F>,
+    f: &Polynomial<F>,
+) -> Result<ZeroTestProof<F, P>, ZeroTestError>
+where
+    F: Field,
+    P: PolynomialCommitmentScheme<F>,
+    P::Commitment: Encoding,
+{
+    let quotient = ZeroTestProver::new(*domain, f.clone()).quotient()?;
+    Ok(prove_with_quotient::<F, P>(ck, domain, f, &quotient))
+}
+
+fn prove_with_quotient<F, P>(
+    ck: &P::CommitterKey,
+    domain: &Domain<F>,
+    f: &Polynomial<F>,
+    quotient: &Polynomial<F>,
+) -> ZeroTestProof<F, P>
+where
+    F: Field,
+    P: PolynomialCommitmentScheme<F>,
+    P::Commitment: Encoding,
+{
+    let f_commitment = P::commit(ck, f);
+    let quotient_commitment = P::commit(ck, quotient);
+    let r = challenge(domain, &f_commitment, &quotient_commitment);
+    let (f_value, f_opening) = P::open(ck, f, r);
+    let (quotient_value, quotient_opening) = P::open(ck, quotient, r);
+    ZeroTestProof {
+        quotient_commitment,
+        f_value,
+        quotient_value,
+        f_opening,
+        quotient_opening,
+    }
+}
+
+/// Checks both openings at the recomputed `r` and `f(r) = q(r) · Z_H(r)`
+pub fn verify<F, P>(
+    vk: &P::VerifierKey,
+    domain: &Domain<F>,
+    f_commitment: &P::Commitment,
+    proof: &ZeroTestProof<F, P>,
+) -> bool
+where
+    F: Field,
+    P: PolynomialCommitmentScheme<F>,
+    P::Commitment: Encoding,
+{
+    let r = challenge(domain, f_commitment, &proof.quotient_commitment);
+    proof.f_value == proof.quotient_value * domain.evaluate_vanishing_polynomial(r)
+        && P::verify(vk, f_commitment, r, proof.f_value, &proof.f_opening)
+        && P::verify(
+            vk,
+            &proof.quotient_commitment,
+            r,
+            proof.quotient_value,
+            &proof.quotient_opening,
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::{Bls12_381, Fr};
+    use ark_ff::FftField;
+    use elliptic_curves::toy::ToyTatePairing;
+    use finite_fields::PrimeField;
+    use polynomial_commitments::kzg::Kzg;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+
+    type Bls = Kzg<Bls12_381>;
+
+    fn domain() -> Domain<Fr> {
+        Domain::new(Fr::get_root_of_unity(8).unwrap(), 8)
+    }
+
+    fn poly(coeffs: &[u64]) -> Polynomial<Fr> {
+        Polynomial::new_from_coeffs(&coeffs.iter().map(|c| Fr::from(*c)).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn honest_proof_verifies_after_serialisation() {
+        let domain = domain();
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        let (ck, vk) = Bls::setup(10, &mut rng);
+        let f = &domain.vanishing_polynomial() * &poly(&[7, 5, 1]);
+        let f_commitment = Bls::commit(&ck, &f);
+
+        let proof = prove::<_, Bls>(&ck, &domain, &f).unwrap();
+        assert!(verify(&vk, &domain, &f_commitment, &proof));
+
+        let bytes = proof.to_bytes();
+        let decoded = ZeroTestProof::<Fr, Bls>::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.to_bytes(), bytes);
+        assert!(verify(&vk, &domain, &f_commitment, &decoded));
+
+        assert!(ZeroTestProof::<Fr, Bls>::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(ZeroTestProof::<Fr, Bls>::from_bytes(&trailing).is_none());
+    }
+
+    #[test]
+    fn tampered_proofs_are_rejected() {
+        let domain = domain();
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let (ck, vk) = Bls::setup(10, &mut rng);
+        let f = &domain.vanishing_polynomial() * &poly(&[1, 1]);
+        let f_commitment = Bls::commit(&ck, &f);
+        let proof = prove::<_, Bls>(&ck, &domain, &f).unwrap();
+
+        // Values consistent with the identity but not with the openings
+        let mut tampered = proof.clone();
+        tampered.f_value = Fr::from(0u64);
+        tampered.quotient_value = Fr::from(0u64);
+        assert!(!verify(&vk, &domain, &f_commitment, &tampered));
+
+        // The proof is bound to the statement
+        let other = Bls::commit(&ck, &(&domain.vanishing_polynomial() * &poly(&[2, 1])));
+        assert!(!verify(&vk, &domain, &other, &proof));
+    }
+
+    #[test]
+    fn non_vanishing_polynomial_is_rejected() {
+        let domain = domain();
+        let mut rng = ChaCha20Rng::seed_from_u64(2);
+        let (ck, vk) = Bls::setup(10, &mut rng);
+        let f = &(&domain.vanishing_polynomial() * &poly(&[7, 5, 1])) + &poly(&[2, 0, 0, 1]);
+        assert_eq!(
+            prove::<_, Bls>(&ck, &domain, &f).err(),
+            Some(ZeroTestError::NotZeroOnDomain)
+        );
+
+        // Dropping the remainder gives a proof that fails the identity at `r`
+        let quotient = f.div_rem(&domain.vanishing_polynomial()).0;
+        let proof = prove_with_quotient::<_, Bls>(&ck, &domain, &f, &quotient);
+        assert!(!verify(&vk, &domain, &Bls::commit(&ck, &f), &proof));
+    }
+
+    #[test]
+    fn toy_pairing() {
+        // 2 has order 8 modulo 17
+        type F17 = PrimeField<17>;
+        type Toy = Kzg<ToyTatePairing>;
+        let domain = Domain::new(F17::from(2u64), 8);
+        let mut rng = ChaCha20Rng::seed_from_u64(3);
+        let (ck, vk) = Toy::setup(10, &mut rng);
+        let f = &domain.vanishing_polynomial()
+            * &Polynomial::new_from_coeffs(&[3u64, 1].map(F17::from));
+
+        let proof = prove::<_, Toy>(&ck, &domain, &f).unwrap();
+        let decoded = ZeroTestProof::<F17, Toy>::from_bytes(&proof.to_bytes()).unwrap();
+        assert!(verify(&vk, &domain, &Toy::commit(&ck, &f), &decoded));
+    }
+
+    #[test]
+    fn transparent_ipa_over_pallas() {
+        use finite_fields::Halo2Field;
+        use halo2_proofs::{
+            arithmetic::Field as _, pasta::group::ff::PrimeField as _, pasta::pallas,
+        };
+        use polynomial_commitments::ipa::Ipa;
+
+        type F = Halo2Field<pallas::Scalar>;
+        type Pallas = Ipa<pallas::Point>;
+        // The 2^32-th root of unity raised to 2^29 has order 8
+        let omega = pallas::Scalar::ROOT_OF_UNITY.pow_vartime([1 << 29]);
+        let domain = Domain::new(Halo2Field(omega), 8);
+        let mut rng = ChaCha20Rng::seed_from_u64(4);
+        let (ck, vk) = Pallas::setup(10, &mut rng);
+        let f = &domain.vanishing_polynomial()
+            * &Polynomial::new_from_coeffs(&[6u64, 0, 1].map(F::from));
+        let f_commitment = Pallas::commit(&ck, &f);
+
+        let proof = prove::<_, Pallas>(&ck, &domain, &f).unwrap();
+        let decoded = ZeroTestProof::<F, Pallas>::from_bytes(&proof.to_bytes()).unwrap();
+        assert!(verify(&vk, &domain, &f_commitment, &decoded));
+
+        let other = Pallas::commit(
+            &ck,
+            &(&domain.vanishing_polynomial() * &Polynomial::new_from_coeffs(&[7u64].map(F::from))),
+        );
+        assert!(!verify(&vk, &domain, &other, &proof));
+    }
+}
@@ -0,0 +1,261 @@
+//! Univariate sumcheck IOP: the prover convinces the verifier that
+//! `Σ_{a ∈ H} f(a) = s` over a multiplicative subgroup `H` of order `k`.
+//!
+//! Dividing by the vanishing polynomial gives `f = q · Z_H + p` with
+//! `deg p < k`. Summing over `H` kills `q · Z_H`, and for `0 < i < k` the
+//! powers `a^i` of the elements of `H` sum to zero, so only the constant
+//! term survives: `Σ_{a ∈ H} f(a) = k · p(0)`. Hence the sum is `s` iff
+//!
+//! `f = q · Z_H + x · g + s / k`
+//!
+//! for some `g` of degree at most `k - 2`. The prover sends `q` and `g`,
+//! and the verifier checks the degree bounds and the identity at a random
+//! point `r`, exactly as in the zero-test.
+
+use finite_fields::Field;
+use polynomial::Polynomial;
+use rand::RngCore;
+
+use fiat_shamir::Transcript;
+
+use crate::{domain::Domain, oracle::PolynomialOracle};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SumcheckError {
+    /// `f` does not sum to the claimed value over the domain
+    WrongSum,
+}
+
+/// The prover's message: `q` and `g` with `f = q · Z_H + x · g + s / k`
+#[derive(Debug, Clone)]
+pub struct SumcheckProof<F> {
+    pub quotient: Polynomial<F>,
+    pub g: Polynomial<F>,
+}
+
+pub struct SumcheckProver<F> {
+    domain: Domain<F>,
+    f: Polynomial<F>,
+}
+
+impl<F: Field> SumcheckProver<F> {
+    pub fn new(domain: Domain<F>, f: Polynomial<F>) -> Self {
+        Self { domain, f }
+    }
+
+    /// `Σ_{a ∈ H} f(a)`, computed by evaluating `f` on the whole domain
+    pub fn sum(&self) -> F {
+        self.domain
+            .elements()
+            .into_iter()
+            .fold(F::zero(), |acc, a| acc + self.f.eval(a))
+    }
+
+    /// Splits `f` into `q` and `g`, failing if `claimed_sum` is not the sum
+    /// of `f` over the domain
+    pub fn prove(&self, claimed_sum: F) -> Result<SumcheckProof<F>, SumcheckError> {
+        let (quotient, remainder) = self.f.div_rem(&self.domain.vanishing_polynomial());
+        let mut coeffs = remainder.get_raw_coefficients();
+        coeffs.resize(self.domain.size(), F::zero());
+        if F::from_u64(self.domain.size() as u64) * coeffs[0] != claimed_sum {
+            return Err(SumcheckError::WrongSum);
+        }
+        // g = (p - p(0)) / x
+        let g = Polynomial::new_from_coeffs(&coeffs[1..]);
+        Ok(SumcheckProof { quotient, g })
+    }
+}
+
+pub struct SumcheckVerifier<F> {
+    domain: Domain<F>,
+}
+
+impl<F: Field> SumcheckVerifier<F> {
+    pub fn new(domain: Domain<F>) -> Self {
+        Self { domain }
+    }
+
+    /// Samples the random evaluation point `r`
+    pub fn sample_challenge<R: RngCore>(&self, rng: &mut R) -> F {
+        F::from_u64(rng.next_u64())
+    }
+
+    /// Accepts iff `deg q <= deg f - k`, `deg g <= k - 2` and
+    /// `f(r) = q(r) · Z_H(r) + r · g(r) + s / k`
+    pub fn verify<Of, Oq, Og>(
+        &self,
+        f: &Of,
+        quotient: &Oq,
+        g: &Og,
+        claimed_sum: F,
+        challenge: F,
+    ) -> bool
+    where
+        Of: PolynomialOracle<F>,
+        Oq: PolynomialOracle<F>,
+        Og: PolynomialOracle<F>,
+    {
+        let size = self.domain.size();
+        let size_inverse = F::from_u64(size as u64)
+            .inverse()
+            .expect("domain size is invertible in a field with a subgroup of that order");
+        quotient.degree_bound() <= f.degree_bound().saturating_sub(size)
+            && g.degree_bound() <= size.saturating_sub(2)
+            && f.query(challenge)
+                == quotient.query(challenge) * self.domain.evaluate_vanishing_polynomial(challenge)
+                    + challenge * g.query(challenge)
+                    + claimed_sum * size_inverse
+    }
+
+    /// Non-interactive variant: the challenge is derived by hashing `f`, the
+    /// claimed sum and the proof instead of being sampled by the verifier
+    pub fn verify_non_interactive(
+        &self,
+        f: &Polynomial<F>,
+        claimed_sum: F,
+        proof: &SumcheckProof<F>,
+    ) -> bool {
+        let challenge = fiat_shamir_challenge(f, claimed_sum, proof);
+        self.verify(f, &proof.quotient, &proof.g, claimed_sum, challenge)
+    }
+}
+
+/// Challenge `r` bound to the statement and to everything the prover sent
+pub fn fiat_shamir_challenge<F: Field>(
+    f: &Polynomial<F>,
+    claimed_sum: F,
+    proof: &SumcheckProof<F>,
+) -> F {
+    let mut transcript = Transcript::new(b"univariate-sumcheck");
+    transcript.append_polynomial(b"f", f);
+    transcript.append_field(b"sum", &claimed_sum);
+    transcript.append_polynomial(b"quotient", &proof.quotient);
+    transcript.append_polynomial(b"g", &proof.g);
+    transcript.challenge(b"r")
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::{oracle::CountingOracle, Fq};
+
+    fn domain() -> Domain<Fq> {
+        Domain::new(Fq::from(3), 8)
+    }
+
+    fn poly(coeffs: &[u64]) -> Polynomial<Fq> {
+        Polynomial::new_from_coeffs(&coeffs.iter().map(|c| Fq::from(*c)).collect::<Vec<_>>())
+    }
+
+    /// Degree 11, so the quotient by `Z_H` is non-trivial
+    fn f() -> Polynomial<Fq> {
+        poly(&[4, 1, 7, 0, 2, 9, 3, 5, 8, 6, 1, 12])
+    }
+
+    #[test]
+    fn sum_is_k_times_constant_term_of_remainder() {
+        // f(x) = 4 + 1·x + ... ; x^8 folds onto 1, x^9 onto x, ... so the
+        // remainder has constant term 4 + 8 = 12 and the sum is 8 · 12
+        let prover = SumcheckProver::new(domain(), f());
+        assert_eq!(prover.sum(), Fq::from(8 * 12));
+    }
+
+    #[test]
+    fn honest_prover_is_accepted() {
+        let domain = domain();
+        let f = f();
+        let prover = SumcheckProver::new(domain, f.clone());
+        let verifier = SumcheckVerifier::new(domain);
+        let sum = prover.sum();
+        let proof = prover.prove(sum).unwrap();
+
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        for _ in 0..10 {
+            let f_oracle = CountingOracle::new(f.clone());
+            let q_oracle = CountingOracle::new(proof.quotient.clone());
+            let g_oracle = CountingOracle::new(proof.g.clone());
+            let challenge = verifier.sample_challenge(&mut rng);
+            assert!(verifier.verify(&f_oracle, &q_oracle, &g_oracle, sum, challenge));
+            assert_eq!(
+                (f_oracle.queries(), q_oracle.queries(), g_oracle.queries()),
+                (1, 1, 1)
+            );
+        }
+    }
+
+    #[test]
+    fn wrong_sum_is_rejected() {
+        let domain = domain();
+        let f = f();
+        let prover = SumcheckProver::new(domain, f.clone());
+        let wrong_sum = prover.sum() + Fq::from(1);
+        assert_eq!(
+            prover.prove(wrong_sum).unwrap_err(),
+            SumcheckError::WrongSum
+        );
+
+        // The cheater sends the honest `q` and `g` for a different sum. The
+        // identity is then off by the constant `(s' - s) / k` and never holds.
+        let proof = prover.prove(prover.sum()).unwrap();
+        let verifier = SumcheckVerifier::new(domain);
+        assert!((0..41u64).all(|r| !verifier.verify(
+            &f,
+            &proof.quotient,
+            &proof.g,
+            wrong_sum,
+            Fq::from(r)
+        )));
+    }
+
+    #[test]
+    fn high_degree_g_is_rejected() {
+        // With `c = s' / k` and `p(0)` the honest constant term, shifting
+        // `q` by `c - p(0)` and setting `g = (p - p(0)) / x - (c - p(0)) x^(k-1)`
+        // satisfies the identity everywhere. Only the degree bound on `g`
+        // catches it.
+        let domain = domain();
+        let f = f();
+        let prover = SumcheckProver::new(domain, f.clone());
+        let SumcheckProof { quotient, g } = prover.prove(prover.sum()).unwrap();
+
+        let wrong_sum = prover.sum() + Fq::from(8);
+        let shift = Fq::from(1);
+        let quotient = &quotient + &Polynomial::new_from_coeffs(&[shift]);
+        let mut high = vec![Fq::from(0); 8];
+        high[7] = -shift;
+        let g = &g + &Polynomial::new_from_coeffs(&high);
+
+        let verifier = SumcheckVerifier::new(domain);
+        let r = Fq::from(5);
+        let z_h = domain.evaluate_vanishing_polynomial(r);
+        assert_eq!(
+            f.eval(r),
+            quotient.eval(r) * z_h + r * g.eval(r) + wrong_sum / Fq::from(8)
+        );
+        assert!(!verifier.verify(&f, &quotient, &g, wrong_sum, r));
+    }
+
+    #[test]
+    fn fiat_shamir_roundtrip() {
+        let domain = domain();
+        let f = f();
+        let prover = SumcheckProver::new(domain, f.clone());
+        let verifier = SumcheckVerifier::new(domain);
+        let sum = prover.sum();
+        let proof = prover.prove(sum).unwrap();
+        assert!(verifier.verify_non_interactive(&f, sum, &proof));
+
+        // A proof for `f` does not verify for a different claimed sum
+        assert!(!verifier.verify_non_interactive(&f, sum + Fq::from(1), &proof));
+
+        // Tampering with `g` changes the identity at almost every point
+        let tampered = SumcheckProof {
+            quotient: proof.quotient.clone(),
+            g: &proof.g + &poly(&[1]),
+        };
+        assert!(!verifier.verify_non_interactive(&f, sum, &tampered));
+    }
+}
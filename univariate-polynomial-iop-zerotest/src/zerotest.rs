@@ -0,0 +1,159 @@
+//! Zero-test IOP: the prover convinces the verifier that `f(x) = 0` for
+//! every `x` in a multiplicative subgroup `H` of order `k`.
+//!
+//! `f` vanishes on `H` iff the vanishing polynomial `Z_H(x) = x^k - 1`
+//! divides it, i.e. iff there is a `q` with `f = q · Z_H`. The prover sends
+//! (oracle access to) `q`, and the verifier checks the identity at a single
+//! random point `r`. If `f ≠ q · Z_H`, their difference is a non-zero
+//! polynomial of degree at most `d = deg f` and the check passes for at
+//! most `d` values of `r`, so a cheating prover is caught with probability
+//! at least `1 - d / |F|`.
+
+use finite_fields::Field;
+use polynomial::Polynomial;
+use rand::RngCore;
+
+use crate::{domain::Domain, oracle::PolynomialOracle};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZeroTestError {
+    /// `f` does not vanish on the domain, so no honest quotient exists
+    NotZeroOnDomain,
+}
+
+pub struct ZeroTestProver<F> {
+    domain: Domain<F>,
+    f: Polynomial<F>,
+}
+
+impl<F: Field> ZeroTestProver<F> {
+    pub fn new(domain: Domain<F>, f: Polynomial<F>) -> Self {
+        Self { domain, f }
+    }
+
+    /// The prover's only message: the quotient `q = f / Z_H`
+    pub fn quotient(&self) -> Result<Polynomial<F>, ZeroTestError> {
+        let (quotient, remainder) = self.f.div_rem(&self.domain.vanishing_polynomial());
+        if remainder.is_zero() {
+            Ok(quotient)
+        } else {
+            Err(ZeroTestError::NotZeroOnDomain)
+        }
+    }
+}
+
+pub struct ZeroTestVerifier<F> {
+    domain: Domain<F>,
+}
+
+impl<F: Field> ZeroTestVerifier<F> {
+    pub fn new(domain: Domain<F>) -> Self {
+        Self { domain }
+    }
+
+    /// Samples the random evaluation point `r`
+    pub fn sample_challenge<R: RngCore>(&self, rng: &mut R) -> F {
+        F::from_u64(rng.next_u64())
+    }
+
+    /// Queries both oracles at `r` and accepts iff `f(r) = q(r) · Z_H(r)`
+    /// and `q` has degree at most `deg f - k`
+    pub fn verify<Of, Oq>(&self, f: &Of, quotient: &Oq, challenge: F) -> bool
+    where
+        Of: PolynomialOracle<F>,
+        Oq: PolynomialOracle<F>,
+    {
+        quotient.degree_bound() <= f.degree_bound().saturating_sub(self.domain.size())
+            && f.query(challenge)
+                == quotient.query(challenge) * self.domain.evaluate_vanishing_polynomial(challenge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::{oracle::CountingOracle, Fq};
+
+    fn domain() -> Domain<Fq> {
+        Domain::new(Fq::from(3), 8)
+    }
+
+    fn poly(coeffs: &[u64]) -> Polynomial<Fq> {
+        Polynomial::new_from_coeffs(&coeffs.iter().map(|c| Fq::from(*c)).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn honest_prover_is_accepted() {
+        let domain = domain();
+        // f = (x^8 - 1)(x^2 + 5x + 7) vanishes on H
+        let f = &domain.vanishing_polynomial() * &poly(&[7, 5, 1]);
+
+        let prover = ZeroTestProver::new(domain, f.clone());
+        let verifier = ZeroTestVerifier::new(domain);
+        let quotient = prover.quotient().unwrap();
+
+        let mut rng = ChaCha20Rng::seed_from_u64(0);
+        for _ in 0..10 {
+            let f_oracle = CountingOracle::new(f.clone());
+            let q_oracle = CountingOracle::new(quotient.clone());
+            let challenge = verifier.sample_challenge(&mut rng);
+            assert!(verifier.verify(&f_oracle, &q_oracle, challenge));
+            assert_eq!((f_oracle.queries(), q_oracle.queries()), (1, 1));
+        }
+    }
+
+    #[test]
+    fn cheating_prover_is_rejected() {
+        let domain = domain();
+        // f = (x^8 - 1)(x^2 + 5x + 7) + x^3 + 2 is non-zero on H: e.g.
+        // f(1) = 3
+        let f = &(&domain.vanishing_polynomial() * &poly(&[7, 5, 1])) + &poly(&[2, 0, 0, 1]);
+        assert_eq!(
+            ZeroTestProver::new(domain, f.clone())
+                .quotient()
+                .unwrap_err(),
+            ZeroTestError::NotZeroOnDomain
+        );
+
+        // The cheater drops the remainder and sends `q = x^2 + 5x + 7`.
+        // The verifier is fooled only when `r^3 + 2 = 0`, which has at
+        // most 3 solutions among the 41 possible challenges.
+        let cheating_quotient = f.div_rem(&domain.vanishing_polynomial()).0;
+        let verifier = ZeroTestVerifier::new(domain);
+        let fooled = (0..41u64)
+            .filter(|r| verifier.verify(&f, &cheating_quotient, Fq::from(*r)))
+            .count();
+        assert!(fooled <= 3);
+
+        // A random challenge catches it here
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        let challenge = verifier.sample_challenge(&mut rng);
+        assert!(!verifier.verify(&f, &cheating_quotient, challenge));
+    }
+
+    #[test]
+    fn inconsistent_oracle_is_rejected() {
+        // An oracle whose answers are not the evaluations of the committed
+        // polynomial breaks the identity at the queried point
+        struct LyingOracle;
+        impl PolynomialOracle<Fq> for LyingOracle {
+            fn query(&self, _point: Fq) -> Fq {
+                Fq::from(1)
+            }
+
+            fn degree_bound(&self) -> usize {
+                0
+            }
+        }
+
+        let domain = domain();
+        let verifier = ZeroTestVerifier::new(domain);
+        let f = &domain.vanishing_polynomial() * &poly(&[1, 1]);
+        let quotient = ZeroTestProver::new(domain, f.clone()).quotient().unwrap();
+        assert!(!verifier.verify(&f, &LyingOracle, Fq::from(2)));
+        assert!(!verifier.verify(&LyingOracle, &quotient, Fq::from(2)));
+    }
+}